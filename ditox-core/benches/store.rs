@@ -0,0 +1,144 @@
+//! Store hot paths: `cargo bench -p ditox-core`
+//!
+//! Histories are generated with [`seed`] into temporary databases; image
+//! blobs go to a temporary data directory.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use ditox_core::config::SortOrder;
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use ditox_core::query::Query;
+use ditox_core::seed::{seed, SeedOptions};
+use std::hint::black_box;
+use std::io::Cursor;
+use std::sync::OnceLock;
+use tempfile::TempDir;
+
+/// Rows in the history that list and search run against
+const HISTORY_ROWS: usize = 100_000;
+
+/// Side of the square PNGs ingested by the image benchmark
+const IMAGE_SIDE: u32 = 512;
+
+/// A fresh database, with the image store in a temporary data directory
+fn open() -> (TempDir, Database) {
+    static DATA_DIR: OnceLock<TempDir> = OnceLock::new();
+    let data_dir = DATA_DIR.get_or_init(|| TempDir::new().unwrap());
+    Database::override_data_dir(data_dir.path().to_path_buf());
+
+    let dir = TempDir::new().unwrap();
+    let db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    (dir, db)
+}
+
+fn seeded(clips: usize) -> (TempDir, Database) {
+    let (dir, db) = open();
+    let options = SeedOptions {
+        clips,
+        images: 0,
+        ..SeedOptions::default()
+    };
+    db.in_transaction(|db| seed(db, &options)).unwrap();
+    (dir, db)
+}
+
+/// A PNG that differs from the one for every other `n`
+fn png(n: u32) -> Vec<u8> {
+    let img = image::RgbaImage::from_fn(IMAGE_SIDE, IMAGE_SIDE, |x, y| {
+        if (x, y) == (0, 0) {
+            image::Rgba(n.to_le_bytes())
+        } else {
+            image::Rgba([(x % 256) as u8, (y % 256) as u8, ((x ^ y) % 256) as u8, 255])
+        }
+    });
+    let mut bytes = Vec::new();
+    img.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)
+        .unwrap();
+    bytes
+}
+
+fn add(c: &mut Criterion) {
+    let (_dir, db) = seeded(10_000);
+    let mut n = 0;
+    c.bench_function("add/text", |b| {
+        b.iter_batched(
+            || {
+                n += 1;
+                Entry::new_text(format!("benchmark clip number {}", n))
+            },
+            |entry| db.insert(&entry).unwrap(),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn history(c: &mut Criterion) {
+    let (_dir, mut db) = seeded(HISTORY_ROWS);
+
+    let mut group = c.benchmark_group("list");
+    group.bench_function("first_page", |b| {
+        b.iter(|| db.get_page(0, black_box(100)).unwrap())
+    });
+    group.bench_function("deep_page", |b| {
+        b.iter(|| db.get_page(black_box(HISTORY_ROWS / 2), 100).unwrap())
+    });
+    group.bench_function("most_used", |b| {
+        b.iter(|| {
+            db.get_page_sorted(0, black_box(100), "all", None, SortOrder::MostUsed)
+                .unwrap()
+        })
+    });
+    // Seeded without images: the whole tab is looked through for none
+    group.bench_function("images", |b| {
+        b.iter(|| {
+            db.get_page_filtered(0, black_box(100), "image", None)
+                .unwrap()
+        })
+    });
+    group.bench_function("count", |b| {
+        b.iter(|| db.count_query(&Query::default()).unwrap())
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("search");
+    group.bench_function("words", |b| {
+        b.iter(|| db.search_entries(black_box("deploy lat"), 50).unwrap())
+    });
+    // No index can help a regex: every text entry is scanned
+    group.bench_function("regex_scan", |b| {
+        let query = Query::regex("deplo+y");
+        b.iter(|| db.query(&query, 50).unwrap())
+    });
+    db.enable_trigram_index().unwrap();
+    group.bench_function("substring", |b| {
+        b.iter(|| db.search_entries(black_box("ploy"), 50).unwrap())
+    });
+    group.finish();
+}
+
+fn image_ingest(c: &mut Criterion) {
+    let (_dir, db) = open();
+    let mut n = 0;
+    c.bench_function("add/image", |b| {
+        b.iter_batched(
+            || {
+                n += 1;
+                png(n)
+            },
+            |bytes| {
+                // What the watcher does with a new clipboard image
+                let hash = Entry::compute_hash(&bytes);
+                if !db.exists_by_hash(&hash).unwrap() {
+                    Database::store_image_blob(&hash, "png", &bytes).unwrap();
+                    db.insert(&Entry::new_image(hash, bytes.len(), "png".to_string()))
+                        .unwrap();
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, add, history, image_ingest);
+criterion_main!(benches);
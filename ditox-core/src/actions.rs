@@ -22,7 +22,9 @@ pub enum Action {
     // Operations
     Copy,
     CopyAndQuit,
+    CopyAndType,
     Delete,
+    UndoDelete,
     ClearAll,
     ToggleFavorite,
     Refresh,
@@ -33,11 +35,13 @@ pub enum Action {
     TogglePreview,
     ToggleExpanded,
     ToggleHelp,
+    ShowDetails,
 
     // Multi-select
     ToggleMultiSelect,
     SelectCurrent,
     SelectAll,
+    MergeSelected,
 
     // Future features (Phase 2-4)
     // Regex Search (#10)
@@ -50,6 +54,7 @@ pub enum Action {
     // Preview Modes (#18)
     CyclePreviewMode,
     ToggleLineNumbers,
+    CycleSort,
 
     // Tabs (#20)
     NextTab,
@@ -66,8 +71,22 @@ pub enum Action {
     QuickSlot8,
     QuickSlot9,
 
+    // Saved views
+    View1,
+    View2,
+    View3,
+    View4,
+    View5,
+    View6,
+    View7,
+    View8,
+    View9,
+
     // Annotations (#13)
     EditAnnotation,
+    EditInEditor,
+    EditInEditorAsNew,
+    EditTags,
 
     // Statistics (#8)
     ShowStats,
@@ -95,7 +114,9 @@ impl Action {
             // Operations
             Action::Copy => "Copy to clipboard",
             Action::CopyAndQuit => "Copy and quit",
+            Action::CopyAndType => "Copy, quit and type the clip into the focused window",
             Action::Delete => "Delete entry",
+            Action::UndoDelete => "Restore the last deleted entries",
             Action::ClearAll => "Clear all entries",
             Action::ToggleFavorite => "Toggle favorite status",
             Action::Refresh => "Refresh entries",
@@ -106,17 +127,20 @@ impl Action {
             Action::TogglePreview => "Toggle preview pane",
             Action::ToggleExpanded => "Toggle expanded preview",
             Action::ToggleHelp => "Toggle help",
+            Action::ShowDetails => "Show clip details",
 
             // Multi-select
             Action::ToggleMultiSelect => "Toggle multi-select mode",
             Action::SelectCurrent => "Select/deselect current",
             Action::SelectAll => "Select/deselect all",
+            Action::MergeSelected => "Merge selected into a new clip",
 
             // Future features
             Action::EnterRegexSearch => "Start regex search",
             Action::ToggleSearchMode => "Toggle search mode (fuzzy/regex)",
             Action::ShowActions => "Show contextual actions",
             Action::CyclePreviewMode => "Cycle preview mode",
+            Action::CycleSort => "Cycle sort order",
             Action::ToggleLineNumbers => "Toggle line numbers",
             Action::NextTab => "Next tab",
             Action::PrevTab => "Previous tab",
@@ -129,7 +153,19 @@ impl Action {
             Action::QuickSlot7 => "Quick slot 7",
             Action::QuickSlot8 => "Quick slot 8",
             Action::QuickSlot9 => "Quick slot 9",
+            Action::View1 => "Saved view 1",
+            Action::View2 => "Saved view 2",
+            Action::View3 => "Saved view 3",
+            Action::View4 => "Saved view 4",
+            Action::View5 => "Saved view 5",
+            Action::View6 => "Saved view 6",
+            Action::View7 => "Saved view 7",
+            Action::View8 => "Saved view 8",
+            Action::View9 => "Saved view 9",
             Action::EditAnnotation => "Edit annotation",
+            Action::EditInEditor => "Edit clip in $EDITOR",
+            Action::EditInEditorAsNew => "Edit clip in $EDITOR, saving a new clip",
+            Action::EditTags => "Edit tags",
             Action::ShowStats => "Show statistics",
 
             // System
@@ -151,7 +187,9 @@ impl Action {
             Action::NextPage => "next_page",
             Action::Copy => "copy",
             Action::CopyAndQuit => "copy_and_quit",
+            Action::CopyAndType => "copy_and_type",
             Action::Delete => "delete",
+            Action::UndoDelete => "undo_delete",
             Action::ClearAll => "clear_all",
             Action::ToggleFavorite => "toggle_favorite",
             Action::Refresh => "refresh",
@@ -160,13 +198,16 @@ impl Action {
             Action::TogglePreview => "toggle_preview",
             Action::ToggleExpanded => "toggle_expanded",
             Action::ToggleHelp => "toggle_help",
+            Action::ShowDetails => "show_details",
             Action::ToggleMultiSelect => "toggle_multi_select",
             Action::SelectCurrent => "select_current",
             Action::SelectAll => "select_all",
+            Action::MergeSelected => "merge_selected",
             Action::EnterRegexSearch => "enter_regex_search",
             Action::ToggleSearchMode => "toggle_search_mode",
             Action::ShowActions => "show_actions",
             Action::CyclePreviewMode => "cycle_preview_mode",
+            Action::CycleSort => "cycle_sort",
             Action::ToggleLineNumbers => "toggle_line_numbers",
             Action::NextTab => "next_tab",
             Action::PrevTab => "prev_tab",
@@ -179,7 +220,19 @@ impl Action {
             Action::QuickSlot7 => "quick_slot_7",
             Action::QuickSlot8 => "quick_slot_8",
             Action::QuickSlot9 => "quick_slot_9",
+            Action::View1 => "view_1",
+            Action::View2 => "view_2",
+            Action::View3 => "view_3",
+            Action::View4 => "view_4",
+            Action::View5 => "view_5",
+            Action::View6 => "view_6",
+            Action::View7 => "view_7",
+            Action::View8 => "view_8",
+            Action::View9 => "view_9",
             Action::EditAnnotation => "edit_annotation",
+            Action::EditInEditor => "edit_in_editor",
+            Action::EditInEditorAsNew => "edit_in_editor_as_new",
+            Action::EditTags => "edit_tags",
             Action::ShowStats => "show_stats",
             Action::Quit => "quit",
             Action::ForceQuit => "force_quit",
@@ -199,7 +252,9 @@ impl Action {
             "next_page" => Some(Action::NextPage),
             "copy" => Some(Action::Copy),
             "copy_and_quit" => Some(Action::CopyAndQuit),
+            "copy_and_type" => Some(Action::CopyAndType),
             "delete" => Some(Action::Delete),
+            "undo_delete" => Some(Action::UndoDelete),
             "clear_all" => Some(Action::ClearAll),
             "toggle_favorite" | "toggle_pin" => Some(Action::ToggleFavorite), // Support legacy "toggle_pin"
             "refresh" => Some(Action::Refresh),
@@ -208,13 +263,16 @@ impl Action {
             "toggle_preview" => Some(Action::TogglePreview),
             "toggle_expanded" => Some(Action::ToggleExpanded),
             "toggle_help" => Some(Action::ToggleHelp),
+            "show_details" => Some(Action::ShowDetails),
             "toggle_multi_select" => Some(Action::ToggleMultiSelect),
             "select_current" => Some(Action::SelectCurrent),
             "select_all" => Some(Action::SelectAll),
+            "merge_selected" => Some(Action::MergeSelected),
             "enter_regex_search" => Some(Action::EnterRegexSearch),
             "toggle_search_mode" => Some(Action::ToggleSearchMode),
             "show_actions" => Some(Action::ShowActions),
             "cycle_preview_mode" => Some(Action::CyclePreviewMode),
+            "cycle_sort" => Some(Action::CycleSort),
             "toggle_line_numbers" => Some(Action::ToggleLineNumbers),
             "next_tab" => Some(Action::NextTab),
             "prev_tab" => Some(Action::PrevTab),
@@ -227,7 +285,19 @@ impl Action {
             "quick_slot_7" => Some(Action::QuickSlot7),
             "quick_slot_8" => Some(Action::QuickSlot8),
             "quick_slot_9" => Some(Action::QuickSlot9),
+            "view_1" => Some(Action::View1),
+            "view_2" => Some(Action::View2),
+            "view_3" => Some(Action::View3),
+            "view_4" => Some(Action::View4),
+            "view_5" => Some(Action::View5),
+            "view_6" => Some(Action::View6),
+            "view_7" => Some(Action::View7),
+            "view_8" => Some(Action::View8),
+            "view_9" => Some(Action::View9),
             "edit_annotation" => Some(Action::EditAnnotation),
+            "edit_in_editor" => Some(Action::EditInEditor),
+            "edit_in_editor_as_new" => Some(Action::EditInEditorAsNew),
+            "edit_tags" => Some(Action::EditTags),
             "show_stats" => Some(Action::ShowStats),
             "quit" => Some(Action::Quit),
             "force_quit" => Some(Action::ForceQuit),
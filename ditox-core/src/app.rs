@@ -1,32 +1,87 @@
 use crate::clipboard::Clipboard;
-use crate::config::Config;
+use crate::config::{Config, SortOrder};
+use crate::confirm::{ConfirmStep, ConfirmStyle, ConfirmTracker, CONFIRM_PHRASE};
 use crate::db::Database;
-use crate::entry::Entry;
+use crate::entry::{Entry, EntryType};
 use crate::error::Result;
+use crate::hooks::{self, HookEvent};
+use crate::i18n::{t, tf};
+use crate::query::{Query, SearchHit};
+use crate::undo::{self, DeletedEntry};
+use crate::watcher::{self, WatcherStatus};
+use chrono::Local;
 use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
 use nucleo_matcher::{Config as MatcherConfig, Matcher};
 use regex::RegexBuilder;
-use std::collections::{HashMap, HashSet};
-use std::time::Instant;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant};
 
 /// Number of entries per page
 const PAGE_SIZE: usize = 20;
+/// Rows in the search box's tag completion popup
+const MAX_TAG_COMPLETIONS: usize = 8;
+/// How long a delete can be undone with `u`
+pub const UNDO_WINDOW: Duration = Duration::from_secs(10);
+/// How long a toast stays up
+const MESSAGE_TTL: Duration = Duration::from_secs(2);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InputMode {
     Normal,
     Search,
     EditNote,
+    EditTags,
     Confirm,
 }
 
-/// Action pending confirmation
+pub use crate::confirm::ConfirmAction;
+
+/// Everything the detail view shows about a clip
+#[derive(Debug, Clone)]
+pub struct EntryDetails {
+    pub entry: Entry,
+    pub tags: Vec<String>,
+    pub meta: BTreeMap<String, String>,
+    pub usage_count: i64,
+}
+
+/// Header a grouped list puts entries under
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ConfirmAction {
-    /// Delete the currently selected entry
-    DeleteSelected,
-    /// Delete all entries (clear history)
-    ClearAll,
+pub enum DayGroup {
+    Today,
+    Yesterday,
+    LastWeek,
+    Older,
+}
+
+impl DayGroup {
+    pub fn label(self) -> String {
+        match self {
+            DayGroup::Today => t("group.today"),
+            DayGroup::Yesterday => t("group.yesterday"),
+            DayGroup::LastWeek => t("group.last_week"),
+            DayGroup::Older => t("group.older"),
+        }
+    }
+}
+
+/// Display name of a sort order
+pub fn sort_label(sort: SortOrder) -> String {
+    match sort {
+        SortOrder::Recent => t("sort.recent"),
+        SortOrder::Created => t("sort.created"),
+        SortOrder::MostUsed => t("sort.most_used"),
+        SortOrder::Largest => t("sort.largest"),
+    }
+}
+
+/// What to do with a clip edited in `$EDITOR`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditMode {
+    /// Replace the clip's text
+    InPlace,
+    /// Keep the clip and save the edited text as a new one
+    AsNew,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -92,22 +147,26 @@ pub enum TabFilter {
     /// Show entries in a specific collection
     #[allow(dead_code)]
     Collection(String), // collection_id
+    /// Show entries matching a saved search ("view")
+    View { name: String, query: Query },
 }
 
 impl TabFilter {
     /// Get the display label for the tab
     pub fn label(&self) -> String {
         match self {
-            TabFilter::All => "All".to_string(),
-            TabFilter::Text => "Text".to_string(),
-            TabFilter::Images => "Images".to_string(),
-            TabFilter::Favorites => "Favorites".to_string(),
-            TabFilter::Today => "Today".to_string(),
+            TabFilter::All => t("tab.all"),
+            TabFilter::Text => t("tab.text"),
+            TabFilter::Images => t("tab.images"),
+            TabFilter::Favorites => t("tab.favorites"),
+            TabFilter::Today => t("tab.today"),
             TabFilter::Collection(name) => name.clone(),
+            TabFilter::View { name, .. } => name.clone(),
         }
     }
 
-    /// Get the DB filter string and optional collection_id
+    /// Get the DB filter string and optional collection_id. Views have no
+    /// filter string; they run their `Query` instead (see `App`).
     pub fn db_filter(&self) -> (&'static str, Option<&str>) {
         match self {
             TabFilter::All => ("all", None),
@@ -116,6 +175,7 @@ impl TabFilter {
             TabFilter::Favorites => ("favorite", None),
             TabFilter::Today => ("today", None),
             TabFilter::Collection(id) => ("collection", Some(id.as_str())),
+            TabFilter::View { .. } => ("all", None),
         }
     }
 }
@@ -136,22 +196,55 @@ pub struct App {
     pub show_preview: bool,
     pub show_expanded: bool,
     pub show_help: bool,
+    /// Detail view of the selected clip, while open
+    pub details: Option<EntryDetails>,
+    /// Lines the detail view's text is scrolled down by
+    pub details_scroll: usize,
     pub should_quit: bool,
     pub should_copy_and_quit: bool,
+    /// Type the copied clip into the focused window once the TUI has
+    /// closed (see `Clipboard::type_text`)
+    pub type_after_copy: bool,
+    /// Set by the edit actions; the UI loop then opens the selected clip
+    /// in `$EDITOR` and calls [`App::apply_edit`]
+    pub edit_requested: Option<EditMode>,
+    /// Picker mode (`ditox pick`): choosing an entry puts it in `picked`
+    /// instead of copying it
+    pub pick_mode: bool,
+    /// Entries chosen in picker mode, in selection order
+    pub picked: Vec<Entry>,
     pub message: Option<String>,
     /// Timestamp when message was set (for timeout)
     pub message_time: Option<Instant>,
+    /// How long the current message stays up
+    message_ttl: Duration,
+    /// Clips removed by the last delete and when, until the undo window
+    /// closes
+    undo: Option<(Vec<DeletedEntry>, Instant)>,
     /// Current terminal height for page navigation calculations
     pub terminal_height: u16,
     /// Match indices for each filtered entry (entry_idx -> char indices that matched)
     pub match_indices: HashMap<usize, Vec<u32>>,
+    /// Full-text search hit for each loaded entry (entry_idx -> snippet and
+    /// match offset), only populated while searching
+    pub search_hits: HashMap<usize, SearchHit>,
+    /// Tags (name, entry count) completing the `#`/`tag:` word being typed
+    /// in the search box; empty when no completion is offered
+    pub tag_completions: Vec<(String, usize)>,
+    /// Highlighted row in `tag_completions`
+    pub tag_completion_selected: usize,
     /// Multi-select mode enabled
     pub multi_select_mode: bool,
-    /// Set of selected entry indices (indices into filtered, not entries)
-    pub multi_selected: HashSet<usize>,
+    /// Selected entry indices in the order they were selected (indices
+    /// into filtered, not entries)
+    pub multi_selected: Vec<usize>,
     /// Note input buffer (for EditNote mode)
     pub note_input: String,
-    /// Entry ID being edited (for EditNote mode)
+    /// Tag input buffer (for EditTags mode)
+    pub tag_input: String,
+    /// Current tags of the entry being tagged (for EditTags mode)
+    pub editing_tags: Vec<String>,
+    /// Entry ID being edited (for EditNote and EditTags modes)
     pub editing_entry_id: Option<String>,
     /// Current preview pane mode
     pub preview_mode: PreviewMode,
@@ -169,10 +262,30 @@ pub struct App {
     pub show_tabs: bool,
     /// Show line numbers in preview
     pub show_line_numbers: bool,
+    /// Order of the list when not searching
+    pub sort: SortOrder,
+    /// Show day headers in the list (see [`App::day_group`])
+    pub group_by_day: bool,
     /// Last refresh timestamp for status bar display
     pub last_refresh: Instant,
+    /// Clips added by other processes since the list was last at its top,
+    /// shown while scrolled down
+    pub new_clips: usize,
+    /// Database `data_version` when entries were last loaded
+    data_version: i64,
+    /// When the search box last changed without the search being run yet
+    /// (see `ui.search_debounce_ms`)
+    search_pending_since: Option<Instant>,
+    /// Watcher status for the status bar, refreshed with `refresh_watcher_status`
+    pub watcher_status: WatcherStatus,
     /// Action pending confirmation (for delete confirmations)
     pub pending_confirm: Option<ConfirmAction>,
+    /// Dialog style of the pending confirmation (Modal or Typed)
+    pub confirm_style: ConfirmStyle,
+    /// Text typed into a Typed confirmation dialog
+    pub confirm_input: String,
+    /// Double-press state for the confirmation policy
+    pub confirm_tracker: ConfirmTracker,
 
     // Pagination state
     /// Total count of entries in database (or matching search query)
@@ -189,9 +302,24 @@ impl App {
     pub fn new(db: Database, config: Config) -> Result<Self> {
         // Load first page
         let total_count = db.count()?;
-        let entries = db.get_page(0, PAGE_SIZE)?;
+        let sort = config.ui.sort;
+        let entries = db.get_page_sorted(0, PAGE_SIZE, "all", None, sort)?;
         let filtered: Vec<usize> = (0..entries.len()).collect();
 
+        // Built-in tabs, then saved searches in the order they were created
+        let mut tabs = vec![
+            TabFilter::All,
+            TabFilter::Text,
+            TabFilter::Images,
+            TabFilter::Favorites,
+            TabFilter::Today,
+        ];
+        tabs.extend(
+            db.get_saved_searches()?
+                .into_iter()
+                .map(|(name, query)| TabFilter::View { name, query }),
+        );
+
         Ok(Self {
             entries,
             filtered,
@@ -203,32 +331,48 @@ impl App {
             show_preview: config.ui.show_preview,
             show_expanded: false,
             show_help: false,
+            details: None,
+            details_scroll: 0,
             should_quit: false,
             should_copy_and_quit: false,
+            type_after_copy: false,
+            edit_requested: None,
+            pick_mode: false,
+            picked: Vec::new(),
             message: None,
             message_time: None,
+            message_ttl: MESSAGE_TTL,
+            undo: None,
             terminal_height: 24, // Default, will be updated on first draw
             match_indices: HashMap::new(),
+            search_hits: HashMap::new(),
+            tag_completions: Vec::new(),
+            tag_completion_selected: 0,
             multi_select_mode: false,
-            multi_selected: HashSet::new(),
+            multi_selected: Vec::new(),
             note_input: String::new(),
+            tag_input: String::new(),
+            editing_tags: Vec::new(),
             editing_entry_id: None,
             preview_mode: PreviewMode::default(),
             preview_scroll_offset: 0,
             snippet_slots: [None, None, None, None, None, None, None, None, None],
             show_snippets: true,
-            tabs: vec![
-                TabFilter::All,
-                TabFilter::Text,
-                TabFilter::Images,
-                TabFilter::Favorites,
-                TabFilter::Today,
-            ],
+            tabs,
             active_tab: 0,
             show_tabs: true,
             show_line_numbers: false,
+            sort,
+            group_by_day: config.ui.group_by_day,
             last_refresh: Instant::now(),
+            new_clips: 0,
+            data_version: db.data_version()?,
+            search_pending_since: None,
+            watcher_status: watcher::watcher_status(),
             pending_confirm: None,
+            confirm_style: ConfirmStyle::Modal,
+            confirm_input: String::new(),
+            confirm_tracker: ConfirmTracker::default(),
             total_count,
             current_page: 0,
             db,
@@ -237,17 +381,21 @@ impl App {
         })
     }
 
+    /// Re-read the watcher status (touches the PID and health files)
+    pub fn refresh_watcher_status(&mut self) {
+        self.watcher_status = watcher::watcher_status();
+    }
+
     /// Reload entries for current page (used for refresh and after modifications)
     pub fn reload_entries(&mut self) -> Result<()> {
         self.last_refresh = Instant::now();
 
         // Get filter for current tab
         let filter = self.active_tab_filter().clone();
-        let (filter_str, collection_id) = filter.db_filter();
 
         if self.search_query.is_empty() {
             // Get filtered count for correct pagination
-            self.total_count = self.db.count_filtered(filter_str, collection_id)?;
+            self.total_count = self.count_tab(&filter)?;
 
             // Ensure current page is still valid
             let total_pages = self.total_pages();
@@ -257,11 +405,10 @@ impl App {
 
             // Load current page with filter
             let offset = self.current_page * PAGE_SIZE;
-            self.entries =
-                self.db
-                    .get_page_filtered(offset, PAGE_SIZE, filter_str, collection_id)?;
+            self.entries = self.load_tab_page(&filter, offset)?;
             self.filtered = (0..self.entries.len()).collect();
             self.match_indices.clear();
+            self.search_hits.clear();
         } else {
             // With search: reload search results (filtering handled in search)
             self.load_search_results()?;
@@ -286,15 +433,13 @@ impl App {
 
         // Get filter for current tab
         let filter = self.active_tab_filter().clone();
-        let (filter_str, collection_id) = filter.db_filter();
 
         self.current_page = page;
         let offset = page * PAGE_SIZE;
-        self.entries = self
-            .db
-            .get_page_filtered(offset, PAGE_SIZE, filter_str, collection_id)?;
+        self.entries = self.load_tab_page(&filter, offset)?;
         self.filtered = (0..self.entries.len()).collect();
         self.match_indices.clear();
+        self.search_hits.clear();
         self.selected = 0; // Reset selection to top of new page
         self.multi_selected.clear(); // Clear multi-selection on page change
 
@@ -315,13 +460,26 @@ impl App {
         self.current_page + 1
     }
 
-    /// Load search results using DB pre-filtering + in-memory matching
+    /// Load search results using DB pre-filtering + in-memory matching.
+    /// The FTS pre-filter also records a `SearchHit` per entry so the list
+    /// and preview can show where long entries matched. In fuzzy mode the
+    /// search box speaks the query language (`tag:`, `is:fav`, ...); a query
+    /// with only filters loads the filtered entries without hits.
     fn load_search_results(&mut self) -> Result<()> {
         // Use DB LIKE to pre-filter, then apply search mode specific matching
         let max_search_results = self.config.general.max_entries;
-        self.entries = self
-            .db
-            .search_entries(&self.search_query, max_search_results)?;
+        let query = self.parsed_search_query();
+        self.search_hits.clear();
+        if query.text.is_some() {
+            let hits = self.db.search_hits(&query, max_search_results)?;
+            self.entries = Vec::with_capacity(hits.len());
+            for (idx, (entry, hit)) in hits.into_iter().enumerate() {
+                self.entries.push(entry);
+                self.search_hits.insert(idx, hit);
+            }
+        } else {
+            self.entries = self.db.query(&query, max_search_results)?;
+        }
         self.total_count = self.entries.len();
         self.current_page = 0; // Reset to first page for search results
 
@@ -336,6 +494,7 @@ impl App {
 
     /// Filter entries - for search, loads from DB; for no search, shows loaded entries
     pub fn filter_entries(&mut self) {
+        self.search_pending_since = None;
         self.match_indices.clear();
 
         if self.search_query.is_empty() {
@@ -359,20 +518,29 @@ impl App {
         }
     }
 
+    /// The search box as a `Query`. Regex mode keeps the raw input as the
+    /// full-text pre-filter, since regex syntax isn't the query language.
+    fn parsed_search_query(&self) -> Query {
+        match self.search_mode {
+            SearchMode::Fuzzy => Query::parse_or_text(&self.search_query),
+            SearchMode::Regex => Query {
+                text: Some(self.search_query.clone()),
+                ..Query::default()
+            },
+        }
+    }
+
     /// Apply fuzzy matching on already-loaded entries (for scoring and highlighting)
     fn apply_fuzzy_filter(&mut self) {
         self.match_indices.clear();
 
-        if self.search_query.is_empty() {
+        // Operators were applied by the database; only free text is ranked
+        let Some(text) = Query::parse_or_text(&self.search_query).plain_text() else {
             self.filtered = (0..self.entries.len()).collect();
             return;
-        }
+        };
 
-        let pattern = Pattern::parse(
-            &self.search_query,
-            CaseMatching::Ignore,
-            Normalization::Smart,
-        );
+        let pattern = Pattern::parse(&text, CaseMatching::Ignore, Normalization::Smart);
 
         let mut matches: Vec<(usize, u32, Vec<u32>)> = self
             .entries
@@ -492,12 +660,13 @@ impl App {
 
         // Re-filter with new mode
         self.filter_entries();
+        self.update_tag_completions();
 
         let mode_name = match self.search_mode {
             SearchMode::Fuzzy => "Fuzzy",
             SearchMode::Regex => "Regex",
         };
-        self.set_message(format!("Search mode: {}", mode_name));
+        self.set_message(tf("toast.search_mode", &[("mode", &mode_name)]));
     }
 
     /// Enter regex search mode directly
@@ -505,7 +674,7 @@ impl App {
         self.search_mode = SearchMode::Regex;
         self.regex_error = None;
         self.input_mode = InputMode::Search;
-        self.set_message("Regex search mode");
+        self.set_message(t("toast.regex_search_mode"));
     }
 
     pub fn selected_entry(&self) -> Option<&Entry> {
@@ -518,6 +687,9 @@ impl App {
         if self.selected > 0 {
             self.selected -= 1;
         }
+        if self.at_top() {
+            self.new_clips = 0;
+        }
         // Don't go to previous page - stay on current page
     }
 
@@ -530,6 +702,47 @@ impl App {
 
     pub fn go_top(&mut self) {
         self.selected = 0;
+        if self.current_page == 0 {
+            self.new_clips = 0;
+        }
+    }
+
+    /// True if the newest clip is the selected one
+    pub fn at_top(&self) -> bool {
+        self.selected == 0 && self.current_page == 0
+    }
+
+    /// Reload if another process changed the database since entries were
+    /// loaded, keeping the same clip selected. Clips that arrive while the
+    /// list is scrolled down are counted in `new_clips`. Returns true if
+    /// anything changed.
+    pub fn check_for_changes(&mut self) -> Result<bool> {
+        let version = self.db.data_version()?;
+        if version == self.data_version {
+            return Ok(false);
+        }
+        self.data_version = version;
+
+        let selected_id = self.selected_entry().map(|e| e.id.clone());
+        let was_at_top = self.at_top();
+        let old_total = self.total_count;
+        self.reload_entries()?;
+        if self.search_query.is_empty() {
+            let added = self.total_count.saturating_sub(old_total);
+            if was_at_top {
+                self.new_clips = 0;
+            } else {
+                self.new_clips += added;
+                if let Some(pos) = selected_id.and_then(|id| {
+                    self.filtered
+                        .iter()
+                        .position(|&idx| self.entries[idx].id == id)
+                }) {
+                    self.selected = pos;
+                }
+            }
+        }
+        Ok(true)
     }
 
     pub fn go_bottom(&mut self) {
@@ -540,6 +753,9 @@ impl App {
     pub fn prev_page(&mut self) {
         if self.search_query.is_empty() && self.current_page > 0 {
             let _ = self.load_page(self.current_page - 1);
+            if self.at_top() {
+                self.new_clips = 0;
+            }
         }
     }
 
@@ -563,13 +779,13 @@ impl App {
     }
 
     pub fn copy_selected(&mut self) -> Result<()> {
-        if let Some(entry) = self.selected_entry() {
+        if let Some(entry) = self.selected_entry().cloned() {
             let id = entry.id.clone();
             let preview = entry.preview(30);
             match entry.entry_type {
                 crate::entry::EntryType::Text => {
                     Clipboard::set_text(&entry.content)?;
-                    self.set_message(format!("Copied: {}", preview));
+                    self.set_message(tf("toast.copied", &[("preview", &preview)]));
                 }
                 crate::entry::EntryType::Image => {
                     // `entry.content` is the content-addressable hash now;
@@ -578,9 +794,10 @@ impl App {
                         crate::error::DitoxError::Other("image entry missing extension".into())
                     })?;
                     Clipboard::set_image(&path.to_string_lossy())?;
-                    self.set_message(format!("Copied image: {}", preview));
+                    self.set_message(tf("toast.copied_image", &[("preview", &preview)]));
                 }
             }
+            hooks::spawn(&self.config.hooks, HookEvent::Copy, &entry);
             // Update last_used timestamp
             self.db.touch(&id)?;
         }
@@ -590,19 +807,82 @@ impl App {
     pub fn delete_selected(&mut self) -> Result<()> {
         if let Some(entry) = self.selected_entry() {
             let id = entry.id.clone();
-            self.db.delete(&id)?;
+            let deleted = undo::delete_restorable(&mut self.db, &id)?;
+            if let Some(deleted) = &deleted {
+                hooks::spawn(&self.config.hooks, HookEvent::Delete, &deleted.entry);
+            }
             self.reload_entries()?;
-            self.set_message("Entry deleted");
+            self.offer_undo(deleted.into_iter().collect(), t("toast.entry_deleted"));
+        }
+        Ok(())
+    }
+
+    /// Keep deleted clips restorable for `UNDO_WINDOW` and say so
+    fn offer_undo(&mut self, deleted: Vec<DeletedEntry>, msg: String) {
+        if deleted.is_empty() {
+            return;
+        }
+        self.undo = Some((deleted, Instant::now()));
+        self.set_message(msg);
+        self.message_ttl = UNDO_WINDOW;
+    }
+
+    /// Restore the clips removed by the last delete, if it was less than
+    /// `UNDO_WINDOW` ago
+    pub fn undo_delete(&mut self) -> Result<()> {
+        let Some((deleted, at)) = self.undo.take() else {
+            self.set_message(t("toast.nothing_to_undo"));
+            return Ok(());
+        };
+        if at.elapsed() >= UNDO_WINDOW {
+            self.set_message(t("toast.nothing_to_undo"));
+            return Ok(());
+        }
+        let mut restored = 0;
+        for entry in &deleted {
+            if undo::restore(&self.db, entry)? {
+                restored += 1;
+            }
         }
+        self.reload_entries()?;
+        self.set_message(tf("toast.restored", &[("count", &restored)]));
         Ok(())
     }
 
+    /// Open the detail view for the selected clip
+    pub fn open_details(&mut self) -> Result<()> {
+        let Some(entry) = self.selected_entry().cloned() else {
+            return Ok(());
+        };
+        self.details = Some(EntryDetails {
+            tags: self.db.get_tags(&entry.id)?,
+            meta: self.db.get_all_meta(&entry.id)?,
+            usage_count: self.db.usage_count(&entry.id)?,
+            entry,
+        });
+        self.details_scroll = 0;
+        Ok(())
+    }
+
+    pub fn close_details(&mut self) {
+        self.details = None;
+    }
+
+    /// Scroll the detail view's text by `lines`, staying within the text
+    pub fn scroll_details(&mut self, lines: isize) {
+        let Some(details) = &self.details else {
+            return;
+        };
+        let max = details.entry.content.lines().count().saturating_sub(1);
+        self.details_scroll = self.details_scroll.saturating_add_signed(lines).min(max);
+    }
+
     pub fn toggle_favorite(&mut self) -> Result<()> {
         if let Some(entry) = self.selected_entry() {
             let id = entry.id.clone();
             self.db.toggle_favorite(&id)?;
             self.reload_entries()?;
-            self.set_message("Favorite toggled");
+            self.set_message(t("toast.favorite_toggled"));
         }
         Ok(())
     }
@@ -610,7 +890,7 @@ impl App {
     pub fn clear_all(&mut self) -> Result<()> {
         self.db.clear_all()?;
         self.reload_entries()?;
-        self.set_message("All entries cleared");
+        self.set_message(t("toast.all_cleared"));
         Ok(())
     }
 
@@ -619,7 +899,9 @@ impl App {
     }
 
     pub fn end_search(&mut self) {
+        self.flush_search();
         self.input_mode = InputMode::Normal;
+        self.tag_completions.clear();
     }
 
     pub fn clear_search(&mut self) {
@@ -629,14 +911,149 @@ impl App {
         self.end_search();
     }
 
+    /// Replace the search box with `query` and search now
+    pub fn set_search(&mut self, query: &str) {
+        self.search_query = query.to_string();
+        self.filter_entries();
+    }
+
     pub fn push_search_char(&mut self, c: char) {
         self.search_query.push(c);
-        self.filter_entries();
+        self.schedule_search();
+        self.update_tag_completions();
     }
 
     pub fn pop_search_char(&mut self) {
         self.search_query.pop();
+        self.schedule_search();
+        self.update_tag_completions();
+    }
+
+    /// Search for the changed query now, or once typing pauses for
+    /// `ui.search_debounce_ms`
+    fn schedule_search(&mut self) {
+        if self.config.ui.search_debounce_ms == 0 {
+            self.filter_entries();
+        } else {
+            self.search_pending_since = Some(Instant::now());
+        }
+    }
+
+    /// Run a scheduled search once the debounce delay has passed. Returns
+    /// true if it ran.
+    pub fn run_pending_search(&mut self) -> bool {
+        let delay = Duration::from_millis(self.config.ui.search_debounce_ms);
+        match self.search_pending_since {
+            Some(since) if since.elapsed() >= delay => {
+                self.flush_search();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Run a scheduled search right away
+    pub fn flush_search(&mut self) {
+        if self.search_pending_since.take().is_some() {
+            self.filter_entries();
+        }
+    }
+
+    /// The tag word being typed at the end of the search box, as
+    /// (byte offset of the word, negated, tag prefix). Both `#name` and
+    /// `tag:name` start one.
+    fn tag_word(&self) -> Option<(usize, bool, &str)> {
+        let start = self
+            .search_query
+            .rfind(char::is_whitespace)
+            .map_or(0, |i| i + 1);
+        let word = &self.search_query[start..];
+        let (negated, word) = match word.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, word),
+        };
+        let prefix = word
+            .strip_prefix('#')
+            .or_else(|| word.strip_prefix("tag:"))?;
+        Some((start, negated, prefix))
+    }
+
+    /// Offer existing tags matching the tag word being typed (fuzzy mode)
+    fn update_tag_completions(&mut self) {
+        self.tag_completion_selected = 0;
+        let prefix = match (self.search_mode, self.tag_word()) {
+            (SearchMode::Fuzzy, Some((_, _, prefix))) => prefix.to_lowercase(),
+            _ => {
+                self.tag_completions.clear();
+                return;
+            }
+        };
+        self.tag_completions = self.tags_starting_with(&prefix, |_| true);
+    }
+
+    /// Tags in use whose name starts with `prefix` (case-insensitive) and
+    /// that pass `keep`
+    fn tags_starting_with(
+        &self,
+        prefix: &str,
+        keep: impl Fn(&str) -> bool,
+    ) -> Vec<(String, usize)> {
+        match self.db.all_tags() {
+            Ok(tags) => tags
+                .into_iter()
+                .filter(|(name, _)| name.to_lowercase().starts_with(prefix) && keep(name))
+                .take(MAX_TAG_COMPLETIONS)
+                .collect(),
+            Err(e) => {
+                tracing::error!("Failed to load tags: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Move the highlighted tag completion (wrapping)
+    pub fn move_tag_completion(&mut self, down: bool) {
+        let len = self.tag_completions.len();
+        if len == 0 {
+            return;
+        }
+        self.tag_completion_selected = if down {
+            (self.tag_completion_selected + 1) % len
+        } else {
+            (self.tag_completion_selected + len - 1) % len
+        };
+    }
+
+    /// Replace the tag word with the highlighted completion as `tag:name`.
+    /// Returns false if no completion was offered.
+    pub fn accept_tag_completion(&mut self) -> bool {
+        let Some((name, _)) = self.tag_completions.get(self.tag_completion_selected) else {
+            return false;
+        };
+        let name = name.clone();
+        let Some((start, negated, _)) = self.tag_word() else {
+            return false;
+        };
+        self.search_query.truncate(start);
+        if negated {
+            self.search_query.push('-');
+        }
+        self.search_query.push_str("tag:");
+        // Same quoting as the query language's Display
+        if name.contains(char::is_whitespace) {
+            self.search_query.push_str(&format!("\"{}\"", name));
+        } else {
+            self.search_query.push_str(&name);
+        }
+        self.search_query.push(' ');
+        self.tag_completions.clear();
         self.filter_entries();
+        true
+    }
+
+    /// Hide the tag completion popup
+    pub fn dismiss_tag_completions(&mut self) {
+        self.tag_completions.clear();
     }
 
     pub fn clear_message(&mut self) {
@@ -648,12 +1065,14 @@ impl App {
     pub fn set_message(&mut self, msg: impl Into<String>) {
         self.message = Some(msg.into());
         self.message_time = Some(Instant::now());
+        self.message_ttl = MESSAGE_TTL;
     }
 
-    /// Check if message has timed out (2 seconds)
+    /// Check if message has timed out (2 seconds, or the undo window for
+    /// delete toasts)
     pub fn is_message_expired(&self) -> bool {
         if let Some(time) = self.message_time {
-            time.elapsed().as_secs() >= 2
+            time.elapsed() >= self.message_ttl
         } else {
             false
         }
@@ -685,9 +1104,9 @@ impl App {
             self.multi_selected.clear();
         }
         let msg = if self.multi_select_mode {
-            "Multi-select ON (Space to select, v to toggle all)"
+            t("toast.multi_select_on")
         } else {
-            "Multi-select OFF"
+            t("toast.multi_select_off")
         };
         self.set_message(msg);
     }
@@ -695,10 +1114,10 @@ impl App {
     /// Toggle selection of current entry in multi-select mode
     pub fn toggle_current_selection(&mut self) {
         if self.multi_select_mode && !self.filtered.is_empty() {
-            if self.multi_selected.contains(&self.selected) {
-                self.multi_selected.remove(&self.selected);
+            if let Some(pos) = self.multi_selected.iter().position(|&i| i == self.selected) {
+                self.multi_selected.remove(pos);
             } else {
-                self.multi_selected.insert(self.selected);
+                self.multi_selected.push(self.selected);
             }
         }
     }
@@ -707,7 +1126,7 @@ impl App {
     pub fn select_all(&mut self) {
         if self.multi_select_mode {
             self.multi_selected = (0..self.filtered.len()).collect();
-            self.set_message(format!("Selected all {} entries", self.filtered.len()));
+            self.set_message(tf("toast.selected_all", &[("count", &self.filtered.len())]));
         }
     }
 
@@ -715,7 +1134,7 @@ impl App {
     pub fn deselect_all(&mut self) {
         if self.multi_select_mode {
             self.multi_selected.clear();
-            self.set_message("Deselected all");
+            self.set_message(t("toast.deselected_all"));
         }
     }
 
@@ -756,51 +1175,54 @@ impl App {
             .collect();
 
         // Delete each entry
+        let mut deleted = Vec::with_capacity(ids_to_delete.len());
         for id in &ids_to_delete {
-            self.db.delete(id)?;
+            deleted.extend(undo::delete_restorable(&mut self.db, id)?);
+        }
+        for deleted in &deleted {
+            hooks::spawn(&self.config.hooks, HookEvent::Delete, &deleted.entry);
         }
 
         // Clear selection and reload
         self.multi_selected.clear();
         self.reload_entries()?;
-        self.set_message(format!("Deleted {} entries", count));
+        self.offer_undo(deleted, tf("toast.deleted_entries", &[("count", &count)]));
 
         Ok(())
     }
 
-    /// Copy all selected entries in multi-select mode (concatenate text)
-    pub fn copy_selected_multi(&mut self) -> Result<()> {
-        if !self.multi_select_mode || self.multi_selected.is_empty() {
-            return Ok(());
-        }
+    /// Text entries selected in multi-select mode, in selection order.
+    /// Images are skipped since they can't be joined.
+    fn selected_texts(&self) -> Vec<&Entry> {
+        self.multi_selected
+            .iter()
+            .filter_map(|&filtered_idx| self.filtered.get(filtered_idx))
+            .filter_map(|&entry_idx| self.entries.get(entry_idx))
+            .filter(|entry| entry.entry_type == EntryType::Text)
+            .collect()
+    }
 
-        // Collect all selected text entries
-        let mut texts: Vec<String> = Vec::new();
-        let mut ids: Vec<String> = Vec::new();
-
-        for &filtered_idx in &self.multi_selected {
-            if let Some(&entry_idx) = self.filtered.get(filtered_idx) {
-                if let Some(entry) = self.entries.get(entry_idx) {
-                    match entry.entry_type {
-                        crate::entry::EntryType::Text => {
-                            texts.push(entry.content.clone());
-                            ids.push(entry.id.clone());
-                        }
-                        crate::entry::EntryType::Image => {
-                            // Skip images in multi-copy (can't concatenate images)
-                        }
-                    }
-                }
-            }
+    /// Selected texts joined with `ui.join_separator`, and their IDs
+    fn joined_selection(&self) -> Option<(String, Vec<String>)> {
+        let entries = self.selected_texts();
+        if !self.multi_select_mode || entries.is_empty() {
+            return None;
         }
+        let texts: Vec<&str> = entries.iter().map(|e| e.content.as_str()).collect();
+        let ids = entries.iter().map(|e| e.id.clone()).collect();
+        Some((texts.join(&self.config.ui.join_separator), ids))
+    }
 
-        if texts.is_empty() {
-            self.set_message("No text entries to copy");
+    /// Copy all selected entries in multi-select mode, joined in the order
+    /// they were selected
+    pub fn copy_selected_multi(&mut self) -> Result<()> {
+        if !self.multi_select_mode || self.multi_selected.is_empty() {
             return Ok(());
         }
-
-        // Join with newlines and copy
-        let combined = texts.join("\n");
+        let Some((combined, ids)) = self.joined_selection() else {
+            self.set_message(t("toast.no_text_to_copy"));
+            return Ok(());
+        };
         Clipboard::set_text(&combined)?;
 
         // Touch all copied entries
@@ -808,7 +1230,33 @@ impl App {
             let _ = self.db.touch(id);
         }
 
-        self.set_message(format!("Copied {} text entries", texts.len()));
+        self.set_message(tf("toast.copied_texts", &[("count", &ids.len())]));
+        Ok(())
+    }
+
+    /// Save the joined selection as a new clip and copy it
+    pub fn merge_selected(&mut self) -> Result<()> {
+        if !self.multi_select_mode || self.multi_selected.is_empty() {
+            return Ok(());
+        }
+        let Some((combined, ids)) = self.joined_selection() else {
+            self.set_message(t("toast.no_text_to_copy"));
+            return Ok(());
+        };
+        let entry = Entry::new_text(combined);
+        match self.db.get_by_hash(&entry.hash)? {
+            Some(existing) => {
+                self.db.touch(&existing.id)?;
+            }
+            None => {
+                self.db.insert(&entry)?;
+            }
+        }
+        Clipboard::set_text(&entry.content)?;
+
+        self.multi_selected.clear();
+        self.reload_entries()?;
+        self.set_message(tf("toast.merged_texts", &[("count", &ids.len())]));
         Ok(())
     }
 
@@ -827,6 +1275,79 @@ impl App {
         self.input_mode = InputMode::EditNote;
     }
 
+    /// Picker mode: take the multi-selection in the order it was made (or
+    /// else the highlighted entry) as the result and quit
+    pub fn pick_selected(&mut self) {
+        let indices = if self.multi_select_mode && !self.multi_selected.is_empty() {
+            self.multi_selected.clone()
+        } else {
+            vec![self.selected]
+        };
+        self.picked = indices
+            .into_iter()
+            .filter_map(|i| self.filtered.get(i))
+            .filter_map(|&idx| self.entries.get(idx))
+            .cloned()
+            .collect();
+        for entry in &self.picked {
+            let _ = self.db.touch(&entry.id);
+        }
+        self.should_quit = true;
+    }
+
+    /// Ask the UI to open the selected clip in an external editor
+    pub fn request_edit(&mut self, mode: EditMode) {
+        match self.selected_entry() {
+            Some(entry) if entry.entry_type == EntryType::Text => self.edit_requested = Some(mode),
+            Some(_) => self.set_message(t("toast.edit_text_only")),
+            None => {}
+        }
+    }
+
+    /// Store the edited text of entry `id`, replacing its text or as a new
+    /// clip. Text that is already another clip is not duplicated: saving it
+    /// as new moves that clip to the top instead. Problems are shown as a
+    /// toast.
+    pub fn apply_edit(
+        &mut self,
+        id: &str,
+        original: &str,
+        edited: &str,
+        mode: EditMode,
+    ) -> Result<()> {
+        if edited == original {
+            self.set_message(t("toast.clip_unchanged"));
+            return Ok(());
+        }
+        let entry = Entry::new_text(edited.to_string());
+        let existing = self.db.get_by_hash(&entry.hash)?.filter(|e| e.id != id);
+        if mode == EditMode::AsNew {
+            match existing {
+                Some(existing) => {
+                    self.db.touch(&existing.id)?;
+                    self.set_message(t("toast.clip_exists_moved"));
+                }
+                None => {
+                    self.db.insert(&entry)?;
+                    self.set_message(t("toast.clip_saved_as_new"));
+                }
+            }
+            return self.reload_entries();
+        }
+        if existing.is_some() {
+            self.set_message(t("toast.clip_exists"));
+            return Ok(());
+        }
+        match self.db.update_content(id, edited) {
+            Ok(_) => {
+                self.reload_entries()?;
+                self.set_message(t("toast.clip_updated"));
+            }
+            Err(e) => self.set_message(e.to_string()),
+        }
+        Ok(())
+    }
+
     /// Save the note and return to normal mode
     pub fn save_note(&mut self) -> Result<()> {
         if let Some(id) = self.editing_entry_id.take() {
@@ -837,7 +1358,7 @@ impl App {
             };
             self.db.update_notes(&id, notes)?;
             self.reload_entries()?;
-            self.set_message("Note saved");
+            self.set_message(t("toast.note_saved"));
         }
         self.note_input.clear();
         self.input_mode = InputMode::Normal;
@@ -849,7 +1370,7 @@ impl App {
         self.editing_entry_id = None;
         self.note_input.clear();
         self.input_mode = InputMode::Normal;
-        self.set_message("Edit cancelled");
+        self.set_message(t("toast.edit_cancelled"));
     }
 
     /// Push a character to the note input
@@ -862,13 +1383,107 @@ impl App {
         self.note_input.pop();
     }
 
+    // Tag editing operations
+
+    /// Start editing the tags of the currently selected entry
+    pub fn start_edit_tags(&mut self) -> Result<()> {
+        let Some(id) = self.selected_entry().map(|e| e.id.clone()) else {
+            return Ok(());
+        };
+        self.editing_tags = self.db.get_tags(&id)?;
+        self.editing_entry_id = Some(id);
+        self.tag_input.clear();
+        self.input_mode = InputMode::EditTags;
+        self.update_tag_input_completions();
+        Ok(())
+    }
+
+    /// Leave tag editing; changes are already saved
+    pub fn end_edit_tags(&mut self) {
+        self.editing_entry_id = None;
+        self.editing_tags.clear();
+        self.tag_input.clear();
+        self.tag_completions.clear();
+        self.input_mode = InputMode::Normal;
+    }
+
+    pub fn push_tag_char(&mut self, c: char) {
+        self.tag_input.push(c);
+        self.update_tag_input_completions();
+    }
+
+    pub fn pop_tag_char(&mut self) {
+        self.tag_input.pop();
+        self.update_tag_input_completions();
+    }
+
+    /// Apply the tag input: `name` adds the tag, `-name` removes it. An
+    /// empty input ends tag editing.
+    pub fn submit_tag_input(&mut self) -> Result<()> {
+        let Some(id) = self.editing_entry_id.clone() else {
+            self.end_edit_tags();
+            return Ok(());
+        };
+        let input = self.tag_input.trim().to_string();
+        if input.is_empty() {
+            self.end_edit_tags();
+            return Ok(());
+        }
+        let result = match input.strip_prefix('-') {
+            Some(name) => self.db.remove_tag(&id, name),
+            None => self.db.add_tag(&id, &input),
+        };
+        match result {
+            Ok(_) => {
+                self.editing_tags = self.db.get_tags(&id)?;
+                self.tag_input.clear();
+                // Tag-filtered views and searches must see the change now
+                self.reload_entries()?;
+            }
+            // e.g. an invalid tag name; keep the input for fixing
+            Err(e) => self.set_message(e.to_string()),
+        }
+        self.update_tag_input_completions();
+        Ok(())
+    }
+
+    /// Replace the tag input with the highlighted completion
+    pub fn complete_tag_input(&mut self) {
+        let Some((name, _)) = self.tag_completions.get(self.tag_completion_selected) else {
+            return;
+        };
+        let remove = self.tag_input.trim_start().starts_with('-');
+        self.tag_input = if remove {
+            format!("-{}", name)
+        } else {
+            name.clone()
+        };
+        self.tag_completions.clear();
+    }
+
+    /// Offer tags for the tag input: ones the entry lacks when adding, its
+    /// own when removing (`-name`)
+    fn update_tag_input_completions(&mut self) {
+        self.tag_completion_selected = 0;
+        let input = self.tag_input.trim_start().to_lowercase();
+        let (remove, prefix) = match input.strip_prefix('-') {
+            Some(prefix) => (true, prefix),
+            None => (false, input.as_str()),
+        };
+        let has = |name: &str| self.editing_tags.iter().any(|t| t == name);
+        self.tag_completions = self.tags_starting_with(prefix, |name| has(name) == remove);
+    }
+
     // Preview mode operations
 
     /// Cycle to the next preview mode
     pub fn cycle_preview_mode(&mut self) {
         self.preview_mode = self.preview_mode.next();
         self.preview_scroll_offset = 0; // Reset scroll when changing modes
-        self.set_message(format!("Preview mode: {}", self.preview_mode.label()));
+        self.set_message(tf(
+            "toast.preview_mode",
+            &[("mode", &self.preview_mode.label())],
+        ));
     }
 
     /// Scroll preview left (for Scroll mode)
@@ -926,14 +1541,14 @@ impl App {
     /// Copy snippet slot (1-9) to clipboard
     pub fn copy_snippet(&mut self, slot: usize) -> Result<()> {
         if !(1..=9).contains(&slot) {
-            self.set_message("Invalid slot number");
+            self.set_message(t("toast.invalid_slot"));
             return Ok(());
         }
 
         let entry_id = match self.snippet_slots[slot - 1].clone() {
             Some(id) => id,
             None => {
-                self.set_message(format!("Slot {} is empty", slot));
+                self.set_message(tf("toast.slot_empty", &[("slot", &slot)]));
                 return Ok(());
             }
         };
@@ -944,20 +1559,27 @@ impl App {
             match entry.entry_type {
                 crate::entry::EntryType::Text => {
                     Clipboard::set_text(&entry.content)?;
-                    self.set_message(format!("Slot {}: {}", slot, preview));
+                    self.set_message(tf(
+                        "toast.slot_copied",
+                        &[("slot", &slot), ("preview", &preview)],
+                    ));
                 }
                 crate::entry::EntryType::Image => {
                     let path = entry.image_path().ok_or_else(|| {
                         crate::error::DitoxError::Other("image entry missing extension".into())
                     })?;
                     Clipboard::set_image(&path.to_string_lossy())?;
-                    self.set_message(format!("Slot {} (image): {}", slot, preview));
+                    self.set_message(tf(
+                        "toast.slot_copied_image",
+                        &[("slot", &slot), ("preview", &preview)],
+                    ));
                 }
             }
+            hooks::spawn(&self.config.hooks, HookEvent::Copy, &entry);
             // Update usage count
             self.db.touch(&entry_id)?;
         } else {
-            self.set_message(format!("Slot {} entry not found", slot));
+            self.set_message(tf("toast.slot_not_found", &[("slot", &slot)]));
         }
 
         Ok(())
@@ -968,9 +1590,9 @@ impl App {
     pub fn toggle_snippets(&mut self) {
         self.show_snippets = !self.show_snippets;
         let msg = if self.show_snippets {
-            "Snippets bar shown"
+            t("toast.snippets_shown")
         } else {
-            "Snippets bar hidden"
+            t("toast.snippets_hidden")
         };
         self.set_message(msg);
     }
@@ -982,6 +1604,81 @@ impl App {
         self.tabs.get(self.active_tab).unwrap_or(&TabFilter::All)
     }
 
+    /// Number of entries shown by a tab (without a search query)
+    fn count_tab(&self, filter: &TabFilter) -> Result<usize> {
+        match filter {
+            TabFilter::View { query, .. } => self.db.count_query(query),
+            _ => {
+                let (filter_str, collection_id) = filter.db_filter();
+                self.db.count_filtered(filter_str, collection_id)
+            }
+        }
+    }
+
+    /// Load one page of a tab (without a search query)
+    fn load_tab_page(&self, filter: &TabFilter, offset: usize) -> Result<Vec<Entry>> {
+        match filter {
+            TabFilter::View { query, .. } => self
+                .db
+                .query_page_sorted(query, offset, PAGE_SIZE, self.sort),
+            _ => {
+                let (filter_str, collection_id) = filter.db_filter();
+                self.db
+                    .get_page_sorted(offset, PAGE_SIZE, filter_str, collection_id, self.sort)
+            }
+        }
+    }
+
+    /// Switch to the next sort order and show the list from its top
+    pub fn cycle_sort(&mut self) -> Result<()> {
+        self.sort = self.sort.next();
+        self.current_page = 0;
+        self.selected = 0;
+        self.multi_selected.clear();
+        self.reload_entries()?;
+        self.set_message(tf("toast.sort", &[("sort", &sort_label(self.sort))]));
+        Ok(())
+    }
+
+    /// Day header group of the entry at `filtered_idx`, or None when the
+    /// list isn't grouped (grouping off, searching, or not sorted by time)
+    pub fn day_group(&self, filtered_idx: usize) -> Option<DayGroup> {
+        if !self.group_by_day || !self.sort.is_by_time() || !self.search_query.is_empty() {
+            return None;
+        }
+        let entry = self.entries.get(*self.filtered.get(filtered_idx)?)?;
+        let time = match self.sort {
+            SortOrder::Created => entry.created_at,
+            _ => entry.last_used,
+        };
+        let today = Local::now().date_naive();
+        let days = (today - time.with_timezone(&Local).date_naive()).num_days();
+        Some(match days {
+            ..=0 => DayGroup::Today,
+            1 => DayGroup::Yesterday,
+            2..=7 => DayGroup::LastWeek,
+            _ => DayGroup::Older,
+        })
+    }
+
+    /// Switch to the `n`th saved view (1-based, in tab order)
+    pub fn go_to_view(&mut self, n: usize) {
+        let tab = self
+            .tabs
+            .iter()
+            .enumerate()
+            .filter(|(_, tab)| matches!(tab, TabFilter::View { .. }))
+            .nth(n.saturating_sub(1))
+            .map(|(i, _)| i);
+        match tab {
+            Some(i) => {
+                self.active_tab = i;
+                self.apply_tab_filter();
+            }
+            None => self.set_message(tf("toast.no_saved_view", &[("n", &n)])),
+        }
+    }
+
     /// Move to the next tab
     pub fn next_tab(&mut self) {
         if !self.tabs.is_empty() {
@@ -1018,16 +1715,13 @@ impl App {
             self.filter_by_tab(&filter);
         } else {
             // Reload from DB with the new filter for correct pagination
-            let (filter_str, collection_id) = filter.db_filter();
-            if let Ok(count) = self.db.count_filtered(filter_str, collection_id) {
+            if let Ok(count) = self.count_tab(&filter) {
                 self.total_count = count;
             }
-            if let Ok(entries) = self
-                .db
-                .get_page_filtered(0, PAGE_SIZE, filter_str, collection_id)
-            {
+            if let Ok(entries) = self.load_tab_page(&filter, 0) {
                 self.entries = entries;
                 self.filtered = (0..self.entries.len()).collect();
+                self.search_hits.clear();
             }
         }
 
@@ -1035,13 +1729,27 @@ impl App {
         self.selected = 0;
 
         // Show message
-        self.set_message(format!("Tab: {}", filter.label()));
+        self.set_message(tf("toast.tab", &[("tab", &filter.label())]));
     }
 
     /// Filter entries by tab filter
     fn filter_by_tab(&mut self, filter: &TabFilter) {
         use chrono::{Duration, Utc};
 
+        // Views can use full-text criteria, so ask the DB which entries match
+        let view_ids: HashSet<String> = match filter {
+            TabFilter::View { query, .. } => {
+                match self.db.query(query, self.config.general.max_entries) {
+                    Ok(entries) => entries.into_iter().map(|e| e.id).collect(),
+                    Err(e) => {
+                        tracing::error!("Failed to run view '{}': {}", filter.label(), e);
+                        HashSet::new()
+                    }
+                }
+            }
+            _ => HashSet::new(),
+        };
+
         self.filtered.retain(|&idx| {
             if let Some(entry) = self.entries.get(idx) {
                 match filter {
@@ -1056,6 +1764,7 @@ impl App {
                     TabFilter::Collection(collection_id) => {
                         entry.collection_id.as_ref() == Some(collection_id)
                     }
+                    TabFilter::View { .. } => view_ids.contains(&entry.id),
                 }
             } else {
                 false
@@ -1068,9 +1777,9 @@ impl App {
     pub fn toggle_tabs(&mut self) {
         self.show_tabs = !self.show_tabs;
         let msg = if self.show_tabs {
-            "Tabs bar shown"
+            t("toast.tabs_shown")
         } else {
-            "Tabs bar hidden"
+            t("toast.tabs_hidden")
         };
         self.set_message(msg);
     }
@@ -1079,55 +1788,75 @@ impl App {
     pub fn toggle_line_numbers(&mut self) {
         self.show_line_numbers = !self.show_line_numbers;
         let msg = if self.show_line_numbers {
-            "Line numbers ON"
+            t("toast.line_numbers_on")
         } else {
-            "Line numbers OFF"
+            t("toast.line_numbers_off")
         };
         self.set_message(msg);
     }
 
     // Confirmation operations
 
-    /// Request confirmation for deleting the selected entry
-    pub fn request_delete_selected(&mut self) {
+    /// Request deleting the selected entry, confirmed per `[confirm]`
+    pub fn request_delete_selected(&mut self) -> Result<()> {
         if self.selected_entry().is_some() {
-            self.pending_confirm = Some(ConfirmAction::DeleteSelected);
-            self.input_mode = InputMode::Confirm;
+            self.request_confirm(ConfirmAction::DeleteSelected)?;
         }
+        Ok(())
     }
 
-    /// Request confirmation for deleting multiple selected entries
-    pub fn request_delete_multi(&mut self) {
+    /// Request deleting the multi-selected entries, confirmed per `[confirm]`
+    pub fn request_delete_multi(&mut self) -> Result<()> {
         if self.multi_select_mode && !self.multi_selected.is_empty() {
-            self.pending_confirm = Some(ConfirmAction::DeleteSelected);
-            self.input_mode = InputMode::Confirm;
+            self.request_confirm(ConfirmAction::DeleteMulti)?;
         }
+        Ok(())
     }
 
-    /// Request confirmation for clearing all entries
-    pub fn request_clear_all(&mut self) {
+    /// Request clearing all entries, confirmed per `[confirm]`
+    pub fn request_clear_all(&mut self) -> Result<()> {
         if self.total_count > 0 {
-            self.pending_confirm = Some(ConfirmAction::ClearAll);
-            self.input_mode = InputMode::Confirm;
+            self.request_confirm(ConfirmAction::ClearAll)?;
+        }
+        Ok(())
+    }
+
+    /// Apply the confirmation policy: run now, open a dialog, or wait for
+    /// the second press
+    fn request_confirm(&mut self, action: ConfirmAction) -> Result<()> {
+        match self.confirm_tracker.request(&self.config.confirm, action) {
+            ConfirmStep::Run => self.run_confirmed(action)?,
+            ConfirmStep::Ask(style) => {
+                self.pending_confirm = Some(action);
+                self.confirm_style = style;
+                self.confirm_input.clear();
+                self.input_mode = InputMode::Confirm;
+            }
+            ConfirmStep::AwaitSecondPress => self.set_message(t("toast.press_again")),
+        }
+        Ok(())
+    }
+
+    fn run_confirmed(&mut self, action: ConfirmAction) -> Result<()> {
+        match action {
+            ConfirmAction::DeleteSelected => self.delete_selected(),
+            ConfirmAction::DeleteMulti => self.delete_selected_multi(),
+            ConfirmAction::ClearAll => self.clear_all(),
         }
     }
 
-    /// Execute the pending confirmation action
+    /// Execute the pending confirmation action. A Typed confirmation only
+    /// goes through once the phrase has been typed.
     pub fn confirm_action(&mut self) -> Result<()> {
+        if self.confirm_style == ConfirmStyle::Typed && self.confirm_input.trim() != CONFIRM_PHRASE
+        {
+            self.set_message(tf("toast.type_phrase", &[("phrase", &CONFIRM_PHRASE)]));
+            return Ok(());
+        }
         if let Some(action) = self.pending_confirm.take() {
             self.input_mode = InputMode::Normal;
-            match action {
-                ConfirmAction::DeleteSelected => {
-                    if self.multi_select_mode && !self.multi_selected.is_empty() {
-                        self.delete_selected_multi()?;
-                    } else {
-                        self.delete_selected()?;
-                    }
-                }
-                ConfirmAction::ClearAll => {
-                    self.clear_all()?;
-                }
-            }
+            self.confirm_input.clear();
+            self.run_confirmed(action)?;
         }
         Ok(())
     }
@@ -1135,28 +1864,26 @@ impl App {
     /// Cancel the pending confirmation
     pub fn cancel_confirm(&mut self) {
         self.pending_confirm = None;
+        self.confirm_input.clear();
         self.input_mode = InputMode::Normal;
-        self.set_message("Cancelled");
+        self.set_message(t("toast.cancelled"));
     }
 
     /// Get a description of the pending confirmation action
     pub fn confirm_message(&self) -> Option<String> {
         self.pending_confirm.map(|action| match action {
+            ConfirmAction::DeleteMulti => tf(
+                "confirm.delete_selected",
+                &[("count", &self.multi_selected.len())],
+            ),
             ConfirmAction::DeleteSelected => {
-                if self.multi_select_mode && !self.multi_selected.is_empty() {
-                    format!("Delete {} selected entries?", self.multi_selected.len())
-                } else if let Some(entry) = self.selected_entry() {
-                    format!("Delete \"{}\"?", entry.preview(30))
+                if let Some(entry) = self.selected_entry() {
+                    tf("confirm.delete_entry", &[("preview", &entry.preview(30))])
                 } else {
-                    "Delete selected entry?".to_string()
+                    t("confirm.delete_selected_entry")
                 }
             }
-            ConfirmAction::ClearAll => {
-                format!(
-                    "Delete ALL {} entries? This cannot be undone!",
-                    self.total_count
-                )
-            }
+            ConfirmAction::ClearAll => tf("confirm.clear_all", &[("count", &self.total_count)]),
         })
     }
 }
@@ -0,0 +1,212 @@
+//! Starting the watcher at login
+//!
+//! One backend per platform: a systemd user unit on Linux, a LaunchAgent on
+//! macOS and a Task Scheduler logon task on Windows. Installing only
+//! registers `ditox watch`; it doesn't start it.
+
+use crate::error::{DitoxError, Result};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// launchd label, also the LaunchAgent file name
+pub const LAUNCHD_LABEL: &str = "io.github.0xfell.ditox";
+
+/// Name of the Windows scheduled task
+pub const TASK_NAME: &str = "Ditox";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Systemd,
+    Launchd,
+    TaskScheduler,
+}
+
+impl Backend {
+    /// The backend for this platform
+    pub fn current() -> Result<Self> {
+        if cfg!(target_os = "linux") {
+            Ok(Backend::Systemd)
+        } else if cfg!(target_os = "macos") {
+            Ok(Backend::Launchd)
+        } else if cfg!(windows) {
+            Ok(Backend::TaskScheduler)
+        } else {
+            Err(DitoxError::Other(
+                "Autostart is not supported on this platform".into(),
+            ))
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Systemd => "systemd",
+            Backend::Launchd => "launchd",
+            Backend::TaskScheduler => "Task Scheduler",
+        }
+    }
+
+    /// Where the watcher is registered: a file path, or the task name
+    pub fn location(self) -> Result<String> {
+        match self {
+            Backend::Systemd => Ok(systemd_unit_path()?.display().to_string()),
+            Backend::Launchd => Ok(launch_agent_path()?.display().to_string()),
+            Backend::TaskScheduler => Ok(TASK_NAME.to_string()),
+        }
+    }
+
+    pub fn is_installed(self) -> Result<bool> {
+        match self {
+            Backend::Systemd => Ok(systemd_unit_path()?.exists()),
+            Backend::Launchd => Ok(launch_agent_path()?.exists()),
+            Backend::TaskScheduler => Ok(run_quietly(
+                Command::new("schtasks").args(["/Query", "/TN", TASK_NAME]),
+            )
+            .is_ok()),
+        }
+    }
+
+    /// Register `{exe} watch` to run at login, replacing an existing entry
+    pub fn install(self, exe: &Path) -> Result<()> {
+        match self {
+            Backend::Systemd => write_file(&systemd_unit_path()?, &systemd_unit(exe)),
+            Backend::Launchd => write_file(&launch_agent_path()?, &launch_agent(exe)),
+            Backend::TaskScheduler => run_quietly(Command::new("schtasks").args([
+                "/Create",
+                "/F",
+                "/SC",
+                "ONLOGON",
+                "/TN",
+                TASK_NAME,
+                "/TR",
+                &format!("\"{}\" watch", exe.display()),
+            ])),
+        }
+    }
+
+    /// Stop the watcher if the backend started it, then remove the entry.
+    /// False if nothing was installed.
+    pub fn uninstall(self) -> Result<bool> {
+        if !self.is_installed()? {
+            return Ok(false);
+        }
+        match self {
+            Backend::Systemd => {
+                // Not enabled or not running is fine
+                let _ = run_quietly(Command::new("systemctl").args([
+                    "--user",
+                    "disable",
+                    "--now",
+                    "ditox.service",
+                ]));
+                std::fs::remove_file(systemd_unit_path()?)?;
+            }
+            Backend::Launchd => {
+                let path = launch_agent_path()?;
+                let _ = run_quietly(Command::new("launchctl").arg("unload").arg(&path));
+                std::fs::remove_file(path)?;
+            }
+            Backend::TaskScheduler => {
+                run_quietly(Command::new("schtasks").args(["/Delete", "/F", "/TN", TASK_NAME]))?;
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Where `ditox daemon install` writes the systemd user unit
+pub fn systemd_unit_path() -> Result<PathBuf> {
+    directories::BaseDirs::new()
+        .map(|dirs| dirs.config_dir().join("systemd/user/ditox.service"))
+        .ok_or_else(|| DitoxError::Config("Could not determine config directory".into()))
+}
+
+/// systemd user unit running `{exe} watch` with the graphical session
+pub fn systemd_unit(exe: &Path) -> String {
+    format!(
+        "[Unit]
+Description=Ditox clipboard watcher
+After=graphical-session.target
+PartOf=graphical-session.target
+
+[Service]
+Type=notify
+ExecStart={} watch
+Restart=on-failure
+RestartSec=5
+
+[Install]
+WantedBy=graphical-session.target
+",
+        exe.display()
+    )
+}
+
+/// Where `ditox daemon install` writes the LaunchAgent
+pub fn launch_agent_path() -> Result<PathBuf> {
+    directories::BaseDirs::new()
+        .map(|dirs| {
+            dirs.home_dir()
+                .join("Library/LaunchAgents")
+                .join(format!("{}.plist", LAUNCHD_LABEL))
+        })
+        .ok_or_else(|| DitoxError::Config("Could not determine home directory".into()))
+}
+
+/// LaunchAgent running `{exe} watch` at login, restarted if it fails
+pub fn launch_agent(exe: &Path) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+        <string>watch</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+        <key>SuccessfulExit</key>
+        <false/>
+    </dict>
+    <key>ProcessType</key>
+    <string>Interactive</string>
+</dict>
+</plist>
+"#,
+        LAUNCHD_LABEL,
+        xml_escape(&exe.display().to_string())
+    )
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+/// Run a service-manager command, turning a failure into an error with
+/// its stderr
+fn run_quietly(command: &mut Command) -> Result<()> {
+    let output = command.stdin(Stdio::null()).output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(DitoxError::Other(format!(
+        "{:?} failed: {}",
+        command.get_program(),
+        String::from_utf8_lossy(&output.stderr).trim()
+    )))
+}
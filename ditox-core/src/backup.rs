@@ -0,0 +1,288 @@
+//! Backups of the database and image store
+//!
+//! [`create`] snapshots the live database with SQLite's online backup API,
+//! so the copy is consistent even while the watcher writes, and packs it
+//! with the image blobs it references into one `.tar.gz`:
+//!
+//! ```text
+//! ditox.db
+//! images/ab/abcd….png
+//! ```
+//!
+//! [`restore`] unpacks an archive into a scratch directory and checks it
+//! before touching anything: the database must pass SQLite's integrity
+//! check, have a schema this build understands, and every image it
+//! references must be validly named and present (and, with `verify`, match
+//! its hash). It then saves a safety backup of the current state under
+//! `backups/`, adds the images to the store, migrates the unpacked copy and
+//! only then copies it over the live database with the backup API. Blobs
+//! only the old history used are left for `ditox repair` to collect.
+//!
+//! With `backup.auto` set, the watcher calls [`run_scheduled`], which writes
+//! `auto-<timestamp>.tar.gz` archives once the newest is a day or a week
+//! old and deletes the oldest beyond `backup.keep`. Other archives in the
+//! directory are never touched.
+
+use crate::config::BackupConfig;
+use crate::db::{check_blob_name, Database, SchemaCompat, SCHEMA_VERSION};
+use crate::entry::Entry;
+use crate::error::{DitoxError, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+/// Name of the database inside an archive
+const DB_NAME: &str = "ditox.db";
+
+/// File name prefix of automatic backups
+const AUTO_PREFIX: &str = "auto-";
+
+/// Outcome of [`create`]
+#[derive(Debug)]
+pub struct BackupReport {
+    pub entries: usize,
+    pub images: usize,
+    /// Image clips whose blob was missing from the store
+    pub missing_images: usize,
+    /// Size of the archive
+    pub bytes: u64,
+}
+
+/// Outcome of [`restore`]
+#[derive(Debug)]
+pub struct RestoreReport {
+    pub entries: usize,
+    pub images: usize,
+    /// Archive of the state before the restore
+    pub safety_backup: PathBuf,
+}
+
+/// Write a backup archive of `db` and its images to `path`
+pub fn create(db: &Database, path: &Path) -> Result<BackupReport> {
+    let scratch = scratch_dir("backup")?;
+    let result = write_archive(db, path, &scratch);
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+fn write_archive(db: &Database, path: &Path, scratch: &Path) -> Result<BackupReport> {
+    let snapshot_path = scratch.join(DB_NAME);
+    db.backup_to(&snapshot_path)?;
+    let snapshot = Database::open_read_only(&snapshot_path)?;
+
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+    let mut archive = tar::Builder::new(GzEncoder::new(
+        File::create(&tmp_path)?,
+        Compression::default(),
+    ));
+    archive.append_path_with_name(&snapshot_path, DB_NAME)?;
+
+    let mut report = BackupReport {
+        entries: snapshot.count()?,
+        images: 0,
+        missing_images: 0,
+        bytes: 0,
+    };
+    for (hash, ext) in snapshot.referenced_image_blobs()? {
+        let blob = Database::image_path(&hash, &ext)?;
+        if blob.is_file() {
+            archive.append_path_with_name(&blob, archive_image_name(&hash, &ext)?)?;
+            report.images += 1;
+        } else {
+            report.missing_images += 1;
+        }
+    }
+    archive.into_inner()?.finish()?.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    report.bytes = fs::metadata(path)?.len();
+    Ok(report)
+}
+
+/// Replace the history in `db` with the one in the archive at `path`,
+/// keeping a safety backup of the current state
+pub fn restore(db: &mut Database, path: &Path, verify: bool) -> Result<RestoreReport> {
+    let scratch = scratch_dir("restore")?;
+    let result = restore_from_scratch(db, path, verify, &scratch);
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+fn restore_from_scratch(
+    db: &mut Database,
+    path: &Path,
+    verify: bool,
+    scratch: &Path,
+) -> Result<RestoreReport> {
+    // `unpack_in` refuses entries that would land outside `scratch`
+    let mut archive = tar::Archive::new(GzDecoder::new(File::open(path)?));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        if name == DB_NAME || name.starts_with("images/") {
+            entry.unpack_in(scratch)?;
+        }
+    }
+
+    let restored_path = scratch.join(DB_NAME);
+    if !restored_path.is_file() {
+        return Err(invalid(path, "no database in archive"));
+    }
+    let restored = Database::open_read_only(&restored_path)?;
+    if let Some(problem) = restored.integrity_problems()?.first() {
+        return Err(invalid(path, problem));
+    }
+    if let SchemaCompat::Newer(version) = restored.schema_compat() {
+        return Err(DitoxError::MigrationNeeded(format!(
+            "Backup {} has schema version {}, newer than this build supports ({}). Upgrade ditox to restore it.",
+            path.display(),
+            version,
+            SCHEMA_VERSION
+        )));
+    }
+    let blobs = restored.referenced_image_blobs()?;
+    for (hash, ext) in &blobs {
+        let name = archive_image_name(hash, ext).map_err(|e| invalid(path, &e.to_string()))?;
+        let blob = scratch.join(name);
+        if !blob.is_file() {
+            return Err(invalid(path, &format!("missing image {}.{}", hash, ext)));
+        }
+        if verify && Entry::compute_hash(&fs::read(&blob)?) != *hash {
+            return Err(invalid(path, &format!("corrupt image {}.{}", hash, ext)));
+        }
+    }
+    let entries = restored.count()?;
+    drop(restored);
+
+    let backups = Database::get_data_dir()?.join("backups");
+    fs::create_dir_all(&backups)?;
+    let safety_backup = backups.join(format!(
+        "pre-restore-{}.tar.gz",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    create(db, &safety_backup)?;
+
+    // Everything that can fail happens before the live database changes:
+    // images go into the store first (extra blobs are harmless, `ditox
+    // repair` collects them), and the scratch copy is brought to the
+    // current schema in place
+    for (hash, ext) in &blobs {
+        let bytes = fs::read(scratch.join(archive_image_name(hash, ext)?))?;
+        Database::store_image_blob(hash, ext, &bytes)?;
+    }
+    let prepared = Database::open_at(restored_path.clone())?;
+    prepared.init_schema()?;
+    drop(prepared);
+
+    // One backup-API copy swaps the history for every open connection,
+    // the watcher's included; renaming over the file would leave them on
+    // the old one
+    db.restore_from(&restored_path)?;
+
+    Ok(RestoreReport {
+        entries,
+        images: blobs.len(),
+        safety_backup,
+    })
+}
+
+/// Directory automatic backups go to
+pub fn auto_dir(config: &BackupConfig) -> Result<PathBuf> {
+    match &config.dir {
+        Some(dir) => Ok(dir.clone()),
+        None => Ok(Database::get_data_dir()?.join("backups")),
+    }
+}
+
+/// Automatic backups in `dir`, oldest first
+pub fn auto_backups(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut archives = Vec::new();
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(archives),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in entries {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        if name.starts_with(AUTO_PREFIX) && name.ends_with(".tar.gz") {
+            archives.push(path);
+        }
+    }
+    // Timestamped names sort by age
+    archives.sort();
+    Ok(archives)
+}
+
+/// Write an automatic backup if `config.auto` is on and the newest one is
+/// older than its interval, then rotate. Returns the new archive, if any.
+pub fn run_scheduled(db: &Database, config: &BackupConfig) -> Result<Option<PathBuf>> {
+    let Some(interval) = config.auto.interval() else {
+        return Ok(None);
+    };
+    let dir = auto_dir(config)?;
+    let newest = auto_backups(&dir)?.pop();
+    if let Some(newest) = newest {
+        let age = fs::metadata(&newest)?
+            .modified()?
+            .elapsed()
+            .unwrap_or_default();
+        if age < interval {
+            return Ok(None);
+        }
+    }
+
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "{}{}.tar.gz",
+        AUTO_PREFIX,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    create(db, &path)?;
+    rotate(&dir, config.keep)?;
+    Ok(Some(path))
+}
+
+/// Delete the oldest automatic backups in `dir` beyond `keep` (0 keeps
+/// all). Returns how many were deleted.
+pub fn rotate(dir: &Path, keep: usize) -> Result<usize> {
+    let archives = auto_backups(dir)?;
+    if keep == 0 || archives.len() <= keep {
+        return Ok(0);
+    }
+    let excess = archives.len() - keep;
+    for path in &archives[..excess] {
+        fs::remove_file(path)?;
+    }
+    Ok(excess)
+}
+
+/// Path of an image inside an archive. Errors for names that aren't a
+/// blob's, e.g. from a crafted archive.
+fn archive_image_name(hash: &str, ext: &str) -> Result<String> {
+    check_blob_name(hash, ext)?;
+    Ok(format!("images/{}/{}.{}", &hash[..2], hash, ext))
+}
+
+/// Empty working directory inside the data dir, so the final renames and
+/// copies stay on one filesystem
+fn scratch_dir(purpose: &str) -> Result<PathBuf> {
+    let dir = Database::get_data_dir()?.join(format!(".{}-{}", purpose, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn invalid(path: &Path, reason: &str) -> DitoxError {
+    DitoxError::Other(format!(
+        "Backup {} is not usable: {}",
+        path.display(),
+        reason
+    ))
+}
@@ -67,9 +67,57 @@ mod platform {
     use std::process::{Command, Stdio};
 
     impl Clipboard {
+        /// Name of the clipboard backend, for diagnostics
+        pub const BACKEND: &'static str = "wayland (wl-clipboard)";
+
         /// Get current clipboard text content using wl-paste CLI
         pub fn get_text() -> Result<Option<String>> {
-            let output = Command::new("wl-paste")
+            Self::paste_text(false)
+        }
+
+        /// Get the current primary selection (the last selected text, pasted
+        /// with middle-click) using wl-paste CLI
+        pub fn get_primary_text() -> Result<Option<String>> {
+            Self::paste_text(true)
+        }
+
+        /// MIME types the current clipboard contents are offered as
+        pub fn list_types() -> Result<Vec<String>> {
+            Self::paste_types(false)
+        }
+
+        /// MIME types the current primary selection is offered as
+        pub fn list_primary_types() -> Result<Vec<String>> {
+            Self::paste_types(true)
+        }
+
+        fn paste_types(primary: bool) -> Result<Vec<String>> {
+            let mut command = Command::new("wl-paste");
+            if primary {
+                command.arg("--primary");
+            }
+            let output = command
+                .arg("--list-types")
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .output()
+                .map_err(|e| DitoxError::Clipboard(format!("Failed to run wl-paste: {}", e)))?;
+            if !output.status.success() {
+                return Ok(Vec::new());
+            }
+            Ok(String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect())
+        }
+
+        fn paste_text(primary: bool) -> Result<Option<String>> {
+            let mut command = Command::new("wl-paste");
+            if primary {
+                command.arg("--primary");
+            }
+            let output = command
                 .arg("--no-newline")
                 .stdout(Stdio::piped())
                 .stderr(Stdio::null())
@@ -148,8 +196,12 @@ mod platform {
         }
 
         /// Set clipboard text content
-        /// Uses wl-copy CLI which properly forks and daemonizes
+        /// Uses wl-copy CLI which properly forks and daemonizes, or OSC 52
+        /// over SSH
         pub fn set_text(content: &str) -> Result<()> {
+            if crate::osc52::active() {
+                return crate::osc52::copy(content);
+            }
             let mut child = Command::new("wl-copy")
                 .stdin(Stdio::piped())
                 .stdout(Stdio::null())
@@ -177,6 +229,59 @@ mod platform {
             Ok(())
         }
 
+        /// Type `text` into the focused window, for apps that don't accept
+        /// a programmatic paste. Uses wtype or ydotool on Wayland and
+        /// xdotool on X11, whichever is installed.
+        pub fn type_text(text: &str) -> Result<()> {
+            let wayland: [(&str, &[&str]); 2] =
+                [("wtype", &["-"]), ("ydotool", &["type", "--file", "-"])];
+            let x11: [(&str, &[&str]); 1] =
+                [("xdotool", &["type", "--clearmodifiers", "--file", "-"])];
+            let typers: Vec<_> = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+                wayland.iter().chain(x11.iter()).collect()
+            } else {
+                x11.iter().chain(wayland.iter()).collect()
+            };
+
+            for (program, args) in typers {
+                let mut child = match Command::new(program)
+                    .args(*args)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn()
+                {
+                    Ok(child) => child,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                    Err(e) => {
+                        return Err(DitoxError::Clipboard(format!(
+                            "Failed to spawn {}: {}",
+                            program, e
+                        )))
+                    }
+                };
+                if let Some(mut stdin) = child.stdin.take() {
+                    stdin.write_all(text.as_bytes()).map_err(|e| {
+                        DitoxError::Clipboard(format!("Failed to write to {}: {}", program, e))
+                    })?;
+                }
+                let status = child.wait().map_err(|e| {
+                    DitoxError::Clipboard(format!("Failed to wait for {}: {}", program, e))
+                })?;
+                if !status.success() {
+                    return Err(DitoxError::Clipboard(format!(
+                        "{} exited with status: {}",
+                        program, status
+                    )));
+                }
+                return Ok(());
+            }
+
+            Err(DitoxError::Clipboard(
+                "No typing tool found (install wtype, ydotool or xdotool)".into(),
+            ))
+        }
+
         /// Set clipboard image content from file path
         /// Uses wl-copy CLI which properly forks and daemonizes
         pub fn set_image(path: &str) -> Result<()> {
@@ -233,6 +338,9 @@ mod platform {
     use arboard::Clipboard as ArboardClipboard;
 
     impl Clipboard {
+        /// Name of the clipboard backend, for diagnostics
+        pub const BACKEND: &'static str = "windows (arboard)";
+
         /// Get current clipboard text content
         pub fn get_text() -> Result<Option<String>> {
             let mut clipboard = ArboardClipboard::new()
@@ -249,6 +357,22 @@ mod platform {
             }
         }
 
+        /// Clipboard formats aren't enumerated through arboard, so no
+        /// MIME hints are seen on Windows
+        pub fn list_types() -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        /// Windows has no primary selection
+        pub fn list_primary_types() -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+
+        /// Windows has no primary selection
+        pub fn get_primary_text() -> Result<Option<String>> {
+            Ok(None)
+        }
+
         /// Read the current clipboard image into memory. Does NOT write to
         /// disk — callers are responsible for deduplication against the DB
         /// and for deciding whether to persist via `Database::store_image_blob`.
@@ -297,8 +421,11 @@ mod platform {
             }
         }
 
-        /// Set clipboard text content
+        /// Set clipboard text content, through OSC 52 over SSH
         pub fn set_text(content: &str) -> Result<()> {
+            if crate::osc52::active() {
+                return crate::osc52::copy(content);
+            }
             let mut clipboard = ArboardClipboard::new()
                 .map_err(|e| DitoxError::Clipboard(format!("Failed to access clipboard: {}", e)))?;
 
@@ -307,6 +434,13 @@ mod platform {
                 .map_err(|e| DitoxError::Clipboard(format!("Failed to set clipboard text: {}", e)))
         }
 
+        /// Typing clips into other windows is only supported on Linux
+        pub fn type_text(_text: &str) -> Result<()> {
+            Err(DitoxError::Clipboard(
+                "Typing clips is not supported on Windows".into(),
+            ))
+        }
+
         /// Set clipboard image content from file path
         pub fn set_image(path: &str) -> Result<()> {
             let mut clipboard = ArboardClipboard::new()
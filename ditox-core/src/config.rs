@@ -1,24 +1,35 @@
+use crate::confirm::ConfirmConfig;
 use crate::error::{DitoxError, Result};
 use directories::ProjectDirs;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
 #[derive(Default)]
 pub struct Config {
     pub general: GeneralConfig,
     pub storage: StorageConfig,
+    pub backup: BackupConfig,
+    pub hooks: HooksConfig,
+    pub capture: CaptureConfig,
     pub ui: UiConfig,
     pub keybindings: KeybindingsConfig,
+    pub confirm: ConfirmConfig,
+    /// Shell commands usable as `ditox copy --transform <name>`; each gets
+    /// the text on stdin and prints the result
+    pub transforms: HashMap<String, String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct GeneralConfig {
     pub max_entries: usize,
     pub poll_interval_ms: u64,
+    /// Most entries to keep per tag (sub-tags included), e.g.
+    /// `{ log = 50, work = 500 }`. Enforced before `max_entries`.
+    pub tag_limits: HashMap<String, usize>,
 }
 
 impl Default for GeneralConfig {
@@ -26,18 +37,127 @@ impl Default for GeneralConfig {
         Self {
             max_entries: 500,
             poll_interval_ms: 250,
+            tag_limits: HashMap::new(),
         }
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
-#[derive(Default)]
 pub struct StorageConfig {
+    /// Database and images (default: the platform data directory)
     pub data_dir: Option<PathBuf>,
+    /// Watcher pid, health and logs (default: `$XDG_STATE_HOME/ditox` on
+    /// Linux, else with the data)
+    pub state_dir: Option<PathBuf>,
+    /// Most clip data to keep, in MB (text and image sizes). The watcher
+    /// evicts the oldest non-favorites beyond it. 0 means no cap.
+    pub max_storage_mb: u64,
+    /// How often the watcher re-applies retention and removes unused image
+    /// blobs, in minutes. 0 only prunes after captures.
+    pub prune_interval_mins: u64,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            data_dir: None,
+            state_dir: None,
+            max_storage_mb: 0,
+            prune_interval_mins: 60,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct BackupConfig {
+    /// How often the watcher writes a backup archive on its own
+    pub auto: AutoBackup,
+    /// Where automatic backups go (default: `backups/` in the data dir)
+    pub dir: Option<PathBuf>,
+    /// Automatic backups to keep; older ones are deleted. 0 keeps all.
+    pub keep: usize,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            auto: AutoBackup::Off,
+            dir: None,
+            keep: 7,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AutoBackup {
+    #[default]
+    Off,
+    Daily,
+    Weekly,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl AutoBackup {
+    /// Time between automatic backups, or None when they are off
+    pub fn interval(self) -> Option<std::time::Duration> {
+        const DAY: u64 = 24 * 60 * 60;
+        match self {
+            AutoBackup::Off => None,
+            AutoBackup::Daily => Some(std::time::Duration::from_secs(DAY)),
+            AutoBackup::Weekly => Some(std::time::Duration::from_secs(7 * DAY)),
+        }
+    }
+}
+
+/// Shell commands run on clip events; see `hooks`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// A clip was captured
+    pub on_add: Option<String>,
+    /// A clip was copied back to the clipboard
+    pub on_copy: Option<String>,
+    /// A clip was deleted
+    pub on_delete: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CaptureConfig {
+    /// Also capture the primary selection (select-to-copy, middle-click
+    /// paste) on Linux. Such entries match `is:primary`.
+    pub primary_selection: bool,
+    /// Clipboard contents offered with any of these MIME types are not
+    /// captured. Password managers use them to mark secrets; an empty list
+    /// captures everything.
+    pub ignore_mime_types: Vec<String>,
+    /// Wait until the clipboard has held the same contents this long
+    /// before capturing, so an app replacing it several times in a burst
+    /// only leaves its final contents. 0 captures on the first poll.
+    pub coalesce_ms: u64,
+    /// Text shorter than this (in characters, ignoring surrounding
+    /// whitespace) is not captured
+    pub min_text_length: usize,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            primary_selection: false,
+            ignore_mime_types: vec![
+                "x-kde-passwordManagerHint".to_string(),
+                "org.kde.klipper.skip".to_string(),
+                "ExcludeClipboardContentFromMonitorProcessing".to_string(),
+            ],
+            coalesce_ms: 0,
+            min_text_length: 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct UiConfig {
     pub show_preview: bool,
@@ -47,9 +167,74 @@ pub struct UiConfig {
     /// Font size in pixels (width, height) for image rendering
     /// Example: [9, 18] for 9x18 pixel font
     pub font_size: Option<(u16, u16)>,
+    /// Minimum number of ID characters shown in CLI listings. Longer
+    /// prefixes are printed when needed to stay unambiguous.
+    pub id_length: usize,
+    /// Language for messages (e.g. "de" or "pt_BR"), read from
+    /// `locales/{locale}.toml` in the config dir. Defaults to the
+    /// LC_ALL / LC_MESSAGES / LANG environment.
+    pub locale: Option<String>,
+    /// Where the preview pane sits next to the list
+    pub preview_position: PreviewPosition,
+    /// Share of the content area given to the preview pane, in percent
+    pub preview_size: u16,
+    /// Color JSON and code in the preview pane
+    pub syntax_highlight: bool,
+    /// Put between texts when several selected entries are copied or
+    /// merged as one
+    pub join_separator: String,
+    /// Wait this long after the last keystroke before searching, so fast
+    /// typing in a large history runs one search instead of one per key.
+    /// 0 searches on every key.
+    pub search_debounce_ms: u64,
+    /// Order of the history list (`o` cycles it in the TUI)
+    pub sort: SortOrder,
+    /// Split the list into Today / Yesterday / Last week / Older under
+    /// header rows when sorted by time
+    pub group_by_day: bool,
 }
 
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewPosition {
+    #[default]
+    Right,
+    Bottom,
+}
+
+/// Order of the history list
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Most recently used first
+    #[default]
+    Recent,
+    /// Most recently captured first
+    Created,
+    /// Most often copied first
+    MostUsed,
+    /// Largest first
+    Largest,
+}
+
+impl SortOrder {
+    /// The order after this one when cycling
+    pub fn next(self) -> Self {
+        match self {
+            SortOrder::Recent => SortOrder::Created,
+            SortOrder::Created => SortOrder::MostUsed,
+            SortOrder::MostUsed => SortOrder::Largest,
+            SortOrder::Largest => SortOrder::Recent,
+        }
+    }
+
+    /// True for orders by a timestamp, which can be grouped by day
+    pub fn is_by_time(self) -> bool {
+        matches!(self, SortOrder::Recent | SortOrder::Created)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum GraphicsProtocol {
     Kitty,
@@ -66,11 +251,20 @@ impl Default for UiConfig {
             theme: ThemeConfig::default(),
             graphics_protocol: None, // Auto-detect
             font_size: None,         // Auto-detect
+            id_length: 8,
+            locale: None,
+            preview_position: PreviewPosition::Right,
+            preview_size: 50,
+            syntax_highlight: true,
+            join_separator: "\n".to_string(),
+            search_debounce_ms: 80,
+            sort: SortOrder::Recent,
+            group_by_day: false,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum DateFormat {
     #[default]
@@ -78,13 +272,58 @@ pub enum DateFormat {
     Iso,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(default)]
 pub struct ThemeConfig {
     pub selected: String,
     pub border: String,
     pub text: String,
     pub muted: String,
+    /// Colors the terminal can show; theme colors are mapped to the
+    /// nearest one it has
+    pub color_depth: ColorDepth,
+}
+
+/// Color support of the terminal
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorDepth {
+    /// Detect from `COLORTERM` and `TERM`
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    /// 24-bit color
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    /// The xterm 256-color palette
+    #[serde(rename = "256")]
+    Ansi256,
+    /// The 16 ANSI colors
+    #[serde(rename = "16")]
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// Resolve `Auto` from the environment: `COLORTERM=truecolor` (or
+    /// `24bit`), a `-direct` `TERM` or Windows Terminal mean truecolor, a
+    /// `TERM` naming 256 colors means 256, and anything else gets the 16
+    /// ANSI colors
+    pub fn resolve(self) -> Self {
+        if self != ColorDepth::Auto {
+            return self;
+        }
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+        if matches!(colorterm.as_str(), "truecolor" | "24bit")
+            || term.ends_with("-direct")
+            || std::env::var_os("WT_SESSION").is_some()
+        {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
 }
 
 impl Default for ThemeConfig {
@@ -94,6 +333,7 @@ impl Default for ThemeConfig {
             border: "#565f89".to_string(),
             text: "#c0caf5".to_string(),
             muted: "#565f89".to_string(),
+            color_depth: ColorDepth::Auto,
         }
     }
 }
@@ -103,7 +343,7 @@ impl Default for ThemeConfig {
 /// Format: "key" = "action"
 /// Keys: "q", "ctrl+d", "alt+x", "shift+g", "enter", "esc", "tab", "space", "f1"-"f12"
 /// Actions: see `Action::config_name()` for all available actions
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(default)]
 pub struct KeybindingsConfig {
     /// Custom key bindings that override defaults
@@ -117,10 +357,14 @@ pub struct KeybindingsConfig {
 
 impl Config {
     pub fn load() -> Result<Self> {
-        let config_path = Self::get_config_path()?;
+        Self::load_from(&Self::get_config_path()?)
+    }
 
+    /// Load the config file at `config_path`, or the defaults if there is
+    /// none
+    pub fn load_from(config_path: &std::path::Path) -> Result<Self> {
         if config_path.exists() {
-            let content = std::fs::read_to_string(&config_path)?;
+            let content = std::fs::read_to_string(config_path)?;
             let config: Config = toml::from_str(&content)
                 .map_err(|e| DitoxError::Config(format!("Failed to parse config: {}", e)))?;
             Ok(config)
@@ -129,7 +373,7 @@ impl Config {
         }
     }
 
-    fn get_config_path() -> Result<PathBuf> {
+    pub fn get_config_path() -> Result<PathBuf> {
         ProjectDirs::from("com", "ditox", "ditox")
             .map(|dirs| dirs.config_dir().join("config.toml"))
             .ok_or_else(|| DitoxError::Config("Could not determine config directory".into()))
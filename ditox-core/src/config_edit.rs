@@ -0,0 +1,210 @@
+//! Reading and editing the config file from the command line
+//!
+//! Keys are dotted paths into the TOML, like `ui.theme.selected`. Which
+//! keys exist is taken from the serialized defaults: a section with no
+//! fields of its own (`keybindings`, `transforms`, `general.tag_limits`)
+//! takes any key, and optional settings serialize as null.
+//!
+//! [`set`] edits the file with `toml_edit`, so comments and layout survive,
+//! and only writes it if the result still loads.
+
+use crate::config::Config;
+use crate::error::{DitoxError, Result};
+use serde_json::Value as Json;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item};
+
+/// Effective value of `key`: the file's, else the default. None for unset
+/// optional settings and missing map entries.
+pub fn get(path: &Path, key: &str) -> Result<Option<Json>> {
+    let segments = split_key(key)?;
+    let config = serde_json::to_value(Config::load_from(path)?).map_err(json_error)?;
+    let mut node = &config;
+    for segment in &segments {
+        match node.get(segment) {
+            Some(next) => node = next,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(node.clone()).filter(|value| !value.is_null()))
+}
+
+/// A value from [`get`] as the user would write it: strings bare,
+/// sections as TOML (without unset optional settings)
+pub fn display(value: &Json) -> Result<String> {
+    match value {
+        Json::String(s) => Ok(s.clone()),
+        Json::Object(_) => toml::to_string(&strip_nulls(value.clone()))
+            .map_err(|e| DitoxError::Other(e.to_string())),
+        other => Ok(other.to_string()),
+    }
+}
+
+fn strip_nulls(value: Json) -> Json {
+    match value {
+        Json::Object(map) => map
+            .into_iter()
+            .filter(|(_, v)| !v.is_null())
+            .map(|(k, v)| (k, strip_nulls(v)))
+            .collect(),
+        other => other,
+    }
+}
+
+/// Set `key` to `value` in the config file, creating it if needed.
+/// `value` is read as TOML (`true`, `50`, `["a"]`, `"text"`), falling back
+/// to a plain string, so `ditox config set ui.locale de` works unquoted.
+pub fn set(path: &Path, key: &str, value: &str) -> Result<()> {
+    let segments = split_key(key)?;
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let doc: DocumentMut = content.parse().map_err(|e| invalid_file(path, &e))?;
+
+    let mut candidates = Vec::new();
+    if let Ok(parsed) = value.trim().parse::<toml_edit::Value>() {
+        candidates.push(parsed);
+    }
+    candidates.push(toml_edit::Value::from(value));
+
+    let mut first_error = None;
+    for candidate in candidates {
+        let mut edited = doc.clone();
+        insert(edited.as_item_mut(), &segments, candidate)?;
+        let text = edited.to_string();
+        match toml::from_str::<Config>(&text) {
+            Ok(_) => return write_atomically(path, &text),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    let error = first_error.map(|e| e.to_string()).unwrap_or_default();
+    Err(DitoxError::Config(format!(
+        "Invalid value for {}: {}",
+        key,
+        error.trim()
+    )))
+}
+
+/// Problems with the config file: a syntax or type error, or unknown keys.
+/// Empty if it is fine (or missing, which means all defaults).
+pub fn validate(path: &Path) -> Result<Vec<String>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let table: toml::Table = match toml::from_str(&content) {
+        Ok(table) => table,
+        Err(e) => return Ok(vec![e.to_string().trim().to_string()]),
+    };
+
+    let mut problems = Vec::new();
+    if let Err(e) = toml::from_str::<Config>(&content) {
+        problems.push(e.to_string().trim().to_string());
+    }
+    let schema = schema()?;
+    unknown_keys(&table, &schema, "", &mut problems);
+    Ok(problems)
+}
+
+/// The default config as JSON, which keeps optional settings as null
+fn schema() -> Result<Json> {
+    serde_json::to_value(Config::default()).map_err(json_error)
+}
+
+/// True if a section takes arbitrary keys
+fn is_free_map(node: &Json) -> bool {
+    node.as_object().is_some_and(|map| map.is_empty())
+}
+
+/// Whether `segments` names a setting
+fn known(schema: &Json, segments: &[&str]) -> bool {
+    let mut node = schema;
+    for segment in segments {
+        if is_free_map(node) {
+            return true;
+        }
+        match node.get(segment) {
+            Some(next) => node = next,
+            None => return false,
+        }
+    }
+    true
+}
+
+fn unknown_keys(table: &toml::Table, schema: &Json, prefix: &str, problems: &mut Vec<String>) {
+    for (key, value) in table {
+        let path = format!("{}{}", prefix, key);
+        match schema.get(key) {
+            None => problems.push(format!("Unknown key: {}", path)),
+            Some(node) => {
+                if let (Some(table), false) = (value.as_table(), is_free_map(node)) {
+                    unknown_keys(table, node, &format!("{}.", path), problems);
+                }
+            }
+        }
+    }
+}
+
+fn split_key(key: &str) -> Result<Vec<&str>> {
+    let segments: Vec<&str> = key.split('.').collect();
+    if segments.iter().any(|s| s.is_empty()) {
+        return Err(DitoxError::Config(format!("Invalid key: {}", key)));
+    }
+    if !known(&schema()?, &segments) {
+        return Err(DitoxError::Config(format!("Unknown key: {}", key)));
+    }
+    Ok(segments)
+}
+
+/// Put `value` at `segments` under `root`, creating tables on the way and
+/// keeping the comments around a value it replaces
+fn insert(root: &mut Item, segments: &[&str], mut value: toml_edit::Value) -> Result<()> {
+    let (last, parents) = segments.split_last().expect("keys are not empty");
+    let mut item = root;
+    for (i, segment) in parents.iter().enumerate() {
+        if item.get(segment).is_none() {
+            let mut table = toml_edit::Table::new();
+            table.set_implicit(true);
+            item[segment] = Item::Table(table);
+        }
+        item = &mut item[segment];
+        if !item.is_table_like() {
+            return Err(DitoxError::Config(format!(
+                "{} is not a section",
+                segments[..=i].join(".")
+            )));
+        }
+    }
+    if let Some(old) = item.get(last).and_then(Item::as_value) {
+        *value.decor_mut() = old.decor().clone();
+    }
+    item[last] = Item::Value(value);
+    Ok(())
+}
+
+fn write_atomically(path: &Path, content: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("toml.tmp");
+    std::fs::write(&tmp, content)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn invalid_file(path: &Path, e: &dyn std::fmt::Display) -> DitoxError {
+    DitoxError::Config(format!(
+        "Failed to parse {}: {}",
+        path.display(),
+        e.to_string().trim()
+    ))
+}
+
+fn json_error(e: serde_json::Error) -> DitoxError {
+    DitoxError::Other(format!("JSON serialization error: {}", e))
+}
@@ -0,0 +1,198 @@
+//! Confirmation policy for destructive actions
+//!
+//! Which actions ask before running, and how, is configured in the
+//! `[confirm]` section of the config:
+//!
+//! ```toml
+//! [confirm]
+//! delete = "double-press"   # press the delete key twice
+//! bulk_delete = "modal"     # y/n dialog
+//! clear_all = "typed"       # type the phrase to clear history
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Phrase to type when a confirmation uses [`ConfirmStyle::Typed`]
+pub const CONFIRM_PHRASE: &str = "delete all";
+
+/// How long a first press waits for the second under
+/// [`ConfirmStyle::DoublePress`]
+pub const DOUBLE_PRESS_WINDOW: Duration = Duration::from_millis(1500);
+
+/// Action that may need confirmation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmAction {
+    /// Delete the currently selected entry
+    DeleteSelected,
+    /// Delete every multi-selected entry
+    DeleteMulti,
+    /// Delete all entries (clear history)
+    ClearAll,
+}
+
+/// How an action is confirmed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfirmStyle {
+    /// Run without asking
+    None,
+    /// Run when the same action is triggered twice in quick succession
+    DoublePress,
+    /// Ask in a y/n dialog
+    Modal,
+    /// Ask in a dialog that only accepts [`CONFIRM_PHRASE`]
+    Typed,
+}
+
+/// Confirmation style per action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfirmConfig {
+    pub delete: ConfirmStyle,
+    pub bulk_delete: ConfirmStyle,
+    pub clear_all: ConfirmStyle,
+}
+
+impl Default for ConfirmConfig {
+    fn default() -> Self {
+        Self {
+            delete: ConfirmStyle::Modal,
+            bulk_delete: ConfirmStyle::Modal,
+            clear_all: ConfirmStyle::Modal,
+        }
+    }
+}
+
+impl ConfirmConfig {
+    /// Configured style for an action
+    pub fn style_for(&self, action: ConfirmAction) -> ConfirmStyle {
+        match action {
+            ConfirmAction::DeleteSelected => self.delete,
+            ConfirmAction::DeleteMulti => self.bulk_delete,
+            ConfirmAction::ClearAll => self.clear_all,
+        }
+    }
+}
+
+/// What a frontend should do after an action was requested
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmStep {
+    /// Run the action now
+    Run,
+    /// Show a dialog in this style (Modal or Typed)
+    Ask(ConfirmStyle),
+    /// First of a double press; run if requested again soon
+    AwaitSecondPress,
+}
+
+/// Tracks double presses so every frontend applies the policy the same way
+#[derive(Debug, Default)]
+pub struct ConfirmTracker {
+    first_press: Option<(ConfirmAction, Instant)>,
+}
+
+impl ConfirmTracker {
+    /// Decide what to do about a requested action under `config`
+    pub fn request(&mut self, config: &ConfirmConfig, action: ConfirmAction) -> ConfirmStep {
+        self.request_at(config, action, Instant::now())
+    }
+
+    fn request_at(
+        &mut self,
+        config: &ConfirmConfig,
+        action: ConfirmAction,
+        now: Instant,
+    ) -> ConfirmStep {
+        match config.style_for(action) {
+            ConfirmStyle::None => ConfirmStep::Run,
+            style @ (ConfirmStyle::Modal | ConfirmStyle::Typed) => ConfirmStep::Ask(style),
+            ConfirmStyle::DoublePress => match self.first_press.take() {
+                Some((first, at))
+                    if first == action && now.duration_since(at) <= DOUBLE_PRESS_WINDOW =>
+                {
+                    ConfirmStep::Run
+                }
+                _ => {
+                    self.first_press = Some((action, now));
+                    ConfirmStep::AwaitSecondPress
+                }
+            },
+        }
+    }
+
+    /// Forget a pending first press (e.g. after any other key)
+    pub fn reset(&mut self) {
+        self.first_press = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_styles_map_to_steps() {
+        let config = ConfirmConfig {
+            delete: ConfirmStyle::None,
+            bulk_delete: ConfirmStyle::Modal,
+            clear_all: ConfirmStyle::Typed,
+        };
+        let mut tracker = ConfirmTracker::default();
+        assert_eq!(
+            tracker.request(&config, ConfirmAction::DeleteSelected),
+            ConfirmStep::Run
+        );
+        assert_eq!(
+            tracker.request(&config, ConfirmAction::DeleteMulti),
+            ConfirmStep::Ask(ConfirmStyle::Modal)
+        );
+        assert_eq!(
+            tracker.request(&config, ConfirmAction::ClearAll),
+            ConfirmStep::Ask(ConfirmStyle::Typed)
+        );
+    }
+
+    #[test]
+    fn test_double_press() {
+        let config = ConfirmConfig {
+            delete: ConfirmStyle::DoublePress,
+            ..ConfirmConfig::default()
+        };
+        let mut tracker = ConfirmTracker::default();
+        let start = Instant::now();
+        let delete = ConfirmAction::DeleteSelected;
+
+        assert_eq!(
+            tracker.request_at(&config, delete, start),
+            ConfirmStep::AwaitSecondPress
+        );
+        assert_eq!(
+            tracker.request_at(&config, delete, start + Duration::from_millis(300)),
+            ConfirmStep::Run
+        );
+
+        // Too slow: the second press starts over
+        tracker.request_at(&config, delete, start);
+        assert_eq!(
+            tracker.request_at(&config, delete, start + DOUBLE_PRESS_WINDOW * 2),
+            ConfirmStep::AwaitSecondPress
+        );
+
+        // Interrupted by another key
+        tracker.reset();
+        assert_eq!(
+            tracker.request_at(&config, delete, start),
+            ConfirmStep::AwaitSecondPress
+        );
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config: ConfirmConfig =
+            toml::from_str("delete = \"double-press\"\nclear_all = \"typed\"").unwrap();
+        assert_eq!(config.delete, ConfirmStyle::DoublePress);
+        assert_eq!(config.bulk_delete, ConfirmStyle::Modal);
+        assert_eq!(config.clear_all, ConfirmStyle::Typed);
+    }
+}
@@ -1,12 +1,22 @@
 use crate::collection::Collection;
+use crate::config::{SortOrder, StorageConfig};
 use crate::entry::{Entry, EntryType};
 use crate::error::{DitoxError, Result};
-use crate::stats::{Stats, TopEntry};
+use crate::query::{Query, SearchHit, HIT_CLOSE, HIT_OPEN};
+use crate::stats::{DayCount, Stats, TagCount, TopEntry};
 use chrono::{DateTime, Duration, Utc};
 use directories::ProjectDirs;
-use rusqlite::{params, Connection, OptionalExtension};
+use regex::Regex;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::types::Value;
+use rusqlite::{
+    params, params_from_iter, Connection, OpenFlags, OptionalExtension, Transaction,
+    TransactionBehavior, MAIN_DB,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 /// Current schema version. Bumped whenever `init_schema` grows a new migration
 /// step. Historical values:
@@ -14,6 +24,18 @@ use std::path::{Path, PathBuf};
 /// - 1: content-addressed image layout + `entries.image_extension` column
 pub const SCHEMA_VERSION: i64 = 1;
 
+/// How a database's schema compares with this build's [`SCHEMA_VERSION`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaCompat {
+    /// Up to date
+    Current,
+    /// Older (or new and empty); `init_schema` upgrades it
+    Older(i64),
+    /// Written by a newer ditox. Reading usually still works, since
+    /// migrations only add to the schema, but writing could break it.
+    Newer(i64),
+}
+
 /// Subdirectory inside `images/` that holds files quarantined by
 /// `ditox repair --fix-hashes` because their on-disk hash didn't match the
 /// DB hash. We don't auto-delete — the user may want to inspect them.
@@ -27,10 +49,65 @@ pub const TMP_SUFFIX: &str = ".tmp";
 /// we do (large screenshots complete in milliseconds even on slow disks).
 pub const TMP_SWEEP_AGE_SECS: u64 = 60;
 
+/// Optional FTS5 table using the trigram tokenizer, for substring search.
+/// Built on demand by `ditox migrate --enable-trigram`.
+const TRIGRAM_TABLE: &str = "entries_trigram";
+
+/// Days covered by the per-day capture counts in [`Stats`]
+const STATS_DAYS: i64 = 30;
+
+/// Number of tokens FTS5 puts in a search snippet (its maximum is 64).
+/// Roughly one list row's worth of text.
+const SNIPPET_TOKENS: i64 = 16;
+
+/// True for a SHA-256 digest in lowercase hex, the name of every blob in
+/// the image store
+pub fn is_blob_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// Check that `hash` and `extension` name an image blob: a SHA-256 in
+/// lowercase hex and plain alphanumerics, so values read from manifests,
+/// archives or other databases can never point outside the store
+pub fn check_blob_name(hash: &str, extension: &str) -> Result<()> {
+    if !is_blob_hash(hash) {
+        return Err(DitoxError::Other(format!("invalid image hash: {}", hash)));
+    }
+    if extension.is_empty() || !extension.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(DitoxError::Other(format!(
+            "invalid image extension: {}",
+            extension
+        )));
+    }
+    Ok(())
+}
+
+/// ORDER BY clause listing entries in `sort` order
+fn order_by(sort: SortOrder) -> &'static str {
+    match sort {
+        SortOrder::Recent => "last_used DESC",
+        SortOrder::Created => "created_at DESC",
+        SortOrder::MostUsed => "usage_count DESC, last_used DESC",
+        SortOrder::Largest => "byte_size DESC, last_used DESC",
+    }
+}
+
 pub struct Database {
     conn: Connection,
 }
 
+/// Outcome of [`Database::maintain`]
+#[derive(Debug)]
+pub struct MaintenanceReport {
+    /// Lines from `PRAGMA integrity_check`; empty if the database is sound
+    pub problems: Vec<String>,
+    /// Entries put back into the search index
+    pub indexed: usize,
+    /// Database plus WAL size before and after, in bytes
+    pub size_before: u64,
+    pub size_after: u64,
+}
+
 /// True if a path ends with our tmp suffix. We include PID in real tmp names
 /// (`…{pid}.tmp`) so a simple `ends_with(".tmp")` check covers both the
 /// current and any legacy one-off tmp layouts.
@@ -68,6 +145,68 @@ fn sweep_one(entry: &std::fs::DirEntry, cutoff: std::time::SystemTime) {
     }
 }
 
+/// Per-connection setup shared by every `open`
+fn configure(conn: &Connection) -> Result<()> {
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    register_regexp(conn)
+}
+
+/// Register the `regexp(pattern, text)` SQL function, which also backs
+/// SQLite's `text REGEXP pattern` operator. The compiled regex is cached as
+/// auxiliary data on the pattern argument, so a query compiles it once
+/// rather than once per row.
+fn register_regexp(conn: &Connection) -> Result<()> {
+    conn.create_scalar_function(
+        "regexp",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| {
+            let re: std::sync::Arc<Regex> = ctx.get_or_create_aux(0, |vr| {
+                Regex::new(vr.as_str()?)
+                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })
+            })?;
+            let text = match ctx.get_raw(1) {
+                rusqlite::types::ValueRef::Text(t) => String::from_utf8_lossy(t),
+                _ => return Ok(false),
+            };
+            Ok(re.is_match(&text))
+        },
+    )?;
+    Ok(())
+}
+
+/// Metadata key marking which selection an entry was captured from. Only
+/// `primary` is recorded; regular clipboard copies carry no marker.
+pub const SELECTION_META_KEY: &str = "selection";
+
+/// How long a statement waits for another connection's write lock before
+/// failing with "database is locked". SQLite's busy handler retries with
+/// growing sleeps (1 ms up to 100 ms) until then.
+const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Data directory set by [`Database::override_data_dir`]
+static DATA_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// State directory set by [`Database::override_state_dir`]
+static STATE_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Environment variable overriding the data directory (and the config's
+/// `storage.data_dir`)
+pub const DATA_DIR_ENV: &str = "DITOX_DATA_DIR";
+
+/// Environment variable overriding the state directory (and the config's
+/// `storage.state_dir`)
+pub const STATE_DIR_ENV: &str = "DITOX_STATE_DIR";
+
+/// Files and directories that live in the state directory
+const STATE_FILES: &[&str] = &["watcher.pid", "watcher.health", "incognito", "logs"];
+
+fn env_dir(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
 /// Helper enum for filter query parameters
 enum FilterParams<'a> {
     None,
@@ -86,6 +225,7 @@ impl Database {
         }
 
         let conn = Connection::open(&db_path)?;
+        configure(&conn)?;
         Ok(Self { conn })
     }
 
@@ -95,21 +235,112 @@ impl Database {
             std::fs::create_dir_all(parent)?;
         }
         let conn = Connection::open(&path)?;
+        configure(&conn)?;
         Ok(Self { conn })
     }
 
-    fn get_db_path() -> Result<PathBuf> {
-        ProjectDirs::from("com", "ditox", "ditox")
-            .map(|dirs| dirs.data_dir().join("ditox.db"))
-            .ok_or_else(|| DitoxError::Config("Could not determine data directory".into()))
+    /// Open an existing database without creating or writing anything
+    pub fn open_read_only(path: &Path) -> Result<Self> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        configure(&conn)?;
+        Ok(Self { conn })
+    }
+
+    /// Path of the history database
+    pub fn get_db_path() -> Result<PathBuf> {
+        Ok(Self::get_data_dir()?.join("ditox.db"))
+    }
+
+    /// Use `dir` instead of the platform data directory for the database,
+    /// images and state files for the rest of this process (e.g. demo
+    /// mode). Must run before anything resolves a path; returns false if an
+    /// override was already set.
+    pub fn override_data_dir(dir: PathBuf) -> bool {
+        DATA_DIR_OVERRIDE.set(dir).is_ok()
+    }
+
+    /// Like [`Database::override_data_dir`], for the state directory
+    pub fn override_state_dir(dir: PathBuf) -> bool {
+        STATE_DIR_OVERRIDE.set(dir).is_ok()
+    }
+
+    /// Use the directories from `[storage]`, unless the environment already
+    /// names them
+    pub fn apply_storage_config(storage: &StorageConfig) {
+        if env_dir(DATA_DIR_ENV).is_none() {
+            if let Some(dir) = &storage.data_dir {
+                Self::override_data_dir(dir.clone());
+            }
+        }
+        if env_dir(STATE_DIR_ENV).is_none() {
+            if let Some(dir) = &storage.state_dir {
+                Self::override_state_dir(dir.clone());
+            }
+        }
     }
 
+    /// Database and images: `$DITOX_DATA_DIR`, else the platform data
+    /// directory (`$XDG_DATA_HOME/ditox` on Linux)
     pub fn get_data_dir() -> Result<PathBuf> {
+        if let Some(dir) = DATA_DIR_OVERRIDE.get() {
+            return Ok(dir.clone());
+        }
+        if let Some(dir) = env_dir(DATA_DIR_ENV) {
+            return Ok(dir);
+        }
         ProjectDirs::from("com", "ditox", "ditox")
             .map(|dirs| dirs.data_dir().to_path_buf())
             .ok_or_else(|| DitoxError::Config("Could not determine data directory".into()))
     }
 
+    /// The watcher's pid, health and log files and the incognito flag:
+    /// `$DITOX_STATE_DIR`, else `$XDG_STATE_HOME/ditox` on Linux. A custom
+    /// data directory keeps them with it, as do platforms without a state
+    /// directory.
+    pub fn get_state_dir() -> Result<PathBuf> {
+        if let Some(dir) = STATE_DIR_OVERRIDE.get() {
+            return Ok(dir.clone());
+        }
+        if let Some(dir) = env_dir(STATE_DIR_ENV) {
+            return Ok(dir);
+        }
+        if DATA_DIR_OVERRIDE.get().is_some() || env_dir(DATA_DIR_ENV).is_some() {
+            return Self::get_data_dir();
+        }
+        match ProjectDirs::from("com", "ditox", "ditox")
+            .and_then(|dirs| dirs.state_dir().map(Path::to_path_buf))
+        {
+            Some(dir) => Ok(dir),
+            None => Self::get_data_dir(),
+        }
+    }
+
+    /// Move state files an older version kept in the data directory to the
+    /// state directory, skipping any the state directory already has.
+    /// Returns the names moved.
+    pub fn migrate_state_files() -> Result<Vec<&'static str>> {
+        let data_dir = Self::get_data_dir()?;
+        let state_dir = Self::get_state_dir()?;
+        let mut moved = Vec::new();
+        if data_dir == state_dir {
+            return Ok(moved);
+        }
+        for name in STATE_FILES {
+            let from = data_dir.join(name);
+            let to = state_dir.join(name);
+            if from.symlink_metadata().is_err() || to.symlink_metadata().is_ok() {
+                continue;
+            }
+            std::fs::create_dir_all(&state_dir)?;
+            std::fs::rename(&from, &to)?;
+            moved.push(*name);
+        }
+        Ok(moved)
+    }
+
     pub fn get_images_dir() -> Result<PathBuf> {
         Ok(Self::get_data_dir()?.join("images"))
     }
@@ -119,10 +350,10 @@ impl Database {
     /// Layout: `images/{hash[..2]}/{hash}.{extension}`. The 2-char prefix
     /// directory fans the tree out so even pathological users with tens of
     /// thousands of images keep each subdirectory under a few hundred files.
+    ///
+    /// Both parts are checked with [`check_blob_name`].
     pub fn image_path(hash: &str, extension: &str) -> Result<PathBuf> {
-        if hash.len() < 2 {
-            return Err(DitoxError::Other(format!("invalid image hash: {}", hash)));
-        }
+        check_blob_name(hash, extension)?;
         let base = Self::get_images_dir()?;
         Ok(base
             .join(&hash[..2])
@@ -374,6 +605,15 @@ impl Database {
             ",
         )?;
 
+        // Don't touch a schema from a newer build
+        if let SchemaCompat::Newer(version) = self.schema_compat() {
+            return Err(DitoxError::MigrationNeeded(format!(
+                "The database has schema version {}, newer than this build supports ({}). \
+                 Upgrade ditox; list, search, get, stats and export still work read-only.",
+                version, SCHEMA_VERSION
+            )));
+        }
+
         // Create table with all columns
         self.conn.execute_batch(
             "
@@ -458,13 +698,76 @@ impl Database {
             ",
         )?;
 
-        // Create indexes (after last_used column exists)
+        // Generic key/value metadata attached to entries (source app, URL
+        // title, OCR text, plugin data, …). New kinds of metadata don't
+        // need a schema migration; rows follow their entry on delete.
+        self.conn.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS entry_meta (
+                entry_id TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (entry_id, key)
+            );
+            CREATE TRIGGER IF NOT EXISTS entries_meta_ad AFTER DELETE ON entries BEGIN
+                DELETE FROM entry_meta WHERE entry_id = old.id;
+            END;
+            ",
+        )?;
+
+        // Free-form tags on entries, matched by `tag:` in queries
+        self.conn.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS entry_tags (
+                entry_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                PRIMARY KEY (entry_id, name)
+            );
+            CREATE INDEX IF NOT EXISTS idx_entry_tags_name ON entry_tags(name);
+            CREATE TRIGGER IF NOT EXISTS entries_tags_ad AFTER DELETE ON entries BEGIN
+                DELETE FROM entry_tags WHERE entry_id = old.id;
+            END;
+            ",
+        )?;
+
+        // User-defined names for entries (`ditox alias set addr <id>`)
+        self.conn.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS entry_aliases (
+                name TEXT PRIMARY KEY,
+                entry_id TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_entry_aliases_entry ON entry_aliases(entry_id);
+            CREATE TRIGGER IF NOT EXISTS entries_aliases_ad AFTER DELETE ON entries BEGIN
+                DELETE FROM entry_aliases WHERE entry_id = old.id;
+            END;
+            ",
+        )?;
+
+        // Named queries ("views"), stored as serialized `Query` JSON
+        self.conn.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS saved_searches (
+                name TEXT PRIMARY KEY,
+                query TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            ",
+        )?;
+
+        // Create indexes (after last_used column exists). One per `SortOrder`
+        // so no list order sorts the whole table, plus the type and
+        // favorite tabs.
         self.conn.execute_batch(
             "
             CREATE INDEX IF NOT EXISTS idx_created_at ON entries(created_at DESC);
             CREATE INDEX IF NOT EXISTS idx_last_used ON entries(last_used DESC);
             CREATE INDEX IF NOT EXISTS idx_hash ON entries(hash);
             CREATE INDEX IF NOT EXISTS idx_collection_id ON entries(collection_id);
+            CREATE INDEX IF NOT EXISTS idx_type_last_used ON entries(entry_type, last_used DESC);
+            CREATE INDEX IF NOT EXISTS idx_pinned_last_used ON entries(pinned, last_used DESC);
+            CREATE INDEX IF NOT EXISTS idx_usage ON entries(usage_count DESC, last_used DESC);
+            CREATE INDEX IF NOT EXISTS idx_size ON entries(byte_size DESC, last_used DESC);
             ",
         )?;
 
@@ -537,6 +840,35 @@ impl Database {
         Ok(())
     }
 
+    /// Schema version recorded in the database, if any
+    pub fn schema_version(&self) -> Option<i64> {
+        self.read_schema_version()
+    }
+
+    /// Compare the recorded schema version with this build's
+    pub fn schema_compat(&self) -> SchemaCompat {
+        match self.read_schema_version().unwrap_or(0) {
+            version if version > SCHEMA_VERSION => SchemaCompat::Newer(version),
+            version if version < SCHEMA_VERSION => SchemaCompat::Older(version),
+            _ => SchemaCompat::Current,
+        }
+    }
+
+    /// Counter that changes whenever another connection (the watcher, a
+    /// CLI command) commits to the database. Cheap enough to poll.
+    pub fn data_version(&self) -> Result<i64> {
+        Ok(self
+            .conn
+            .query_row("PRAGMA data_version", [], |row| row.get(0))?)
+    }
+
+    /// SQLite journal mode (`delete`, `wal`, ...)
+    pub fn journal_mode(&self) -> Result<String> {
+        Ok(self
+            .conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))?)
+    }
+
     fn read_schema_version(&self) -> Option<i64> {
         self.conn
             .query_row(
@@ -672,8 +1004,28 @@ impl Database {
         Ok(())
     }
 
-    pub fn insert(&self, entry: &Entry) -> Result<()> {
-        self.conn.execute(
+    /// Run `f` in a transaction, committing if it succeeds and rolling
+    /// back every write it made if it fails. The write lock is taken up
+    /// front, so a concurrent writer makes this wait rather than fail
+    /// halfway through.
+    pub fn in_transaction<T>(&self, f: impl FnOnce(&Self) -> Result<T>) -> Result<T> {
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let value = f(self)?;
+        tx.commit()?;
+        Ok(value)
+    }
+
+    /// Run `f` against one consistent snapshot, without blocking writers
+    pub fn in_read_transaction<T>(&self, f: impl FnOnce(&Self) -> Result<T>) -> Result<T> {
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Deferred)?;
+        let value = f(self)?;
+        tx.commit()?;
+        Ok(value)
+    }
+
+    /// Insert an entry. Returns false if its id is already taken.
+    pub fn insert(&self, entry: &Entry) -> Result<bool> {
+        let added = self.conn.execute(
             "INSERT OR IGNORE INTO entries (id, entry_type, content, hash, byte_size, created_at, last_used, pinned, notes, collection_id, image_extension)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
@@ -690,7 +1042,7 @@ impl Database {
                 entry.image_extension,
             ],
         )?;
-        Ok(())
+        Ok(added > 0)
     }
 
     pub fn get_all(&self, limit: usize) -> Result<Vec<Entry>> {
@@ -719,6 +1071,50 @@ impl Database {
         Ok(entry)
     }
 
+    /// IDs of entries whose ID starts with `prefix`, at most `limit` of them
+    pub fn find_ids_by_prefix(&self, prefix: &str, limit: usize) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id FROM entries
+             WHERE substr(id, 1, length(?1)) = ?1
+             ORDER BY id
+             LIMIT ?2",
+        )?;
+        let ids = stmt
+            .query_map(params![prefix, limit as i64], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(ids)
+    }
+
+    /// Shortest prefix of each of `ids` (at least `min_len` chars) that no
+    /// other entry in the database shares, in the same order as `ids`
+    pub fn unique_id_prefixes(&self, ids: &[&str], min_len: usize) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("SELECT id FROM entries ORDER BY id")?;
+        let all: Vec<String> = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let common =
+            |a: &str, b: &str| a.chars().zip(b.chars()).take_while(|(x, y)| x == y).count();
+
+        Ok(ids
+            .iter()
+            .map(|id| {
+                // Only the sorted neighbours can share a longer prefix
+                let pos = all.partition_point(|other| other.as_str() < *id);
+                let mut needed = min_len;
+                for other in [pos.checked_sub(1), Some(pos), Some(pos + 1)]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|i| all.get(i))
+                    .filter(|other| other.as_str() != *id)
+                {
+                    needed = needed.max(common(id, other) + 1);
+                }
+                id.chars().take(needed).collect()
+            })
+            .collect())
+    }
+
     pub fn get_by_index(&self, index: usize) -> Result<Option<Entry>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, entry_type, content, hash, byte_size, created_at, last_used, pinned, notes, collection_id, image_extension
@@ -737,7 +1133,9 @@ impl Database {
     /// Delete a single entry. For image rows the backing blob is pruned
     /// iff no other live row references the same hash.
     pub fn delete(&mut self, id: &str) -> Result<bool> {
-        let tx = self.conn.transaction()?;
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
         let removed: Option<(String, String, Option<String>)> = tx
             .query_row(
                 "DELETE FROM entries WHERE id = ?1
@@ -800,7 +1198,9 @@ impl Database {
 
     /// Wipe every entry. Every image row's blob is pruned.
     pub fn clear_all(&mut self) -> Result<usize> {
-        let tx = self.conn.transaction()?;
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
         // Queue every image row's blob BEFORE deletion (need hash+ext).
         tx.execute(
             "INSERT OR IGNORE INTO pending_blob_prunes (hash, extension, queued_at)
@@ -824,6 +1224,44 @@ impl Database {
         Ok(count > 0)
     }
 
+    /// An entry with this content hash, if any
+    pub fn get_by_hash(&self, hash: &str) -> Result<Option<Entry>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, entry_type, content, hash, byte_size, created_at, last_used, pinned, notes, collection_id, image_extension
+             FROM entries WHERE hash = ?1 LIMIT 1",
+        )?;
+
+        let entry = stmt.query_row([hash], Self::row_to_entry).optional()?;
+
+        Ok(entry)
+    }
+
+    /// Fold another copy of an entry into the stored one `id`: it becomes a
+    /// favorite if either copy is, keeps the earliest creation and latest
+    /// use, and takes the other's notes if it has none. Returns false if
+    /// nothing changed.
+    pub fn merge_entry(&self, id: &str, other: &Entry) -> Result<bool> {
+        // RFC 3339 UTC timestamps compare correctly as strings
+        let rows = self.conn.execute(
+            "UPDATE entries SET
+                 pinned = MAX(pinned, ?2),
+                 created_at = MIN(created_at, ?3),
+                 last_used = MAX(last_used, ?4),
+                 notes = COALESCE(notes, ?5)
+             WHERE id = ?1
+               AND (pinned < ?2 OR created_at > ?3 OR last_used < ?4
+                    OR (notes IS NULL AND ?5 IS NOT NULL))",
+            params![
+                id,
+                other.favorite as i32,
+                other.created_at.to_rfc3339(),
+                other.last_used.to_rfc3339(),
+                other.notes,
+            ],
+        )?;
+        Ok(rows > 0)
+    }
+
     pub fn toggle_favorite(&self, id: &str) -> Result<bool> {
         let rows = self
             .conn
@@ -841,32 +1279,123 @@ impl Database {
         Ok(rows > 0)
     }
 
+    /// How many times an entry has been copied
+    pub fn usage_count(&self, id: &str) -> Result<i64> {
+        let count = self.conn.query_row(
+            "SELECT COALESCE(usage_count, 0) FROM entries WHERE id = ?1",
+            [id],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    pub fn set_usage_count(&self, id: &str, count: i64) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE entries SET usage_count = ?1 WHERE id = ?2",
+            params![count, id],
+        )?;
+        Ok(rows > 0)
+    }
+
     /// LRU eviction of non-pinned entries beyond `max_entries`. Image blobs
     /// are queued for pruning inside the same transaction.
     pub fn cleanup_old(&mut self, max_entries: usize) -> Result<usize> {
-        let tx = self.conn.transaction()?;
+        self.evict_lru("", None, max_entries)
+    }
+
+    /// LRU eviction of non-pinned entries tagged `tag` (or one of its
+    /// sub-tags) beyond the newest `max_entries` of them, so a noisy tag
+    /// can't crowd out the rest of the history.
+    pub fn cleanup_tagged(&mut self, tag: &str, max_entries: usize) -> Result<usize> {
+        self.evict_lru(
+            "AND id IN (SELECT entry_id FROM entry_tags
+                        WHERE name = ?3 OR (name >= ?3 || '/' AND name < ?3 || '0'))",
+            Some(tag.trim()),
+            max_entries,
+        )
+    }
+
+    /// Apply every per-tag limit, then the global one. Returns how many
+    /// entries were evicted in total.
+    pub fn enforce_retention(
+        &mut self,
+        max_entries: usize,
+        tag_limits: &HashMap<String, usize>,
+    ) -> Result<usize> {
+        let mut removed = 0;
+        for (tag, &limit) in tag_limits {
+            removed += self.cleanup_tagged(tag, limit)?;
+        }
+        Ok(removed + self.cleanup_old(max_entries)?)
+    }
+
+    /// Evict non-pinned entries matching the extra `scope` condition
+    /// (which may use `?3` for `scope_param`) beyond the newest `keep`.
+    fn evict_lru(&mut self, scope: &str, scope_param: Option<&str>, keep: usize) -> Result<usize> {
+        let mut values = vec![
+            Value::Text(Utc::now().to_rfc3339()),
+            Value::Integer(keep as i64),
+        ];
+        values.extend(scope_param.map(|p| Value::Text(p.to_string())));
+        let victims = format!(
+            "SELECT id FROM entries
+             WHERE pinned = 0 {scope}
+             ORDER BY last_used DESC
+             LIMIT -1 OFFSET ?2"
+        );
+        self.evict(&victims, &values)
+    }
+
+    /// Evict the oldest non-pinned entries until the stored clip bytes
+    /// (text and image sizes) fit in `max_bytes`. Favorites count towards
+    /// the total but are never evicted.
+    pub fn enforce_storage_cap(&mut self, max_bytes: u64) -> Result<usize> {
+        let pinned: i64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(byte_size), 0) FROM entries WHERE pinned = 1",
+            [],
+            |row| row.get(0),
+        )?;
+        let budget = (max_bytes as i64).saturating_sub(pinned).max(0);
+        let values = vec![Value::Text(Utc::now().to_rfc3339()), Value::Integer(budget)];
+        let victims = "SELECT id FROM (
+                 SELECT id, SUM(byte_size) OVER (
+                     ORDER BY last_used DESC, id ROWS UNBOUNDED PRECEDING
+                 ) AS running
+                 FROM entries WHERE pinned = 0
+             ) WHERE running > ?2";
+        self.evict(victims, &values)
+    }
+
+    /// Total stored clip bytes, the quantity [`enforce_storage_cap`] limits
+    pub fn stored_bytes(&self) -> Result<u64> {
+        let bytes: i64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(byte_size), 0) FROM entries",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(bytes as u64)
+    }
+
+    /// Delete the entries selected by `victims` (bound with `values`, whose
+    /// `?1` is the prune queue timestamp), queueing their image blobs
+    fn evict(&mut self, victims: &str, values: &[Value]) -> Result<usize> {
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
         // Queue image blobs of rows we're about to evict.
         tx.execute(
-            "INSERT OR IGNORE INTO pending_blob_prunes (hash, extension, queued_at)
-             SELECT hash, COALESCE(image_extension, 'png'), ?1
-             FROM entries
-             WHERE entry_type = 'image'
-               AND id IN (
-                   SELECT id FROM entries
-                   WHERE pinned = 0
-                   ORDER BY last_used DESC
-                   LIMIT -1 OFFSET ?2
-               )",
-            params![Utc::now().to_rfc3339(), max_entries as i64],
+            &format!(
+                "INSERT OR IGNORE INTO pending_blob_prunes (hash, extension, queued_at)
+                 SELECT hash, COALESCE(image_extension, 'png'), ?1
+                 FROM entries
+                 WHERE entry_type = 'image' AND id IN ({victims})"
+            ),
+            params_from_iter(values),
         )?;
+        // Unused ?1 still counts, so the same values bind here
         let rows = tx.execute(
-            "DELETE FROM entries WHERE id IN (
-                SELECT id FROM entries
-                WHERE pinned = 0
-                ORDER BY last_used DESC
-                LIMIT -1 OFFSET ?1
-            )",
-            [max_entries as i64],
+            &format!("DELETE FROM entries WHERE id IN ({victims})"),
+            params_from_iter(values),
         )?;
         tx.commit()?;
 
@@ -874,6 +1403,17 @@ impl Database {
         Ok(rows)
     }
 
+    /// Number of entries tagged `tag` or one of its sub-tags
+    pub fn count_tagged(&self, tag: &str) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(DISTINCT entry_id) FROM entry_tags
+             WHERE name = ?1 OR (name >= ?1 || '/' AND name < ?1 || '0')",
+            [tag.trim()],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
     pub fn count(&self) -> Result<usize> {
         let count: i64 = self
             .conn
@@ -914,49 +1454,84 @@ impl Database {
         Ok(entries)
     }
 
-    /// Search entries using FTS5 (Full-Text Search)
-    /// Returns entries matching the query, ordered by relevance (implicitly, but we sort by last_used)
+    /// Search entries using FTS5 (Full-Text Search). `query` is in the
+    /// search language of [`Query::parse`]; plain words are prefix-matched.
+    /// Returns matching entries ordered by last_used.
     pub fn search_entries(&self, query: &str, limit: usize) -> Result<Vec<Entry>> {
-        // FTS syntax: wrap in quotes to match phrase or sanitize
-        // For simple partial matching in FTS, typically just the words or wildcards.
-        // We'll use prefix matching for the query terms.
+        self.query(&Query::parse_or_text(query), limit)
+    }
 
-        let should_use_wildcard = !query.contains('"') && !query.contains('*');
-        let fts_query = if should_use_wildcard {
-            format!("\"{}\"*", query.replace("\"", "\"\""))
-        } else {
-            query.to_string()
-        };
+    /// Like `search_entries`, but also returns where each entry matched so
+    /// callers can show the relevant part of long content instead of its
+    /// head. Only the full-text part of `query` produces hits; a query
+    /// without one returns nothing.
+    pub fn search_hits(&self, query: &Query, limit: usize) -> Result<Vec<(Entry, SearchHit)>> {
+        if query.text.as_deref().is_none_or(str::is_empty) {
+            return Ok(Vec::new());
+        }
+        let mut hits = self.fts_hits("entries_fts", query, limit)?;
+        if self.has_trigram_index() {
+            // Substring-only matches, located by the trigram table's own
+            // snippet/highlight
+            let mut seen: HashSet<String> = hits.iter().map(|(e, _)| e.id.clone()).collect();
+            for (entry, hit) in self.fts_hits(TRIGRAM_TABLE, query, limit)? {
+                if seen.insert(entry.id.clone()) {
+                    hits.push((entry, hit));
+                }
+            }
+            hits.sort_by_key(|(entry, _)| std::cmp::Reverse(entry.last_used));
+            hits.truncate(limit);
+        }
+        Ok(hits)
+    }
 
-        // Note: ORDER BY rank is the default relevance for FTS, but usually users want recent stuff too.
-        // However, usually detailed search means relevance matters.
-        // Current requirement says "ordered by last_used" in previous implementation.
-        // But FTS is most useful when ranked by match.
-        // LIMIT applies.
-        // Let's stick to last_used DESC as the primary sort for consistency unless the user requested relevance sort.
-        // Actually, for search, let's just get matching entries and sort by last_used.
+    /// Entries whose text matches `query.text` in one FTS5 table with
+    /// `(id, content, notes)` columns and that pass the query's other
+    /// filters, with snippet and highlight of the content column
+    fn fts_hits(
+        &self,
+        table: &str,
+        query: &Query,
+        limit: usize,
+    ) -> Result<Vec<(Entry, SearchHit)>> {
+        let mut values = vec![
+            Value::Text(self.fts_match_expr(query.text.as_deref().unwrap_or_default())),
+            Value::Integer(limit as i64),
+            Value::Text(HIT_OPEN.to_string()),
+            Value::Text(HIT_CLOSE.to_string()),
+            Value::Integer(SNIPPET_TOKENS),
+        ];
+        let filters: String = self
+            .query_clauses(&query.filters(), &mut values)?
+            .iter()
+            .map(|clause| format!(" AND {}", clause))
+            .collect();
 
-        // We join with FTS table to get the matching IDs
-        let mut stmt = self.conn.prepare(
-            "SELECT e.id, e.entry_type, e.content, e.hash, e.byte_size, e.created_at, e.last_used, e.pinned, e.notes, e.collection_id, e.image_extension
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT e.id, e.entry_type, e.content, e.hash, e.byte_size, e.created_at, e.last_used, e.pinned, e.notes, e.collection_id, e.image_extension,
+                    snippet({table}, 1, ?3, ?4, '…', ?5),
+                    highlight({table}, 1, ?3, ?4)
              FROM entries e
-             JOIN entries_fts f ON e.id = f.id
-             WHERE entries_fts MATCH ?1
+             JOIN {table} f ON e.id = f.id
+             WHERE {table} MATCH ?1{filters}
              ORDER BY e.last_used DESC
-             LIMIT ?2",
-        )?;
-
-        let entries = stmt
-            .query_map(params![fts_query, limit as i64], |row| {
-                Self::row_to_entry(row)
+             LIMIT ?2"
+        ))?;
+
+        let hits = stmt
+            .query_map(params_from_iter(values), |row| {
+                let entry = Self::row_to_entry(row)?;
+                let snippet: String = row.get(11)?;
+                let highlighted: String = row.get(12)?;
+                Ok((entry, SearchHit::from_marked(&snippet, &highlighted)))
             })?
             .collect::<std::result::Result<Vec<_>, _>>()?;
 
-        Ok(entries)
+        Ok(hits)
     }
 
-    /// Search entries using FTS5 with additional filtering
-    /// Returns entries matching the query and filter, ordered by last_used
+    /// Search entries with additional tab filtering (see `count_filtered`
+    /// for the filter strings). Returns matching entries ordered by last_used.
     pub fn search_entries_filtered(
         &self,
         query: &str,
@@ -964,104 +1539,405 @@ impl Database {
         filter: &str,
         collection_id: Option<&str>,
     ) -> Result<Vec<Entry>> {
-        let should_use_wildcard = !query.contains('"') && !query.contains('*');
-        let fts_query = if should_use_wildcard {
-            format!("\"{}\"*", query.replace("\"", "\"\""))
-        } else {
-            query.to_string()
-        };
-        let limit_i64 = limit as i64;
-
-        // Base query joins with FTS
-        let base_sql = "SELECT e.id, e.entry_type, e.content, e.hash, e.byte_size, e.created_at, e.last_used, e.pinned, e.notes, e.collection_id, e.image_extension
-                        FROM entries e
-                        JOIN entries_fts f ON e.id = f.id
-                        WHERE entries_fts MATCH ?2";
-
-        // Append filter conditions
+        let mut query = Query::parse_or_text(query);
         match filter {
-            "text" | "image" => {
-                let sql = format!(
-                    "{} AND e.entry_type = ?1 ORDER BY e.last_used DESC LIMIT ?3",
-                    base_sql
-                );
-                let mut stmt = self.conn.prepare(&sql)?;
-                let entries = stmt
-                    .query_map(params![filter, fts_query, limit_i64], Self::row_to_entry)?
-                    .collect::<std::result::Result<Vec<_>, _>>()?;
-                return Ok(entries);
-            }
+            "text" => query.entry_type = Some(EntryType::Text),
+            "image" => query.entry_type = Some(EntryType::Image),
+            "favorite" => query.favorites_only = true,
             "today" => {
-                let today = (Utc::now() - Duration::hours(24)).to_rfc3339();
-                let sql = format!(
-                    "{} AND e.created_at > ?1 ORDER BY e.last_used DESC LIMIT ?3",
-                    base_sql
-                );
-                let mut stmt = self.conn.prepare(&sql)?;
-                let entries = stmt
-                    .query_map(params![today, fts_query, limit_i64], Self::row_to_entry)?
-                    .collect::<std::result::Result<Vec<_>, _>>()?;
-                return Ok(entries);
+                let today = Utc::now() - Duration::hours(24);
+                query.after = Some(query.after.map_or(today, |after| after.max(today)));
             }
             "collection" if collection_id.is_some() => {
-                let cid = collection_id.unwrap();
-                let sql = format!(
-                    "{} AND e.collection_id = ?1 ORDER BY e.last_used DESC LIMIT ?3",
-                    base_sql
-                );
-                let mut stmt = self.conn.prepare(&sql)?;
-                let entries = stmt
-                    .query_map(params![cid, fts_query, limit_i64], Self::row_to_entry)?
-                    .collect::<std::result::Result<Vec<_>, _>>()?;
-                return Ok(entries);
+                query.collection_id = collection_id.map(str::to_string);
             }
-            "favorite" => {
-                // Fixed param index: pinned=1 is simpler to inline or use param.
-                // Using ?1 for consistency in param ordering logic is tricky if no other param.
-                // Here we don't have a ?1 so we need to be careful with indexing.
-                // The base query uses ?2 for MATCH.
-                // So we can put pinned=1 in SQL.
-                let _sql = format!(
-                    "{} AND e.pinned = 1 ORDER BY e.last_used DESC LIMIT ?3",
-                    base_sql
-                );
-                // We only need fts_query and limit.
-                // But wait, our base query uses ?2. So we need ?1 to be something or renumber?
-                // Actually base_sql uses ?2. So we can pass a dummy as ?1 or just fix the indices.
-                // Let's rewrite simple query for this case.
-                let mut stmt = self.conn.prepare(
-                    "SELECT e.id, e.entry_type, e.content, e.hash, e.byte_size, e.created_at, e.last_used, e.pinned, e.notes, e.collection_id, e.image_extension
-                     FROM entries e
-                     JOIN entries_fts f ON e.id = f.id
-                     WHERE entries_fts MATCH ?1 AND e.pinned = 1
-                     ORDER BY e.last_used DESC
-                     LIMIT ?2"
-                )?;
-                let entries = stmt
-                    .query_map(params![fts_query, limit_i64], Self::row_to_entry)?
-                    .collect::<std::result::Result<Vec<_>, _>>()?;
-                return Ok(entries);
-            }
-            _ => {} // Fall through
+            _ => {}
         }
+        self.query(&query, limit)
+    }
 
-        // Default query (all)
-        // Redefine base sql to use ?1 for match and ?2 for limit
-        let mut stmt = self.conn.prepare(
+    /// Run a structured [`Query`], newest (by last_used) first.
+    ///
+    /// An invalid `regex` is reported up front as an error rather than
+    /// surfacing as an opaque SQLite user-function failure.
+    pub fn query(&self, query: &Query, limit: usize) -> Result<Vec<Entry>> {
+        self.query_page(query, 0, limit)
+    }
+
+    /// One page of a structured [`Query`], newest (by last_used) first
+    pub fn query_page(&self, query: &Query, offset: usize, limit: usize) -> Result<Vec<Entry>> {
+        self.query_page_sorted(query, offset, limit, SortOrder::Recent)
+    }
+
+    /// [`Database::query_page`] in the given order
+    pub fn query_page_sorted(
+        &self,
+        query: &Query,
+        offset: usize,
+        limit: usize,
+        sort: SortOrder,
+    ) -> Result<Vec<Entry>> {
+        let (where_clause, mut values) = self.query_where(query)?;
+        values.push(Value::Integer(limit as i64));
+        values.push(Value::Integer(offset as i64));
+        let sql = format!(
             "SELECT e.id, e.entry_type, e.content, e.hash, e.byte_size, e.created_at, e.last_used, e.pinned, e.notes, e.collection_id, e.image_extension
-             FROM entries e
-             JOIN entries_fts f ON e.id = f.id
-             WHERE entries_fts MATCH ?1
-             ORDER BY e.last_used DESC
-             LIMIT ?2"
-        )?;
+             FROM entries e{}
+             ORDER BY {} LIMIT ?{} OFFSET ?{}",
+            where_clause,
+            order_by(sort),
+            values.len() - 1,
+            values.len()
+        );
 
+        let mut stmt = self.conn.prepare(&sql)?;
         let entries = stmt
-            .query_map(params![fts_query, limit_i64], Self::row_to_entry)?
+            .query_map(params_from_iter(values), Self::row_to_entry)?
             .collect::<std::result::Result<Vec<_>, _>>()?;
         Ok(entries)
     }
 
+    /// Number of entries a structured [`Query`] matches
+    pub fn count_query(&self, query: &Query) -> Result<usize> {
+        let (where_clause, values) = self.query_where(query)?;
+        let sql = format!("SELECT COUNT(*) FROM entries e{}", where_clause);
+        let count: i64 = self
+            .conn
+            .query_row(&sql, params_from_iter(values), |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    /// WHERE clause (with leading space, or empty) and bound values for a
+    /// [`Query`] over `entries e`
+    fn query_where(&self, query: &Query) -> Result<(String, Vec<Value>)> {
+        let mut values = Vec::new();
+        let clauses = self.query_clauses(query, &mut values)?;
+        if clauses.is_empty() {
+            Ok((String::new(), values))
+        } else {
+            Ok((format!(" WHERE {}", clauses.join(" AND ")), values))
+        }
+    }
+
+    /// SQL conditions over `entries e` for a [`Query`]. Bound values are
+    /// appended to `values`, whose existing entries keep their `?N` slots.
+    fn query_clauses(&self, query: &Query, values: &mut Vec<Value>) -> Result<Vec<String>> {
+        let mut clauses: Vec<String> = Vec::new();
+
+        if let Some(text) = query.text.as_deref().filter(|t| !t.is_empty()) {
+            values.push(Value::Text(self.fts_match_expr(text)));
+            clauses.push(self.text_match_sql(values.len()));
+        }
+        for term in query.exclude_text.iter().filter(|t| !t.is_empty()) {
+            values.push(Value::Text(self.fts_match_expr(term)));
+            clauses.push(format!("NOT {}", self.text_match_sql(values.len())));
+        }
+        if let Some(pattern) = query.regex.as_deref() {
+            Regex::new(pattern).map_err(|e| DitoxError::Other(format!("Invalid regex: {}", e)))?;
+            values.push(Value::Text(pattern.to_string()));
+            clauses.push(format!(
+                "e.entry_type = 'text' AND e.content REGEXP ?{}",
+                values.len()
+            ));
+        }
+        if let Some(entry_type) = query.entry_type {
+            values.push(Value::Text(entry_type.as_str().to_string()));
+            clauses.push(format!("e.entry_type = ?{}", values.len()));
+        }
+        if query.favorites_only {
+            clauses.push("e.pinned = 1".to_string());
+        }
+        if query.exclude_favorites {
+            clauses.push("e.pinned = 0".to_string());
+        }
+        for (wanted, op) in [
+            (query.primary_only, "IN"),
+            (query.exclude_primary, "NOT IN"),
+        ] {
+            if wanted {
+                clauses.push(format!(
+                    "e.id {op} (SELECT entry_id FROM entry_meta
+                     WHERE key = '{SELECTION_META_KEY}' AND value = 'primary')"
+                ));
+            }
+        }
+        for (tags, op) in [(&query.tags, "IN"), (&query.exclude_tags, "NOT IN")] {
+            for tag in tags {
+                values.push(Value::Text(tag.clone()));
+                // `work` also matches `work/...`; '0' is the character after
+                // '/', so the range stays on idx_entry_tags_name
+                let n = values.len();
+                clauses.push(format!(
+                    "e.id {op} (SELECT entry_id FROM entry_tags
+                     WHERE name = ?{n} OR (name >= ?{n} || '/' AND name < ?{n} || '0'))"
+                ));
+            }
+        }
+        if let Some(cid) = &query.collection_id {
+            values.push(Value::Text(cid.clone()));
+            clauses.push(format!("e.collection_id = ?{}", values.len()));
+        }
+        if let Some(ago) = query.after_ago {
+            values.push(Value::Text(ago.resolve().to_rfc3339()));
+            clauses.push(format!("e.created_at > ?{}", values.len()));
+        }
+        if let Some(ago) = query.before_ago {
+            values.push(Value::Text(ago.resolve().to_rfc3339()));
+            clauses.push(format!("e.created_at < ?{}", values.len()));
+        }
+        if let Some(after) = query.after {
+            values.push(Value::Text(after.to_rfc3339()));
+            clauses.push(format!("e.created_at > ?{}", values.len()));
+        }
+        if let Some(before) = query.before {
+            values.push(Value::Text(before.to_rfc3339()));
+            clauses.push(format!("e.created_at < ?{}", values.len()));
+        }
+
+        Ok(clauses)
+    }
+
+    // ============= Saved Search Methods =============
+
+    /// Save `query` under `name`, replacing an existing saved search
+    pub fn save_search(&self, name: &str, query: &Query) -> Result<()> {
+        let json = serde_json::to_string(query)
+            .map_err(|e| DitoxError::Other(format!("JSON serialization error: {}", e)))?;
+        self.conn.execute(
+            "INSERT INTO saved_searches (name, query, created_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(name) DO UPDATE SET query = excluded.query",
+            params![name, json, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Look up a saved search by name
+    pub fn get_saved_search(&self, name: &str) -> Result<Option<Query>> {
+        let json: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT query FROM saved_searches WHERE name = ?1",
+                [name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        json.map(|json| Self::parse_saved_query(&json)).transpose()
+    }
+
+    /// All saved searches as (name, query), in the order they were created
+    pub fn get_saved_searches(&self) -> Result<Vec<(String, Query)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, query FROM saved_searches ORDER BY created_at, name")?;
+        let rows: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        rows.into_iter()
+            .map(|(name, json)| Ok((name, Self::parse_saved_query(&json)?)))
+            .collect()
+    }
+
+    /// Delete a saved search. Returns false if it didn't exist.
+    pub fn delete_saved_search(&self, name: &str) -> Result<bool> {
+        let removed = self
+            .conn
+            .execute("DELETE FROM saved_searches WHERE name = ?1", [name])?;
+        Ok(removed > 0)
+    }
+
+    fn parse_saved_query(json: &str) -> Result<Query> {
+        serde_json::from_str(json)
+            .map_err(|e| DitoxError::Other(format!("Invalid saved search: {}", e)))
+    }
+
+    /// SQL condition selecting entries whose text matches the FTS5
+    /// expression bound to parameter `?param`: word matches from
+    /// `entries_fts`, plus substring matches if the trigram index is built
+    fn text_match_sql(&self, param: usize) -> String {
+        if self.has_trigram_index() {
+            format!(
+                "e.id IN (SELECT id FROM entries_fts WHERE entries_fts MATCH ?{param}
+                          UNION SELECT id FROM {TRIGRAM_TABLE} WHERE {TRIGRAM_TABLE} MATCH ?{param})"
+            )
+        } else {
+            format!("e.id IN (SELECT id FROM entries_fts WHERE entries_fts MATCH ?{param})")
+        }
+    }
+
+    // ============= Trigram Index =============
+
+    /// True if the optional trigram index has been built
+    pub fn has_trigram_index(&self) -> bool {
+        self.conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                [TRIGRAM_TABLE],
+                |_| Ok(()),
+            )
+            .is_ok()
+    }
+
+    /// Build the trigram index so searches also find substrings inside
+    /// words (e.g. `b3f9` inside a hash). Kept in sync by triggers from then
+    /// on. Returns the number of entries indexed; no-op if already built.
+    pub fn enable_trigram_index(&mut self) -> Result<usize> {
+        if self.has_trigram_index() {
+            return Ok(0);
+        }
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
+        tx.execute_batch(&format!(
+            "
+            CREATE VIRTUAL TABLE {TRIGRAM_TABLE} USING fts5(id UNINDEXED, content, notes, tokenize = 'trigram');
+
+            CREATE TRIGGER IF NOT EXISTS entries_trigram_ai AFTER INSERT ON entries BEGIN
+                INSERT INTO {TRIGRAM_TABLE}(id, content, notes) VALUES (new.id, new.content, new.notes);
+            END;
+            CREATE TRIGGER IF NOT EXISTS entries_trigram_ad AFTER DELETE ON entries BEGIN
+                DELETE FROM {TRIGRAM_TABLE} WHERE id = old.id;
+            END;
+            CREATE TRIGGER IF NOT EXISTS entries_trigram_au AFTER UPDATE ON entries BEGIN
+                DELETE FROM {TRIGRAM_TABLE} WHERE id = old.id;
+                INSERT INTO {TRIGRAM_TABLE}(id, content, notes) VALUES (new.id, new.content, new.notes);
+            END;
+            "
+        ))?;
+        let indexed = tx.execute(
+            &format!(
+                "INSERT INTO {TRIGRAM_TABLE}(id, content, notes) SELECT id, content, notes FROM entries"
+            ),
+            [],
+        )?;
+        tx.commit()?;
+        Ok(indexed)
+    }
+
+    /// Drop the trigram index and its triggers. Returns false if it wasn't built.
+    pub fn disable_trigram_index(&mut self) -> Result<bool> {
+        if !self.has_trigram_index() {
+            return Ok(false);
+        }
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
+        tx.execute_batch(&format!(
+            "
+            DROP TRIGGER IF EXISTS entries_trigram_ai;
+            DROP TRIGGER IF EXISTS entries_trigram_ad;
+            DROP TRIGGER IF EXISTS entries_trigram_au;
+            DROP TABLE {TRIGRAM_TABLE};
+            "
+        ))?;
+        tx.commit()?;
+        Ok(true)
+    }
+
+    // ============= Maintenance =============
+
+    /// Check the database and, if it's sound, rebuild the full-text
+    /// indexes from `entries`, refresh the query planner statistics,
+    /// compact the file and fold the WAL back into it. A corrupt database
+    /// is left untouched.
+    pub fn maintain(&mut self) -> Result<MaintenanceReport> {
+        let size_before = self.file_size();
+        let problems = self.integrity_problems()?;
+        if !problems.is_empty() {
+            return Ok(MaintenanceReport {
+                problems,
+                indexed: 0,
+                size_before,
+                size_after: size_before,
+            });
+        }
+
+        let mut tables = vec!["entries_fts"];
+        if self.has_trigram_index() {
+            tables.push(TRIGRAM_TABLE);
+        }
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let mut indexed = 0;
+        for table in tables {
+            tx.execute(&format!("DELETE FROM {table}"), [])?;
+            indexed = tx.execute(
+                &format!("INSERT INTO {table}(id, content, notes) SELECT id, content, notes FROM entries"),
+                [],
+            )?;
+            tx.execute(
+                &format!("INSERT INTO {table}({table}) VALUES ('optimize')"),
+                [],
+            )?;
+        }
+        tx.commit()?;
+
+        self.conn.execute_batch("ANALYZE; VACUUM;")?;
+        self.conn
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+
+        Ok(MaintenanceReport {
+            problems,
+            indexed,
+            size_before,
+            size_after: self.file_size(),
+        })
+    }
+
+    /// Lines reported by `PRAGMA integrity_check`; empty if the database
+    /// is sound
+    pub fn integrity_problems(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let lines = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(lines.into_iter().filter(|line| line != "ok").collect())
+    }
+
+    /// Copy the database to a new file at `path` with SQLite's online
+    /// backup API, which gives a consistent snapshot even while the watcher
+    /// is writing
+    pub fn backup_to(&self, path: &Path) -> Result<()> {
+        self.conn.backup(MAIN_DB, path, None)?;
+        Ok(())
+    }
+
+    /// Replace the contents of this database with the one at `path`
+    pub fn restore_from(&mut self, path: &Path) -> Result<()> {
+        self.conn
+            .restore(MAIN_DB, path, None::<fn(rusqlite::backup::Progress)>)?;
+        Ok(())
+    }
+
+    /// Bytes on disk for the database file and its WAL
+    fn file_size(&self) -> u64 {
+        let Some(path) = self.conn.path().filter(|p| !p.is_empty()) else {
+            return 0;
+        };
+        let size = |p: &str| std::fs::metadata(p).map(|m| m.len()).unwrap_or(0);
+        size(path) + size(&format!("{}-wal", path))
+    }
+
+    /// Turn user input into an FTS5 MATCH expression: plain input becomes a
+    /// quoted prefix phrase, input that already uses FTS syntax (quotes or
+    /// `*`) is passed through. Syntax FTS5 rejects, like an unbalanced
+    /// quote, is searched for as a plain phrase instead of failing.
+    fn fts_match_expr(&self, query: &str) -> String {
+        let phrase = || format!("\"{}\"*", query.replace('"', "\"\""));
+        if !query.contains('"') && !query.contains('*') {
+            return phrase();
+        }
+        let valid = self.conn.query_row(
+            "SELECT 1 FROM entries_fts WHERE entries_fts MATCH ?1 LIMIT 1",
+            [query],
+            |_| Ok(()),
+        );
+        match valid {
+            Err(rusqlite::Error::SqliteFailure(..)) => phrase(),
+            _ => query.to_string(),
+        }
+    }
+
     /// Expects columns in order: id, entry_type, content, hash, byte_size, created_at, last_used, pinned, notes, collection_id
     fn row_to_entry(row: &rusqlite::Row) -> std::result::Result<Entry, rusqlite::Error> {
         let entry_type_str: String = row.get(1)?;
@@ -1101,6 +1977,296 @@ impl Database {
         Ok(rows > 0)
     }
 
+    /// Replace the content of a text entry, updating its hash and size.
+    /// Fails if another entry already holds the new content.
+    pub fn update_content(&self, id: &str, content: &str) -> Result<bool> {
+        let hash = Entry::compute_hash(content.as_bytes());
+        if let Some(other) = self.get_by_hash(&hash)? {
+            if other.id != id {
+                return Err(DitoxError::Other(
+                    "An identical clip is already in the history".to_string(),
+                ));
+            }
+        }
+        let rows = self.conn.execute(
+            "UPDATE entries SET content = ?1, hash = ?2, byte_size = ?3
+             WHERE id = ?4 AND entry_type = 'text'",
+            params![content, hash, content.len() as i64, id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    // ============= Alias Methods =============
+
+    /// Point alias `name` at an entry, replacing any previous target.
+    /// Purely numeric names are rejected since they would read as indexes.
+    pub fn set_alias(&self, name: &str, entry_id: &str) -> Result<()> {
+        if name.is_empty() || name.chars().all(|c| c.is_ascii_digit()) {
+            return Err(DitoxError::Other(format!(
+                "Invalid alias '{}': must not be empty or a number",
+                name
+            )));
+        }
+        self.conn.execute(
+            "INSERT INTO entry_aliases (name, entry_id) VALUES (?1, ?2)
+             ON CONFLICT(name) DO UPDATE SET entry_id = excluded.entry_id",
+            params![name, entry_id],
+        )?;
+        Ok(())
+    }
+
+    /// Entry ID an alias points at
+    pub fn get_alias(&self, name: &str) -> Result<Option<String>> {
+        let entry_id = self
+            .conn
+            .query_row(
+                "SELECT entry_id FROM entry_aliases WHERE name = ?1",
+                [name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(entry_id)
+    }
+
+    /// All aliases as (name, entry_id), sorted by name
+    pub fn get_aliases(&self) -> Result<Vec<(String, String)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, entry_id FROM entry_aliases ORDER BY name")?;
+        let aliases = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(aliases)
+    }
+
+    /// Remove an alias. Returns false if it didn't exist.
+    pub fn delete_alias(&self, name: &str) -> Result<bool> {
+        let removed = self
+            .conn
+            .execute("DELETE FROM entry_aliases WHERE name = ?1", [name])?;
+        Ok(removed > 0)
+    }
+
+    // ============= Tag Methods =============
+
+    /// Trimmed tag name, rejecting empty ones. `/` separates hierarchy
+    /// levels (`work/project-x`), so no level may be empty.
+    fn tag_name(name: &str) -> Result<&str> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(DitoxError::Other("Tag name must not be empty".to_string()));
+        }
+        if name.split('/').any(|level| level.trim().is_empty()) {
+            return Err(DitoxError::Other(format!(
+                "Invalid tag '{}': empty level between '/'",
+                name
+            )));
+        }
+        Ok(name)
+    }
+
+    /// Tag an entry. Returns false if it already had the tag.
+    pub fn add_tag(&self, entry_id: &str, name: &str) -> Result<bool> {
+        let added = self.conn.execute(
+            "INSERT OR IGNORE INTO entry_tags (entry_id, name) VALUES (?1, ?2)",
+            params![entry_id, Self::tag_name(name)?],
+        )?;
+        Ok(added > 0)
+    }
+
+    /// Untag an entry. Returns false if it didn't have the tag.
+    pub fn remove_tag(&self, entry_id: &str, name: &str) -> Result<bool> {
+        let removed = self.conn.execute(
+            "DELETE FROM entry_tags WHERE entry_id = ?1 AND name = ?2",
+            params![entry_id, name.trim()],
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Tag an entry with several tags at once: all names are validated
+    /// before any is added. Returns how many were new.
+    pub fn add_tags(&mut self, entry_id: &str, names: &[String]) -> Result<usize> {
+        let names = names
+            .iter()
+            .map(|name| Self::tag_name(name))
+            .collect::<Result<Vec<_>>>()?;
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let mut added = 0;
+        for name in names {
+            added += tx.execute(
+                "INSERT OR IGNORE INTO entry_tags (entry_id, name) VALUES (?1, ?2)",
+                params![entry_id, name],
+            )?;
+        }
+        tx.commit()?;
+        Ok(added)
+    }
+
+    /// Untag several tags at once. If the entry lacks any of them nothing
+    /// is removed.
+    pub fn remove_tags(&mut self, entry_id: &str, names: &[String]) -> Result<()> {
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
+        for name in names {
+            let removed = tx.execute(
+                "DELETE FROM entry_tags WHERE entry_id = ?1 AND name = ?2",
+                params![entry_id, name.trim()],
+            )?;
+            if removed == 0 {
+                // Dropping the transaction rolls back earlier removals
+                return Err(DitoxError::Other(format!(
+                    "Entry has no tag: {}",
+                    name.trim()
+                )));
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Tags of one entry, sorted by name
+    pub fn get_tags(&self, entry_id: &str) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name FROM entry_tags WHERE entry_id = ?1 ORDER BY name")?;
+        let tags = stmt
+            .query_map([entry_id], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(tags)
+    }
+
+    /// Every tag in use with its number of entries, sorted by name
+    pub fn all_tags(&self) -> Result<Vec<(String, usize)>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, COUNT(*) FROM entry_tags GROUP BY name ORDER BY name")?;
+        let tags = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(tags)
+    }
+
+    /// Rename a tag on every entry, moving its sub-tags along
+    /// (`work/x` becomes `new/x`). If `new` is already in use the two tags
+    /// are merged, so entries carrying both end up with `new` once.
+    /// Returns how many tag assignments were renamed.
+    pub fn rename_tag(&mut self, old: &str, new: &str) -> Result<usize> {
+        let old = old.trim();
+        let new = Self::tag_name(new)?;
+        if old == new {
+            return Ok(self
+                .all_tags()?
+                .into_iter()
+                .find(|(name, _)| name == old)
+                .map_or(0, |(_, count)| count));
+        }
+        if new.starts_with(&format!("{}/", old)) {
+            return Err(DitoxError::Other(format!(
+                "Cannot rename tag '{}' into its own sub-tag '{}'",
+                old, new
+            )));
+        }
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
+        tx.execute(
+            "INSERT OR IGNORE INTO entry_tags (entry_id, name)
+             SELECT entry_id, ?2 FROM entry_tags WHERE name = ?1",
+            params![old, new],
+        )?;
+        let renamed = tx.execute("DELETE FROM entry_tags WHERE name = ?1", [old])?;
+        // Sub-tags: substr keeps the `/...` suffix after the old prefix
+        let subtree = "name >= ?1 || '/' AND name < ?1 || '0'";
+        tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO entry_tags (entry_id, name)
+                 SELECT entry_id, ?2 || substr(name, length(?1) + 1)
+                 FROM entry_tags WHERE {subtree}"
+            ),
+            params![old, new],
+        )?;
+        let moved = tx.execute(&format!("DELETE FROM entry_tags WHERE {subtree}"), [old])?;
+        tx.commit()?;
+        Ok(renamed + moved)
+    }
+
+    /// Remove a tag from every entry. Returns how many entries lost it.
+    pub fn delete_tag(&self, name: &str) -> Result<usize> {
+        let removed = self
+            .conn
+            .execute("DELETE FROM entry_tags WHERE name = ?1", [name.trim()])?;
+        Ok(removed)
+    }
+
+    // ============= Metadata Methods =============
+
+    /// Set (insert or replace) a metadata value on an entry
+    pub fn set_meta(&self, entry_id: &str, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO entry_meta (entry_id, key, value) VALUES (?1, ?2, ?3)
+             ON CONFLICT(entry_id, key) DO UPDATE SET value = excluded.value",
+            params![entry_id, key, value],
+        )?;
+        Ok(())
+    }
+
+    /// Get a single metadata value
+    pub fn get_meta(&self, entry_id: &str, key: &str) -> Result<Option<String>> {
+        let value = self
+            .conn
+            .query_row(
+                "SELECT value FROM entry_meta WHERE entry_id = ?1 AND key = ?2",
+                params![entry_id, key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value)
+    }
+
+    /// All metadata for an entry, ordered by key
+    pub fn get_all_meta(&self, entry_id: &str) -> Result<BTreeMap<String, String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT key, value FROM entry_meta WHERE entry_id = ?1")?;
+        let meta = stmt
+            .query_map([entry_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<std::result::Result<BTreeMap<_, _>, _>>()?;
+        Ok(meta)
+    }
+
+    /// Remove a metadata key from an entry
+    pub fn delete_meta(&self, entry_id: &str, key: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "DELETE FROM entry_meta WHERE entry_id = ?1 AND key = ?2",
+            params![entry_id, key],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Set every key/value pair in `meta` on an entry in one transaction
+    /// (used when importing metadata from JSON)
+    pub fn import_meta(
+        &mut self,
+        entry_id: &str,
+        meta: &BTreeMap<String, String>,
+    ) -> Result<usize> {
+        let tx = self
+            .conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)?;
+        for (key, value) in meta {
+            tx.execute(
+                "INSERT INTO entry_meta (entry_id, key, value) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(entry_id, key) DO UPDATE SET value = excluded.value",
+                params![entry_id, key, value],
+            )?;
+        }
+        tx.commit()?;
+        Ok(meta.len())
+    }
+
     /// Get top entries by usage count (for quick snippets)
     pub fn get_top_by_usage(&self, limit: usize) -> Result<Vec<Entry>> {
         let mut stmt = self.conn.prepare(
@@ -1210,6 +2376,62 @@ impl Database {
             |row| row.get(0),
         )?;
 
+        // Clips captured per day over the last month, oldest first, with
+        // empty days filled in
+        let mut day_stmt = self.conn.prepare(
+            "SELECT substr(created_at, 1, 10), COUNT(*) FROM entries
+             WHERE created_at >= ?1 GROUP BY 1",
+        )?;
+        let first_day = (now - Duration::days(STATS_DAYS - 1)).date_naive();
+        let day_counts: HashMap<String, i64> = day_stmt
+            .query_map([first_day.to_string()], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<std::result::Result<_, _>>()?;
+        let clips_per_day = first_day
+            .iter_days()
+            .take(STATS_DAYS as usize)
+            .map(|day| {
+                let date = day.to_string();
+                let clips = day_counts.get(&date).copied().unwrap_or(0) as usize;
+                DayCount { date, clips }
+            })
+            .collect();
+
+        let mut tag_stmt = self.conn.prepare(
+            "SELECT name, COUNT(*) FROM entry_tags GROUP BY name
+             ORDER BY COUNT(*) DESC, name LIMIT 5",
+        )?;
+        let top_tags = tag_stmt
+            .query_map([], |row| {
+                Ok(TagCount {
+                    name: row.get(0)?,
+                    count: row.get::<_, i64>(1)? as usize,
+                })
+            })?
+            .collect::<std::result::Result<_, _>>()?;
+
+        let avg_text_length: f64 = self.conn.query_row(
+            "SELECT COALESCE(AVG(LENGTH(content)), 0) FROM entries WHERE entry_type = 'text'",
+            [],
+            |row| row.get(0),
+        )?;
+
+        // Pages of the full-text (and trigram) index tables; needs the
+        // dbstat table, which the bundled SQLite has
+        let fts_index_bytes: i64 = self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT COALESCE(SUM(pgsize), 0) FROM dbstat
+                     WHERE name LIKE 'entries_fts%' OR name LIKE '{}%'",
+                    TRIGRAM_TABLE
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(0);
+
         // Get file sizes
         let db_path = Self::get_db_path()?;
         let db_size_bytes = std::fs::metadata(&db_path).map(|m| m.len()).unwrap_or(0);
@@ -1239,6 +2461,10 @@ impl Database {
             copies_week: copies_week as usize,
             copies_month: copies_month as usize,
             total_usage: total_usage as u64,
+            clips_per_day,
+            top_tags,
+            avg_text_length,
+            fts_index_bytes: fts_index_bytes as u64,
         })
     }
 
@@ -1412,24 +2638,38 @@ impl Database {
         }
     }
 
-    /// Get a page of entries with filtering
+    /// Get a page of entries with filtering, most recently used first
     pub fn get_page_filtered(
         &self,
         offset: usize,
         limit: usize,
         filter: &str,
         collection_id: Option<&str>,
+    ) -> Result<Vec<Entry>> {
+        self.get_page_sorted(offset, limit, filter, collection_id, SortOrder::Recent)
+    }
+
+    /// Get a page of entries with filtering in the given order
+    pub fn get_page_sorted(
+        &self,
+        offset: usize,
+        limit: usize,
+        filter: &str,
+        collection_id: Option<&str>,
+        sort: SortOrder,
     ) -> Result<Vec<Entry>> {
         let (where_clause, params) = self.build_filter_clause(filter, collection_id);
+        let order = order_by(sort);
 
         match params {
             FilterParams::None => {
-                let mut stmt = self.conn.prepare(
+                let sql = format!(
                     "SELECT id, entry_type, content, hash, byte_size, created_at, last_used, pinned, notes, collection_id, image_extension
                      FROM entries
-                     ORDER BY last_used DESC
-                     LIMIT ?1 OFFSET ?2",
-                )?;
+                     ORDER BY {order}
+                     LIMIT ?1 OFFSET ?2"
+                );
+                let mut stmt = self.conn.prepare(&sql)?;
                 let entries = stmt
                     .query_map(params![limit as i64, offset as i64], |row| {
                         Self::row_to_entry(row)
@@ -1441,7 +2681,7 @@ impl Database {
                 let sql = format!(
                     "SELECT id, entry_type, content, hash, byte_size, created_at, last_used, pinned, notes, collection_id, image_extension
                      FROM entries{}
-                     ORDER BY last_used DESC
+                     ORDER BY {order}
                      LIMIT ?2 OFFSET ?3",
                     where_clause
                 );
@@ -1457,7 +2697,7 @@ impl Database {
                 let sql = format!(
                     "SELECT id, entry_type, content, hash, byte_size, created_at, last_used, pinned, notes, collection_id, image_extension
                      FROM entries{}
-                     ORDER BY last_used DESC
+                     ORDER BY {order}
                      LIMIT ?2 OFFSET ?3",
                     where_clause
                 );
@@ -1473,7 +2713,7 @@ impl Database {
                 let sql = format!(
                     "SELECT id, entry_type, content, hash, byte_size, created_at, last_used, pinned, notes, collection_id, image_extension
                      FROM entries{}
-                     ORDER BY last_used DESC
+                     ORDER BY {order}
                      LIMIT ?2 OFFSET ?3",
                     where_clause
                 );
@@ -1489,7 +2729,7 @@ impl Database {
                 let sql = format!(
                     "SELECT id, entry_type, content, hash, byte_size, created_at, last_used, pinned, notes, collection_id, image_extension
                      FROM entries{}
-                     ORDER BY last_used DESC
+                     ORDER BY {order}
                      LIMIT ?1 OFFSET ?2",
                     where_clause
                 );
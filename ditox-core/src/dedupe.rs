@@ -0,0 +1,106 @@
+//! Folding duplicate clips together
+//!
+//! Content hashes are unique, so byte-identical clips can't pile up. What
+//! does pile up are copies that differ only in line endings or surrounding
+//! whitespace, like the same command copied with and without its trailing
+//! newline. [`dedupe`] groups text clips by their content with CRLF turned
+//! into LF and surrounding whitespace trimmed, keeps the oldest clip of each
+//! group and folds the others into it:
+//!
+//! - it stays a favorite if any copy was one, keeps the latest use and adds
+//!   up the copy counts
+//! - it gains the others' tags, aliases, metadata keys it lacks, notes and
+//!   collection if it had none
+//!
+//! Images are matched by the SHA-256 of their bytes, which is already the
+//! unique content hash, so they never have duplicates to fold.
+
+use crate::db::Database;
+use crate::entry::{Entry, EntryType};
+use crate::error::Result;
+use std::collections::HashMap;
+
+/// Clips that hold the same text
+#[derive(Debug)]
+pub struct DuplicateGroup {
+    /// The oldest clip, which is kept
+    pub keep: Entry,
+    /// Newer copies folded into it
+    pub duplicates: Vec<Entry>,
+}
+
+/// Key that duplicate clips share
+fn normalize(content: &str) -> String {
+    content.replace("\r\n", "\n").trim().to_string()
+}
+
+/// Groups of text clips that are duplicates of each other, oldest first
+pub fn find_duplicates(db: &Database) -> Result<Vec<DuplicateGroup>> {
+    let mut by_content: HashMap<String, Vec<Entry>> = HashMap::new();
+    for entry in db.get_all(i64::MAX as usize)? {
+        if entry.entry_type == EntryType::Text {
+            by_content
+                .entry(normalize(&entry.content))
+                .or_default()
+                .push(entry);
+        }
+    }
+
+    let mut groups: Vec<DuplicateGroup> = by_content
+        .into_values()
+        .filter(|entries| entries.len() > 1)
+        .map(|mut entries| {
+            entries.sort_by_key(|e| e.created_at);
+            let keep = entries.remove(0);
+            DuplicateGroup {
+                keep,
+                duplicates: entries,
+            }
+        })
+        .collect();
+    groups.sort_by_key(|g| g.keep.created_at);
+    Ok(groups)
+}
+
+/// Fold every duplicate into the oldest clip of its group and delete it,
+/// in one transaction. Returns the groups that were folded.
+pub fn dedupe(db: &Database) -> Result<Vec<DuplicateGroup>> {
+    let groups = find_duplicates(db)?;
+    db.in_transaction(|db| {
+        for group in &groups {
+            fold_group(db, group)?;
+        }
+        Ok(())
+    })?;
+    Ok(groups)
+}
+
+fn fold_group(db: &Database, group: &DuplicateGroup) -> Result<()> {
+    let keep = &group.keep;
+    let mut usage = db.usage_count(&keep.id)?;
+    let mut collection = keep.collection_id.clone();
+    let aliases = db.get_aliases()?;
+    for dup in &group.duplicates {
+        db.merge_entry(&keep.id, dup)?;
+        usage += db.usage_count(&dup.id)?;
+        for tag in db.get_tags(&dup.id)? {
+            db.add_tag(&keep.id, &tag)?;
+        }
+        for (key, value) in db.get_all_meta(&dup.id)? {
+            if db.get_meta(&keep.id, &key)?.is_none() {
+                db.set_meta(&keep.id, &key, &value)?;
+            }
+        }
+        for (name, _) in aliases.iter().filter(|(_, id)| *id == dup.id) {
+            db.set_alias(name, &keep.id)?;
+        }
+        if collection.is_none() && dup.collection_id.is_some() {
+            collection = dup.collection_id.clone();
+            db.set_entry_collection(&keep.id, collection.as_deref())?;
+        }
+        // Text rows have no blob to prune
+        db.delete_dangling_row(&dup.id)?;
+    }
+    db.set_usage_count(&keep.id, usage)?;
+    Ok(())
+}
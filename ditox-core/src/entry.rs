@@ -122,6 +122,12 @@ impl Entry {
         crate::db::Database::image_path(&self.hash, ext).ok()
     }
 
+    /// Width and height of an image entry, read from the blob's header
+    /// without decoding it. `None` for text or a missing or unreadable blob.
+    pub fn image_dimensions(&self) -> Option<(u32, u32)> {
+        image::image_dimensions(self.image_path()?).ok()
+    }
+
     pub fn compute_hash(content: &[u8]) -> String {
         let mut hasher = Sha256::new();
         hasher.update(content);
@@ -209,7 +215,7 @@ impl Entry {
 }
 
 /// Strips ANSI escape sequences from a string.
-fn strip_ansi_escapes(s: &str) -> String {
+pub fn strip_ansi_escapes(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
     let mut chars = s.chars().peekable();
 
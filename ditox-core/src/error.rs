@@ -14,11 +14,34 @@ pub enum DitoxError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
-    #[error("Entry not found: {0}")]
+    /// A missing entry, tag, collection or file; the message says which
+    #[error("{0}")]
     NotFound(String),
 
+    /// Another process holds the store, e.g. a second `ditox watch`
+    #[error("{0}")]
+    Locked(String),
+
+    /// The database schema doesn't match what this build expects
+    #[error("{0}")]
+    MigrationNeeded(String),
+
     #[error("{0}")]
     Other(String),
 }
 
+impl DitoxError {
+    /// True for [`DitoxError::Locked`] and SQLite busy/locked errors
+    pub fn is_locked(&self) -> bool {
+        match self {
+            DitoxError::Locked(_) => true,
+            DitoxError::Database(rusqlite::Error::SqliteFailure(e, _)) => matches!(
+                e.code,
+                rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked
+            ),
+            _ => false,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, DitoxError>;
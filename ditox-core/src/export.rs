@@ -0,0 +1,310 @@
+//! Image export and import
+//!
+//! An export mirrors the content-addressed image store: blobs live under
+//! `images/{hash[..2]}/{hash}.{ext}` next to a `manifest.json` mapping
+//! entry IDs to files and recording each file's SHA-256, so a copy can be
+//! verified later with [`verify_export`].
+//!
+//! Because the layout and names match the store, blobs are hard-linked in
+//! both directions when source and destination share a filesystem; across
+//! filesystems they are copied. Every blob is hashed on the way out and on
+//! the way back in, so a bad file can't enter either side under the wrong
+//! name. A bounded pool of worker threads does the export.
+
+use crate::db::{is_blob_hash, Database};
+use crate::error::{DitoxError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// File name of the manifest inside an export directory
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Upper bound for the default worker count; beyond this the disk is the
+/// bottleneck, not hashing
+const MAX_DEFAULT_JOBS: usize = 8;
+
+/// One exported file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// File name relative to the export directory
+    pub file: String,
+    /// SHA-256 of the file contents (hex)
+    pub sha256: String,
+    /// File size in bytes
+    pub bytes: u64,
+    /// Entries whose image is this file
+    pub entries: Vec<String>,
+}
+
+/// Contents of `manifest.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub created_at: DateTime<Utc>,
+    pub files: Vec<ManifestFile>,
+}
+
+/// Outcome of [`export_images`]
+#[derive(Debug, Default)]
+pub struct ExportReport {
+    /// Files added to the export directory
+    pub written: usize,
+    /// Files already present in the export directory, left alone
+    pub unchanged: usize,
+    /// Image entries whose blob is missing from the image store
+    pub missing: Vec<String>,
+}
+
+/// Outcome of [`import_images`]
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Blobs added to the image store
+    pub imported: usize,
+    /// Blobs the store already had
+    pub present: usize,
+    /// Manifest files that are missing, corrupt or malformed
+    pub bad: Vec<String>,
+}
+
+/// Worker count used when the caller doesn't pick one
+pub fn default_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(MAX_DEFAULT_JOBS)
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// A distinct blob to export and the entries referring to it
+struct Job {
+    source: PathBuf,
+    hash: String,
+    extension: String,
+    entries: Vec<String>,
+}
+
+enum Outcome {
+    Written(ManifestFile),
+    Unchanged(ManifestFile),
+    Missing(Vec<String>),
+}
+
+/// Path of a blob relative to an export directory, mirroring the store.
+/// `hash` is always a computed SHA-256 hex digest.
+fn export_file_name(hash: &str, extension: &str) -> String {
+    format!("images/{}/{}.{}", &hash[..2], hash, extension)
+}
+
+/// A manifest file name that stays inside the export directory: relative
+/// and without `..` components
+fn is_contained(file: &str) -> bool {
+    let path = Path::new(file);
+    path.components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)))
+        && path.components().next().is_some()
+}
+
+/// Hard-link `source` to `dest`, falling back to a copy (e.g. across
+/// filesystems). `dest` must not exist yet.
+fn link_or_copy(source: &Path, dest: &Path) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::hard_link(source, dest).is_err() {
+        fs::copy(source, dest)?;
+    }
+    Ok(())
+}
+
+fn export_one(job: &Job, dir: &Path) -> Result<Outcome> {
+    let bytes = match fs::read(&job.source) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(Outcome::Missing(job.entries.clone()));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    // Name by the checksum of what was actually read, so the manifest is
+    // truthful even if a store blob went bad, and identical images stored
+    // under different hashes still land in one file
+    let sha256 = sha256_hex(&bytes);
+    if sha256 != job.hash {
+        tracing::warn!(
+            "image blob {} doesn't match its hash; run `ditox repair --fix-hashes`",
+            job.source.display()
+        );
+    }
+    let file = export_file_name(&sha256, &job.extension);
+    let record = ManifestFile {
+        file: file.clone(),
+        sha256,
+        bytes: bytes.len() as u64,
+        entries: job.entries.clone(),
+    };
+
+    // Content-addressed: an existing file with this name has this content
+    let dest = dir.join(&file);
+    if dest.exists() {
+        return Ok(Outcome::Unchanged(record));
+    }
+    link_or_copy(&job.source, &dest)?;
+    Ok(Outcome::Written(record))
+}
+
+/// Export every image entry into `dir` (created if needed) using up to
+/// `jobs` worker threads, then write the manifest. Files already in `dir`
+/// are not rewritten, so re-running an export only adds what changed.
+pub fn export_images(db: &Database, dir: &Path, jobs: usize) -> Result<ExportReport> {
+    fs::create_dir_all(dir)?;
+
+    let mut by_source: BTreeMap<PathBuf, Job> = BTreeMap::new();
+    for (id, hash, extension, source) in db.image_rows_with_paths()? {
+        by_source
+            .entry(source.clone())
+            .or_insert_with(|| Job {
+                source,
+                hash,
+                extension,
+                entries: Vec::new(),
+            })
+            .entries
+            .push(id);
+    }
+    let work: Vec<Job> = by_source.into_values().collect();
+
+    let next = AtomicUsize::new(0);
+    let outcomes: Mutex<Vec<Outcome>> = Mutex::new(Vec::with_capacity(work.len()));
+    let first_error: Mutex<Option<DitoxError>> = Mutex::new(None);
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, work.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = work.get(i) else { break };
+                match export_one(job, dir) {
+                    Ok(outcome) => outcomes.lock().unwrap().push(outcome),
+                    Err(e) => {
+                        first_error.lock().unwrap().get_or_insert(e);
+                        // Stop handing out work; in-flight jobs finish
+                        next.store(work.len(), Ordering::Relaxed);
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    if let Some(e) = first_error.into_inner().unwrap() {
+        return Err(e);
+    }
+
+    let mut report = ExportReport::default();
+    let mut files: BTreeMap<String, ManifestFile> = BTreeMap::new();
+    for outcome in outcomes.into_inner().unwrap() {
+        let record = match outcome {
+            Outcome::Written(record) => {
+                report.written += 1;
+                record
+            }
+            Outcome::Unchanged(record) => {
+                report.unchanged += 1;
+                record
+            }
+            Outcome::Missing(entries) => {
+                report.missing.extend(entries);
+                continue;
+            }
+        };
+        match files.get_mut(&record.file) {
+            // Same content from two blobs: one file, both entries
+            Some(existing) => existing.entries.extend(record.entries),
+            None => {
+                files.insert(record.file.clone(), record);
+            }
+        }
+    }
+    report.missing.sort();
+
+    let mut files: Vec<ManifestFile> = files.into_values().collect();
+    for record in &mut files {
+        record.entries.sort();
+    }
+    let manifest = Manifest {
+        created_at: Utc::now(),
+        files,
+    };
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| DitoxError::Other(format!("JSON serialization error: {}", e)))?;
+    fs::write(dir.join(MANIFEST_FILE), json)?;
+
+    Ok(report)
+}
+
+fn read_manifest(dir: &Path) -> Result<Manifest> {
+    let json = fs::read_to_string(dir.join(MANIFEST_FILE))?;
+    serde_json::from_str(&json).map_err(|e| DitoxError::Other(format!("Invalid manifest: {}", e)))
+}
+
+/// Check an export directory against its manifest. Returns the files that
+/// are missing or whose checksum doesn't match.
+pub fn verify_export(dir: &Path) -> Result<Vec<String>> {
+    let manifest = read_manifest(dir)?;
+
+    let mut bad: Vec<String> = manifest
+        .files
+        .into_iter()
+        .filter(|record| {
+            fs::read(dir.join(&record.file))
+                .map_or(true, |bytes| sha256_hex(&bytes) != record.sha256)
+        })
+        .map(|record| record.file)
+        .collect();
+    bad.sort();
+    Ok(bad)
+}
+
+/// Put the blobs of an export back into the image store, hard-linking when
+/// possible. Blobs the store already has are skipped. The manifest isn't
+/// trusted: entries with a malformed hash or a file outside `dir`, and
+/// files whose contents don't hash to the recorded SHA-256, are reported
+/// as bad and never reach the store.
+pub fn import_images(dir: &Path) -> Result<ImportReport> {
+    let manifest = read_manifest(dir)?;
+    let mut report = ImportReport::default();
+
+    for record in manifest.files {
+        let extension = Path::new(&record.file)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("png");
+        if !is_blob_hash(&record.sha256) || !is_contained(&record.file) {
+            report.bad.push(record.file);
+            continue;
+        }
+        let Ok(dest) = Database::image_path(&record.sha256, extension) else {
+            report.bad.push(record.file);
+            continue;
+        };
+        if dest.exists() {
+            report.present += 1;
+            continue;
+        }
+        let source = dir.join(&record.file);
+        if !fs::read(&source).is_ok_and(|bytes| sha256_hex(&bytes) == record.sha256) {
+            report.bad.push(record.file);
+            continue;
+        }
+        link_or_copy(&source, &dest)?;
+        report.imported += 1;
+    }
+    report.bad.sort();
+    Ok(report)
+}
@@ -0,0 +1,112 @@
+//! User commands run on clip events
+//!
+//! `[hooks]` in the config names a shell command per event. It runs with
+//! the clip's text on stdin (empty for images) and these variables set:
+//!
+//! - `DITOX_EVENT`: `add`, `copy` or `delete`
+//! - `DITOX_ID`, `DITOX_KIND` (`text` or `image`), `DITOX_HASH`
+//! - `DITOX_IMAGE_PATH`: the image file, for images (not on delete, when it
+//!   may already be gone)
+//!
+//! The watcher and the TUI run hooks in the background so a slow command
+//! can't hold up capture or input; one-shot commands wait for them.
+//! Failures are logged, never returned to the action that triggered them.
+
+use crate::config::HooksConfig;
+use crate::entry::{Entry, EntryType};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    /// A clip was captured
+    Add,
+    /// A clip was put back on the clipboard
+    Copy,
+    /// A clip was deleted
+    Delete,
+}
+
+impl HookEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HookEvent::Add => "add",
+            HookEvent::Copy => "copy",
+            HookEvent::Delete => "delete",
+        }
+    }
+}
+
+impl HooksConfig {
+    /// Command configured for `event`
+    pub fn command(&self, event: HookEvent) -> Option<&str> {
+        let command = match event {
+            HookEvent::Add => &self.on_add,
+            HookEvent::Copy => &self.on_copy,
+            HookEvent::Delete => &self.on_delete,
+        };
+        command.as_deref().filter(|c| !c.trim().is_empty())
+    }
+}
+
+/// Run the hook for `event` on `entry`, if one is configured, and wait for
+/// it to finish
+pub fn run(hooks: &HooksConfig, event: HookEvent, entry: &Entry) {
+    if let Some(command) = hooks.command(event) {
+        execute(command, event, entry);
+    }
+}
+
+/// Like [`run`], but on a background thread
+pub fn spawn(hooks: &HooksConfig, event: HookEvent, entry: &Entry) {
+    if let Some(command) = hooks.command(event) {
+        let command = command.to_string();
+        let entry = entry.clone();
+        std::thread::spawn(move || execute(&command, event, &entry));
+    }
+}
+
+fn execute(command: &str, event: HookEvent, entry: &Entry) {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell
+        .arg(command)
+        .env("DITOX_EVENT", event.as_str())
+        .env("DITOX_ID", &entry.id)
+        .env("DITOX_KIND", entry.entry_type.as_str())
+        .env("DITOX_HASH", &entry.hash)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null());
+    if event != HookEvent::Delete {
+        if let Some(path) = entry.image_path() {
+            shell.env("DITOX_IMAGE_PATH", path);
+        }
+    }
+
+    let mut child = match shell.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            warn!("Failed to run on_{} hook: {}", event.as_str(), e);
+            return;
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        if entry.entry_type == EntryType::Text {
+            // A hook that doesn't read its input closes the pipe early
+            let _ = stdin.write_all(entry.content.as_bytes());
+        }
+    }
+    match child.wait() {
+        Ok(status) if status.success() => debug!("on_{} hook done", event.as_str()),
+        Ok(status) => warn!("on_{} hook exited with {}", event.as_str(), status),
+        Err(e) => warn!("Failed to wait for on_{} hook: {}", event.as_str(), e),
+    }
+}
@@ -0,0 +1,733 @@
+//! User-facing message catalog
+//!
+//! Messages are looked up by key. English is built in; a translation is a
+//! flat TOML file `locales/{lang}.toml` in the config directory mapping keys
+//! to text, e.g. for `de.toml`:
+//!
+//! ```toml
+//! "toast.entry_deleted" = "Eintrag gelöscht"
+//! "toast.deleted_entries" = "{count} Einträge gelöscht"
+//! ```
+//!
+//! Keys missing from a translation fall back to English. `{name}`
+//! placeholders are filled in by [`tf`].
+//!
+//! The language comes from `ui.locale` in the config, else from
+//! `LC_ALL` / `LC_MESSAGES` / `LANG`.
+
+use directories::ProjectDirs;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// Built-in English messages
+const ENGLISH: &[(&str, &str)] = &[
+    // TUI toasts
+    ("toast.search_mode", "Search mode: {mode}"),
+    ("toast.regex_search_mode", "Regex search mode"),
+    ("toast.copied", "Copied: {preview}"),
+    ("toast.copied_image", "Copied image: {preview}"),
+    ("toast.entry_deleted", "Entry deleted (u to undo)"),
+    ("toast.favorite_toggled", "Favorite toggled"),
+    ("toast.all_cleared", "All entries cleared"),
+    (
+        "toast.multi_select_on",
+        "Multi-select ON (Space to select, v to toggle all)",
+    ),
+    ("toast.multi_select_off", "Multi-select OFF"),
+    ("toast.selected_all", "Selected all {count} entries"),
+    ("toast.deselected_all", "Deselected all"),
+    ("toast.deleted_entries", "Deleted {count} entries (u to undo)"),
+    ("toast.restored", "Restored {count} entries"),
+    ("toast.nothing_to_undo", "Nothing to undo"),
+    ("toast.no_text_to_copy", "No text entries to copy"),
+    ("toast.copied_texts", "Copied {count} text entries"),
+    (
+        "toast.merged_texts",
+        "Merged {count} text entries into a new clip",
+    ),
+    ("toast.note_saved", "Note saved"),
+    ("toast.edit_cancelled", "Edit cancelled"),
+    ("toast.clip_updated", "Clip updated"),
+    ("toast.clip_unchanged", "Clip unchanged"),
+    ("toast.clip_saved_as_new", "Saved as a new clip"),
+    (
+        "toast.clip_exists",
+        "An identical clip is already in the history (edit with E to keep both)",
+    ),
+    (
+        "toast.clip_exists_moved",
+        "An identical clip is already in the history; moved it to the top",
+    ),
+    ("toast.edit_text_only", "Only text clips can be edited"),
+    (
+        "toast.editor_failed",
+        "Editor failed ({error}); clip unchanged",
+    ),
+    ("toast.preview_mode", "Preview mode: {mode}"),
+    ("toast.invalid_slot", "Invalid slot number"),
+    ("toast.slot_empty", "Slot {slot} is empty"),
+    ("toast.slot_copied", "Slot {slot}: {preview}"),
+    ("toast.slot_copied_image", "Slot {slot} (image): {preview}"),
+    ("toast.slot_not_found", "Slot {slot} entry not found"),
+    ("toast.snippets_shown", "Snippets bar shown"),
+    ("toast.snippets_hidden", "Snippets bar hidden"),
+    ("toast.no_saved_view", "No saved view {n}"),
+    ("toast.tab", "Tab: {tab}"),
+    ("toast.theme_reloaded", "Theme reloaded"),
+    ("toast.theme_reload_failed", "Theme not reloaded: {error}"),
+    ("inspect.cell", "Cell {x},{y}"),
+    ("inspect.builtin", "built-in color {color}, not set by the theme"),
+    ("inspect.default_color", "terminal default"),
+    ("toast.sort", "Sort: {sort}"),
+    ("sort.recent", "recently used"),
+    ("sort.created", "recently captured"),
+    ("sort.most_used", "most used"),
+    ("sort.largest", "largest"),
+    ("group.today", "Today"),
+    ("group.yesterday", "Yesterday"),
+    ("group.last_week", "Last week"),
+    ("group.older", "Older"),
+    ("toast.tabs_shown", "Tabs bar shown"),
+    ("toast.tabs_hidden", "Tabs bar hidden"),
+    ("toast.line_numbers_on", "Line numbers ON"),
+    ("toast.line_numbers_off", "Line numbers OFF"),
+    ("toast.cancelled", "Cancelled"),
+    ("toast.press_again", "Press again to confirm"),
+    ("toast.type_phrase", "Type \"{phrase}\" to confirm"),
+    ("toast.refreshed", "Refreshed"),
+    // TUI confirmation dialog
+    (
+        "confirm.delete_selected",
+        "Delete {count} selected entries?",
+    ),
+    ("confirm.delete_entry", "Delete \"{preview}\"?"),
+    ("confirm.delete_selected_entry", "Delete selected entry?"),
+    (
+        "confirm.clear_all",
+        "Delete ALL {count} entries? This cannot be undone!",
+    ),
+    ("confirm.hint", "[y/Enter] Confirm  [n/Esc] Cancel"),
+    (
+        "confirm.typed_hint",
+        "Type \"{phrase}\" and press Enter  [Esc] Cancel",
+    ),
+    ("confirm.title", "Confirm"),
+    ("confirm.delete_entry_title", "Delete Entry?"),
+    ("confirm.delete_favorite", "Delete Favorite?"),
+    (
+        "confirm.favorite_warning",
+        "This entry is marked as a favorite.",
+    ),
+    // TUI search bar
+    ("search.label", "Search"),
+    ("search.one_match", "1 match"),
+    ("search.matches", "{count} matches"),
+    ("search.regex_indicator", "[regex]"),
+    ("search.title", "Ditox"),
+    ("search.title_search", "Ditox (search)"),
+    ("search.title_regex", "Ditox (regex search)"),
+    ("search.title_note", "Ditox (editing note)"),
+    ("search.title_tags", "Ditox (editing tags)"),
+    ("search.title_confirm", "Ditox (confirm)"),
+    ("search.tag_completions", "Tags"),
+    // TUI list, tabs and preview
+    ("list.history", "History  [Page {page} of {pages}]"),
+    ("list.search_results", "Search Results ({count})"),
+    ("list.new_clips", "↑ {count} new"),
+    ("tab.all", "All"),
+    ("tab.text", "Text"),
+    ("tab.images", "Images"),
+    ("tab.favorites", "Favorites"),
+    ("tab.today", "Today"),
+    ("preview.title", "Preview ({mode})"),
+    ("preview.no_entry", "No entry selected"),
+    ("preview.loading_image", "Loading image..."),
+    ("preview.image_unavailable", "[Image preview not available]"),
+    ("preview.unknown_dimensions", "unknown"),
+    (
+        "preview.image_info",
+        "Path: {path}\nSize: {size} │ Dimensions: {dimensions}\nCreated: {created}",
+    ),
+    ("preview.cols_hidden", "← {count} cols"),
+    ("preview.more_lines", "... {count} more lines"),
+    ("preview.total_bytes", "... {count} total bytes"),
+    ("expanded.title", "Expanded Preview (Esc to close)"),
+    ("details.title", "Clip details"),
+    ("details.content", "Content"),
+    ("details.id", "ID"),
+    ("details.created", "Created"),
+    ("details.last_used", "Last used"),
+    ("details.used", "Copied"),
+    ("details.times", "{count} times"),
+    ("details.size", "Size"),
+    ("details.sha256", "SHA-256"),
+    ("details.tags", "Tags"),
+    ("details.favorite", "Favorite"),
+    ("details.yes", "yes"),
+    ("details.note", "Note"),
+    ("details.meta", "Meta"),
+    ("details.image", "Image: {path}"),
+    (
+        "details.hint",
+        "j/k: Scroll │ y: Copy │ Enter: Copy+Exit │ i/Esc: Close",
+    ),
+    (
+        "expanded.image_info",
+        "Path: {path} │ Size: {size} │ {dimensions} │ Created: {created}",
+    ),
+    (
+        "expanded.hint",
+        "t/Esc: Close │ j/k: Navigate │ Enter: Copy+Exit │ y: Copy",
+    ),
+    (
+        "layout.too_small",
+        "Terminal too small\n\nCurrent: {width}x{height}\nMinimum: {min_width}x{min_height}\n\nPlease resize your terminal",
+    ),
+    // TUI status bar
+    ("status.hint", "j/k:Move  Enter:Copy  /:Search  ?:Help  q:Quit"),
+    ("status.snippet_hint", "1-9:Snippet"),
+    (
+        "status.multi_hint",
+        "[MULTI] Space:Select  v:All  d:Delete  y:Copy  M:Merge  Esc:Exit",
+    ),
+    ("status.selected", "{count} selected"),
+    ("status.entries", "{count} entries"),
+    ("status.filtered", "{shown}/{total} filtered"),
+    ("status.updated", "Updated: {ago} ago"),
+    // TUI note and tag editors
+    ("note.title", "Edit Note"),
+    ("note.hint", "Enter: Save  Esc: Cancel"),
+    ("tags.title", "Edit Tags"),
+    ("tags.none", "no tags"),
+    (
+        "tags.hint",
+        "Enter: Add  -tag: Remove  Tab: Complete  Esc: Done",
+    ),
+    // TUI help popup
+    ("help.title", "Help"),
+    ("help.unbound", "N/A"),
+    ("help.navigation", "Navigation"),
+    ("help.down", "Down"),
+    ("help.top", "Top"),
+    ("help.up", "Up"),
+    ("help.bottom", "Bottom"),
+    ("help.prev_page", "Prev page"),
+    ("help.next_page", "Next page"),
+    ("help.tabs", "Prev/Next tab"),
+    ("help.saved_view", "Saved view"),
+    ("help.actions", "Actions"),
+    ("help.copy_and_exit", "Copy & exit"),
+    ("help.delete", "Delete"),
+    ("help.undo_delete", "Undo delete"),
+    ("help.copy_and_type", "Copy, exit, type"),
+    ("help.cycle_sort", "Sort order"),
+    ("help.details", "Clip details"),
+    ("help.copy", "Copy"),
+    ("help.clear_all", "Clear all"),
+    ("help.toggle_favorite", "Toggle fav"),
+    ("help.edit_note", "Edit note"),
+    ("help.edit_clip", "Edit clip"),
+    ("help.edit_clip_as_new", "Edit as new"),
+    ("help.edit_tags", "Edit tags"),
+    ("help.search", "Search"),
+    ("help.start_search", "Start search"),
+    ("help.regex_mode", "Regex mode"),
+    ("help.toggle_mode", "Toggle mode"),
+    ("help.clear_exit", "Clear/exit"),
+    ("help.multi_select", "Multi-select"),
+    ("help.select", "Select"),
+    ("help.select_all", "Select all"),
+    ("help.merge", "Merge"),
+    ("help.view", "View"),
+    ("help.expand", "Expand"),
+    ("help.preview", "Preview"),
+    ("help.preview_mode", "Preview mode"),
+    ("help.line_numbers", "Line numbers"),
+    ("help.help", "Help"),
+    ("help.quit", "Quit"),
+    // CLI command output
+    ("cli.no_entries", "No clipboard entries found."),
+    ("cli.no_matches", "No matches found for: {query}"),
+    ("cli.entry_not_found", "Entry not found: {target}"),
+    ("cli.config.set", "Set {key} in {path}"),
+    ("cli.config.valid", "{path} is valid"),
+    ("cli.config.invalid", "{count} problem(s) in {path}"),
+    // ditox init
+    ("cli.init.intro", "Setting up {path}. Press Enter to keep the value in brackets."),
+    ("cli.init.max_entries", "Clips to keep"),
+    ("cli.init.max_storage_mb", "Storage cap in MB (0 for none)"),
+    ("cli.init.prune_interval", "Prune every N minutes"),
+    ("cli.init.backup_auto", "Automatic backups (off, daily, weekly)"),
+    ("cli.init.primary_selection", "Also capture the primary selection (y/n)"),
+    ("cli.init.accent", "Accent color"),
+    ("cli.init.invalid", "  {error}"),
+    ("cli.init.database", "Database ready at {path}"),
+    ("cli.init.install_unit", "Start the watcher at login ({backend})?"),
+    ("cli.transform.image", "Transforms only apply to text clips"),
+    ("cli.transform.unknown", "Unknown transform '{name}' (available: {available})"),
+    ("cli.transform.not_json", "json-pretty: not JSON: {error}"),
+    ("cli.transform.failed", "Transform '{name}' failed: {error}"),
+    ("cli.transform.not_utf8", "Transform '{name}' printed invalid UTF-8"),
+    ("cli.get.binary_to_terminal", "Not writing image bytes to a terminal; use --out FILE or a pipe"),
+    ("cli.index_zero", "Index must be 1 or greater"),
+    ("cli.no_recent_text", "recent text clip {n}"),
+    ("cli.copied", "Copied: {preview}"),
+    ("cli.copied_image", "Copied image: {preview}"),
+    ("cli.deleted", "Deleted: {preview}"),
+    ("stats.avg_length", "  Avg text length: {chars} chars"),
+    ("stats.fts_index", "  Search index:    {size}"),
+    ("stats.per_day", "Clips per day (last 30 days):"),
+    ("stats.per_day_peak", "  Peak: {count} on {date}"),
+    ("stats.top_tags", "Top tags:"),
+    ("cli.dedupe.none", "No duplicate clips found"),
+    ("cli.dedupe.group", "  {preview}  (+{count})"),
+    ("cli.dedupe.would_remove", "Would remove {count} duplicates in {groups} groups"),
+    ("cli.dedupe.removed", "Removed {count} duplicates in {groups} groups"),
+    ("cli.maintain.integrity_ok", "Integrity check: ok"),
+    ("cli.maintain.reindexed", "Rebuilt the search index ({count} clips); analyzed, vacuumed and checkpointed"),
+    ("cli.maintain.size", "Size: {before} -> {after}"),
+    ("cli.maintain.corrupt", "Integrity check failed; nothing was changed. Restore a backup or run `ditox repair`"),
+    ("cli.backup.created", "Backed up {entries} clips and {images} images to {path} ({size})"),
+    ("cli.backup.missing_images", "{count} image clips had no file in the store and were left out (see `ditox repair`)"),
+    ("cli.backup.restored", "Restored {entries} clips and {images} images from {path}"),
+    ("cli.backup.safety", "The previous history was saved to {path}"),
+    ("cli.ndjson.exported", "Exported {count} clips to {path}"),
+    ("cli.ndjson.imported", "Imported {added} clips from {path} ({updated} updated, {skipped} already present, {missing} images missing)"),
+    ("cli.import.history_done", "Imported {imported} clips from {path} ({duplicates} already present, {skipped} skipped)"),
+    ("cli.serve.listening", "Serving clips on {url}"),
+    ("cli.serve.writable", "Writable: clips can be deleted and favorited from the page"),
+    ("cli.favorited", "Added to favorites: {preview}"),
+    ("cli.unfavorited", "Removed from favorites: {preview}"),
+    ("cli.clear_prompt", "Clear all clipboard history? [y/N] "),
+    (
+        "cli.clear_typed_prompt",
+        "Clear all clipboard history? Type \"{phrase}\" to confirm: ",
+    ),
+    ("cli.cancelled", "Cancelled."),
+    ("cli.cleared", "Cleared {count} entries."),
+    ("cli.json_error", "JSON serialization error: {error}"),
+    // Unit after a right-aligned count in table rows
+    ("cli.entries_column", "entries"),
+    // Table headers
+    ("cli.column.id", "ID"),
+    ("cli.column.type", "Type"),
+    // Over the favorite star
+    ("cli.column.favorite", "Fav"),
+    ("cli.column.content", "Content"),
+    ("cli.column.age", "Age"),
+    ("cli.column.name", "Name"),
+    ("cli.column.color", "Color"),
+    ("cli.column.key", "Key"),
+    ("cli.column.entries", "Entries"),
+    // ditox collection
+    (
+        "cli.collection.none",
+        "No collections found. Create one with: ditox collection create <name>",
+    ),
+    ("cli.collection.exists", "Collection '{name}' already exists"),
+    ("cli.collection.not_found", "Collection not found: {target}"),
+    ("cli.collection.created", "Created collection: {name}"),
+    ("cli.collection.deleted", "Deleted collection: {name}"),
+    ("cli.collection.renamed", "Renamed collection '{old}' to '{new}'"),
+    ("cli.collection.added", "Added '{preview}' to collection '{name}'"),
+    ("cli.collection.removed", "Removed '{preview}' from its collection"),
+    ("cli.collection.empty", "No entries in collection '{name}'"),
+    ("cli.collection.title", "Collection: {name}"),
+    ("cli.watcher_stopped", "Stopped the running watcher"),
+    // ditox status
+    ("cli.status.title", "Ditox Status"),
+    ("cli.status.entries", "Entries:     {count}"),
+    ("cli.status.data_dir", "Data dir:    {path}"),
+    ("cli.status.images_dir", "Images dir:  {path}"),
+    ("cli.status.state_dir", "State dir:   {path}"),
+    ("cli.status.watcher", "Watcher:     {status}"),
+    ("cli.status.incognito", "Incognito:   on ({count} clips in memory)"),
+    ("cli.status.last_prune", "Last prune:  {time} ({count} removed)"),
+    ("cli.status.last_backup", "Last backup: {time}"),
+    ("cli.status.image_files", "Image files: {count}"),
+    ("cli.watcher.not_running", "not running"),
+    ("cli.watcher.running", "running"),
+    ("cli.watcher.healthy", "running, healthy ({count} recoveries)"),
+    (
+        "cli.watcher.failing",
+        "running, failing ({count} errors in a row: {error})",
+    ),
+    ("cli.watcher.not_responding", "running, not responding"),
+    ("cli.watcher.unknown_error", "unknown error"),
+    // Database from a newer ditox
+    (
+        "cli.schema.read_only",
+        "warning: the database has schema version {version}, newer than this build supports ({supported}); opened read-only",
+    ),
+    // ditox doctor
+    ("cli.doctor.no_log_problems", "No warnings or errors in the watcher log."),
+    ("cli.doctor.data_dir", "Data dir"),
+    ("cli.doctor.database", "Database"),
+    ("cli.doctor.clipboard", "Clipboard"),
+    ("cli.doctor.watcher", "Watcher"),
+    ("cli.doctor.writers", "Writers"),
+    ("cli.doctor.logs", "Logs"),
+    ("cli.doctor.entries", "{count} entries"),
+    ("cli.doctor.clipboard_ok", "clipboard tools available"),
+    ("cli.doctor.watcher_ok", "running"),
+    ("cli.doctor.watcher_failing", "running but failing: {error}"),
+    ("cli.doctor.not_responding", "not responding"),
+    ("cli.doctor.watcher_missing", "not running (ditox watch)"),
+    ("cli.doctor.writers_ok", "at most one process capturing clips"),
+    (
+        "cli.doctor.writers_many",
+        "several processes capture clips, so each is stored twice: {list}",
+    ),
+    (
+        "cli.doctor.log_summary",
+        "{path} ({count} warnings/errors, see --logs)",
+    ),
+    // ditox doctor --daemon
+    ("cli.daemon.database", "Database"),
+    ("cli.daemon.path", "  Path:        {path}"),
+    ("cli.daemon.schema", "  Schema:      v{version} (current v{current})"),
+    ("cli.daemon.journal", "  Journal:     {mode} (WAL {wal})"),
+    ("cli.daemon.size", "  Size:        {size}"),
+    (
+        "cli.daemon.clips",
+        "  Clips:       {text} text, {images} image ({favorites} favorites)",
+    ),
+    ("cli.daemon.images", "  Images:      {size}"),
+    ("cli.daemon.watcher", "Watcher"),
+    ("cli.daemon.not_running", "  Status:      not running (ditox watch)"),
+    (
+        "cli.daemon.no_health",
+        "  Status:      running (PID {pid}), no health report yet",
+    ),
+    ("cli.daemon.status", "  Status:      running (PID {pid}), {status}"),
+    ("cli.daemon.healthy", "healthy"),
+    ("cli.daemon.failing", "failing"),
+    ("cli.daemon.not_responding", "not responding"),
+    ("cli.daemon.backend", "  Backend:     {backend}"),
+    ("cli.daemon.capture", "  Capture:     {state}"),
+    ("cli.daemon.capture_active", "active"),
+    (
+        "cli.daemon.capture_incognito",
+        "paused, incognito ({count} clips in memory)",
+    ),
+    ("cli.daemon.captured_at", "  Captured at: {time}"),
+    ("cli.daemon.none_yet", "none yet"),
+    ("cli.daemon.last_poll", "  Last poll:   {time}"),
+    (
+        "cli.daemon.counters",
+        "  Counters:    {captured} captured, {duplicates} duplicates, {skipped} skipped, {errors} poll errors, {recoveries} recoveries",
+    ),
+    ("cli.daemon.last_error", "  Last error:  {error}"),
+    ("cli.daemon.last_prune", "  Last prune:  {time} ({count} removed)"),
+    ("cli.daemon.last_backup", "  Last backup: {time}"),
+    // ditox daemon install / uninstall / status
+    (
+        "cli.unit.exists",
+        "{path} already exists (use --force to overwrite)",
+    ),
+    ("cli.unit.written", "Wrote {path}"),
+    (
+        "cli.unit.enable_hint",
+        "Enable it with: systemctl --user daemon-reload && systemctl --user enable --now ditox",
+    ),
+    ("cli.unit.enable_hint_launchd", "It starts at next login; to start it now: launchctl load {path}"),
+    ("cli.unit.enable_hint_windows", "It starts at next login; to start it now: schtasks /Run /TN {path}"),
+    ("cli.unit.removed", "Removed {path}"),
+    ("cli.unit.not_installed", "Autostart ({backend}): not installed"),
+    ("cli.unit.installed", "Autostart ({backend}): {path}"),
+    ("cli.unit.running", "Watcher: running (PID {pid})"),
+    ("cli.unit.stopped", "Watcher: not running"),
+    // ditox pick
+    ("cli.pick.nothing_picked", "No clip picked"),
+    ("cli.image_missing_extension", "image entry missing extension"),
+    // ditox export-images / import-images / merge-db
+    ("cli.export.verified", "Export OK: every file matches the manifest"),
+    ("cli.export.bad_file", "  missing or corrupt: {file}"),
+    ("cli.export.verify_failed", "{count} file(s) failed verification"),
+    (
+        "cli.export.done",
+        "Exported images to {path}: {written} written, {unchanged} unchanged",
+    ),
+    (
+        "cli.export.missing_blobs",
+        "  {count} image entries have no blob on disk (run `ditox repair`)",
+    ),
+    (
+        "cli.import.done",
+        "Imported images from {path}: {imported} added, {present} already present",
+    ),
+    ("cli.import.bad_file", "  missing, corrupt or invalid: {file}"),
+    ("cli.import.failed", "{count} file(s) could not be imported"),
+    (
+        "cli.merge.done",
+        "Merged {path}: {added} added, {updated} updated, {unchanged} unchanged, {tags} tags added",
+    ),
+    (
+        "cli.merge.missing_images",
+        "Skipped {count} image clip(s) whose blob is missing or corrupt",
+    ),
+    // ditox migrate
+    ("cli.migrate.trigram_exists", "Trigram index already built."),
+    ("cli.migrate.trigram_built", "Built trigram index for {count} entries."),
+    ("cli.migrate.trigram_dropped", "Dropped trigram index."),
+    ("cli.migrate.trigram_missing", "Trigram index not built."),
+    ("cli.migrate.trigram_status", "Trigram index: {status}"),
+    ("cli.migrate.schema_version", "Schema version: {version} (this build: {supported})"),
+    ("cli.migrate.enabled", "enabled"),
+    (
+        "cli.migrate.disabled",
+        "disabled (enable with --enable-trigram)",
+    ),
+    // ditox meta
+    ("cli.meta.none", "No metadata for '{preview}'"),
+    ("cli.meta.key_not_found", "Metadata key not found: {key}"),
+    ("cli.meta.set", "Set {key} on '{preview}'"),
+    ("cli.meta.removed", "Removed {key} from '{preview}'"),
+    ("cli.meta.invalid_json", "Invalid metadata JSON: {error}"),
+    ("cli.meta.imported", "Imported {count} keys into '{preview}'"),
+    // ditox alias
+    ("cli.alias.none", "No aliases defined."),
+    ("cli.alias.removed", "Removed alias: {name}"),
+    ("cli.alias.not_found", "Alias not found: {name}"),
+    // ditox seed
+    (
+        "cli.seed.done",
+        "Seeded {clips} clips, {images} images and {tags} tags over {days} days",
+    ),
+    // ditox tag
+    ("cli.tag.none", "No tags."),
+    ("cli.tag.removed", "Removed {count} tag(s) from {preview}"),
+    ("cli.tag.not_found", "Tag not found: {name}"),
+    ("cli.tag.renamed", "Renamed tag '{old}' to '{new}' on {count} entries"),
+    (
+        "cli.tag.merged",
+        "Merged {from} into '{into}' ({count} entries retagged)",
+    ),
+    ("cli.tag.deleted", "Deleted tag '{name}' from {count} entries"),
+    // ditox views
+    (
+        "cli.views.unknown_type",
+        "Unknown entry type '{type}' (expected text or image)",
+    ),
+    ("cli.views.collection_not_found", "Collection not found: {target}"),
+    ("cli.views.saved", "Saved view '{name}' ({count} entries match now)"),
+    ("cli.views.none", "No saved views."),
+    ("cli.views.not_found", "View not found: {name}"),
+    ("cli.views.no_matches", "No entries match view '{name}'"),
+    ("cli.views.deleted", "Deleted view: {name}"),
+    // ditox incognito
+    ("cli.incognito.on", "Incognito on: new clips are kept in memory only."),
+    (
+        "cli.incognito.on_pending",
+        "Incognito on (takes effect when `ditox watch` starts).",
+    ),
+    ("cli.incognito.not_on", "Incognito is not on."),
+    (
+        "cli.incognito.off_commit",
+        "Incognito off: captured clips will be saved to history.",
+    ),
+    ("cli.incognito.off_discard", "Incognito off: captured clips discarded."),
+    ("cli.incognito.off", "Incognito off."),
+    ("cli.incognito.status_off", "Incognito: off"),
+    ("cli.incognito.status_on", "Incognito: on"),
+    (
+        "cli.incognito.status_clips",
+        "Incognito: on ({count} clips in memory)",
+    ),
+];
+
+/// Messages for the selected language, over the English defaults
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// English only
+    pub fn english() -> Self {
+        Self {
+            messages: ENGLISH
+                .iter()
+                .map(|(key, text)| (key.to_string(), text.to_string()))
+                .collect(),
+        }
+    }
+
+    /// English overlaid with a translation in TOML form. Non-string
+    /// values are ignored.
+    pub fn with_translation(toml_source: &str) -> Result<Self, toml::de::Error> {
+        let table: toml::Table = toml::from_str(toml_source)?;
+        let mut catalog = Self::english();
+        for (key, value) in table {
+            if let toml::Value::String(text) = value {
+                catalog.messages.insert(key, text);
+            }
+        }
+        Ok(catalog)
+    }
+
+    /// Catalog for a language code (`de`, `pt_BR`, ...) from its
+    /// translation file, or None if there is none. An invalid file falls
+    /// back to English with a warning in the log.
+    pub fn load(lang: &str) -> Option<Self> {
+        let path = ProjectDirs::from("com", "ditox", "ditox")?
+            .config_dir()
+            .join("locales")
+            .join(format!("{}.toml", lang));
+        let source = std::fs::read_to_string(&path).ok()?;
+        Some(Self::with_translation(&source).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring invalid translation {}: {}", path.display(), e);
+            Self::english()
+        }))
+    }
+
+    /// Catalog for a language, preferring a region-specific translation
+    /// (`pt_BR.toml`) over the bare language (`pt.toml`)
+    fn for_language(lang: &str) -> Self {
+        Self::load(lang)
+            .or_else(|| lang.split_once('_').and_then(|(base, _)| Self::load(base)))
+            .unwrap_or_else(Self::english)
+    }
+
+    /// Message text for `key`, or the key itself if it is unknown
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.messages.get(key).map_or(key, String::as_str)
+    }
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Language code from a locale name: `de_DE.UTF-8` -> `de_DE`. The C/POSIX
+/// locales mean English.
+pub fn language_of(locale: &str) -> Option<String> {
+    let lang = locale.split(['.', '@']).next().unwrap_or_default().trim();
+    match lang {
+        "" | "C" | "POSIX" => None,
+        lang => Some(lang.to_string()),
+    }
+}
+
+/// Language from the environment, in POSIX precedence order
+fn env_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        .and_then(|locale| language_of(&locale))
+}
+
+/// Select the catalog once at startup. `locale` is the configured
+/// `ui.locale`; without one the environment decides. Later calls have no
+/// effect.
+pub fn init(locale: Option<&str>) {
+    CATALOG.get_or_init(|| {
+        locale
+            .and_then(language_of)
+            .or_else(env_language)
+            .map_or_else(Catalog::english, |lang| Catalog::for_language(&lang))
+    });
+}
+
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| {
+        env_language().map_or_else(Catalog::english, |lang| Catalog::for_language(&lang))
+    })
+}
+
+/// Message text for `key`
+pub fn t(key: &str) -> String {
+    catalog().get(key).to_string()
+}
+
+/// Message text for `key` with `{name}` placeholders filled in
+pub fn tf(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    fill(catalog().get(key), args)
+}
+
+/// Substitute `{name}` placeholders in one pass, so braces inside a
+/// substituted value (clip text, a tag name) are left alone. Unknown
+/// placeholders stay as they are.
+fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        text.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let arg = after.find('}').and_then(|close| {
+            args.iter()
+                .find(|(name, _)| *name == &after[..close])
+                .map(|(_, value)| (close, value))
+        });
+        match arg {
+            Some((close, value)) => {
+                text.push_str(&value.to_string());
+                rest = &after[close + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_substitutes_once() {
+        assert_eq!(
+            fill(
+                "Added '{preview}' to collection '{name}'",
+                &[("preview", &"{name}"), ("name", &"work")]
+            ),
+            "Added '{name}' to collection 'work'"
+        );
+        assert_eq!(
+            fill("{a}{b} {c} {", &[("a", &1), ("b", &"{a}")]),
+            "1{a} {c} {"
+        );
+    }
+
+    #[test]
+    fn test_language_of() {
+        assert_eq!(language_of("de_DE.UTF-8").as_deref(), Some("de_DE"));
+        assert_eq!(language_of("sr_RS@latin").as_deref(), Some("sr_RS"));
+        assert_eq!(language_of("C.UTF-8"), None);
+        assert_eq!(language_of("POSIX"), None);
+    }
+
+    #[test]
+    fn test_translation_overrides_and_falls_back() {
+        let catalog = Catalog::with_translation(
+            r#"
+            "toast.entry_deleted" = "Eintrag gelöscht"
+            "toast.note_saved" = 3
+            "#,
+        )
+        .unwrap();
+        assert_eq!(catalog.get("toast.entry_deleted"), "Eintrag gelöscht");
+        assert_eq!(catalog.get("toast.note_saved"), "Note saved");
+        assert_eq!(catalog.get("no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        assert_eq!(
+            fill(
+                "Slot {slot}: {preview}",
+                &[("slot", &3), ("preview", &"abc")]
+            ),
+            "Slot 3: abc"
+        );
+    }
+
+    #[test]
+    fn test_english_keys_are_unique() {
+        let keys: std::collections::HashSet<_> = ENGLISH.iter().map(|(k, _)| k).collect();
+        assert_eq!(keys.len(), ENGLISH.len());
+    }
+}
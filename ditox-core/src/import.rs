@@ -0,0 +1,233 @@
+//! Importing history from other clipboard managers
+//!
+//! Supported sources, detected from the file's content unless given:
+//!
+//! - clipman: `~/.local/share/clipman.json`, a JSON array of strings,
+//!   oldest first
+//! - clipse: `~/.config/clipse/clipboard_history.json`, with timestamps,
+//!   pinned items (imported as favorites) and image files
+//! - GPaste: the XML history in `~/.local/share/gpaste/`, newest first;
+//!   password items are skipped
+//!
+//! Clips already in the history (same content hash) are left alone. Sources
+//! without timestamps get one second apart, ending now, so their order is
+//! kept.
+
+use crate::db::Database;
+use crate::entry::Entry;
+use crate::error::{DitoxError, Result};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use regex::Regex;
+use std::path::Path;
+
+/// History formats [`import`] understands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Clipman,
+    Clipse,
+    Gpaste,
+}
+
+impl ImportFormat {
+    /// Guess the format from a file's content
+    pub fn detect(content: &str) -> Option<Self> {
+        let trimmed = content.trim_start();
+        if trimmed.starts_with('<') {
+            Some(ImportFormat::Gpaste)
+        } else if trimmed.starts_with('[') {
+            Some(ImportFormat::Clipman)
+        } else if trimmed.starts_with('{') && trimmed.contains("\"clipboardHistory\"") {
+            Some(ImportFormat::Clipse)
+        } else {
+            None
+        }
+    }
+}
+
+/// Outcome of [`import`]
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub imported: usize,
+    /// Clips whose content was already in the history
+    pub duplicates: usize,
+    /// Passwords, and images whose file is gone
+    pub skipped: usize,
+}
+
+/// A clip read from another manager's history
+#[derive(Debug)]
+enum Item {
+    Text(String),
+    Image(String),
+    Skipped,
+}
+
+#[derive(Debug)]
+struct Record {
+    item: Item,
+    created_at: Option<DateTime<Utc>>,
+    favorite: bool,
+}
+
+/// Import the history file at `path`, in `format` or detected from its
+/// content
+pub fn import(db: &Database, path: &Path, format: Option<ImportFormat>) -> Result<ImportReport> {
+    let content = std::fs::read_to_string(path)?;
+    let format = format
+        .or_else(|| ImportFormat::detect(&content))
+        .ok_or_else(|| {
+            DitoxError::Other(format!(
+                "Unrecognized history format: {} (pass --format)",
+                path.display()
+            ))
+        })?;
+    let records = match format {
+        ImportFormat::Clipman => parse_clipman(&content)?,
+        ImportFormat::Clipse => parse_clipse(&content)?,
+        ImportFormat::Gpaste => parse_gpaste(&content)?,
+    };
+    db.in_transaction(|db| insert_records(db, records))
+}
+
+fn insert_records(db: &Database, records: Vec<Record>) -> Result<ImportReport> {
+    let mut report = ImportReport::default();
+    let now = Utc::now();
+    let count = records.len() as i64;
+    for (i, record) in records.into_iter().enumerate() {
+        let mut entry = match record.item {
+            Item::Text(text) if !text.is_empty() => Entry::new_text(text),
+            Item::Image(path) => match image_entry(&path)? {
+                Some(entry) => entry,
+                None => {
+                    report.skipped += 1;
+                    continue;
+                }
+            },
+            Item::Text(_) | Item::Skipped => {
+                report.skipped += 1;
+                continue;
+            }
+        };
+        entry.created_at = record
+            .created_at
+            .unwrap_or_else(|| now - Duration::seconds(count - 1 - i as i64));
+        entry.last_used = entry.created_at;
+        entry.favorite = record.favorite;
+        if db.insert(&entry)? {
+            report.imported += 1;
+        } else {
+            report.duplicates += 1;
+        }
+    }
+    Ok(report)
+}
+
+/// Copy an image file into the store, or None if it can't be read
+fn image_entry(path: &str) -> Result<Option<Entry>> {
+    let Ok(bytes) = std::fs::read(path) else {
+        return Ok(None);
+    };
+    let ext = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .filter(|e| !e.is_empty() && e.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or_else(|| "png".to_string());
+    let hash = Entry::compute_hash(&bytes);
+    Database::store_image_blob(&hash, &ext, &bytes)?;
+    Ok(Some(Entry::new_image(hash, bytes.len(), ext)))
+}
+
+fn json_error(e: serde_json::Error) -> DitoxError {
+    DitoxError::Other(format!("Invalid history file: {}", e))
+}
+
+fn parse_clipman(content: &str) -> Result<Vec<Record>> {
+    let items: Vec<String> = serde_json::from_str(content).map_err(json_error)?;
+    Ok(items
+        .into_iter()
+        .map(|text| Record {
+            item: Item::Text(text),
+            created_at: None,
+            favorite: false,
+        })
+        .collect())
+}
+
+fn parse_clipse(content: &str) -> Result<Vec<Record>> {
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct History {
+        clipboard_history: Vec<ClipseItem>,
+    }
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ClipseItem {
+        value: String,
+        #[serde(default)]
+        recorded: String,
+        #[serde(default)]
+        file_path: String,
+        #[serde(default)]
+        pinned: bool,
+    }
+
+    let history: History = serde_json::from_str(content).map_err(json_error)?;
+    // Newest first in the file
+    Ok(history
+        .clipboard_history
+        .into_iter()
+        .rev()
+        .map(|item| Record {
+            item: if item.file_path.is_empty() || item.file_path == "null" {
+                Item::Text(item.value)
+            } else {
+                Item::Image(item.file_path)
+            },
+            created_at: parse_time(&item.recorded),
+            favorite: item.pinned,
+        })
+        .collect())
+}
+
+fn parse_gpaste(content: &str) -> Result<Vec<Record>> {
+    let item_re = Regex::new(r#"(?s)<item\s([^>]*)>\s*<value><!\[CDATA\[(.*?)\]\]></value>"#)
+        .map_err(|e| DitoxError::Other(e.to_string()))?;
+    let kind_re = Regex::new(r#"kind="([^"]*)""#).map_err(|e| DitoxError::Other(e.to_string()))?;
+    // Newest first in the file
+    let mut records: Vec<Record> = item_re
+        .captures_iter(content)
+        .map(|caps| {
+            let kind = kind_re
+                .captures(&caps[1])
+                .map(|k| k[1].to_string())
+                .unwrap_or_default();
+            // A "]]>" inside the value is split across two CDATA sections
+            let value = caps[2].replace("]]]]><![CDATA[>", "]]>");
+            let item = match kind.as_str() {
+                "Text" | "Uris" => Item::Text(value),
+                "Image" => Item::Image(value),
+                _ => Item::Skipped,
+            };
+            Record {
+                item,
+                created_at: None,
+                favorite: false,
+            }
+        })
+        .collect();
+    records.reverse();
+    Ok(records)
+}
+
+/// clipse timestamps: RFC 3339, or Go's default `2006-01-02 15:04:05.999`
+/// layout (taken as UTC)
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+        return Some(time.with_timezone(&Utc));
+    }
+    let head = s.get(..s.find(" +").unwrap_or(s.len()))?;
+    NaiveDateTime::parse_from_str(head, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|t| t.and_utc())
+}
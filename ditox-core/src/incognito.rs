@@ -0,0 +1,165 @@
+//! Incognito capture
+//!
+//! While incognito is on, the watcher keeps capturing clipboard changes but
+//! holds them in an in-memory buffer instead of the database, so nothing
+//! reaches disk. Ending incognito either discards the buffer or commits it
+//! to the database.
+//!
+//! The switch is a small state file in the data dir, which lets the CLI
+//! (`ditox incognito on|off`) control a `ditox watch` running elsewhere.
+
+use crate::clipboard::ClipboardImage;
+use crate::db::Database;
+use crate::entry::Entry;
+use crate::error::Result;
+use std::fs;
+use std::path::PathBuf;
+
+/// Incognito mode as requested through the state file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncognitoState {
+    /// Normal capture into the database
+    Off,
+    /// Capture into memory only
+    On,
+    /// End incognito and write the buffered clips to the database
+    EndCommit,
+    /// End incognito and drop the buffered clips
+    EndDiscard,
+}
+
+impl IncognitoState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            IncognitoState::Off => "off",
+            IncognitoState::On => "on",
+            IncognitoState::EndCommit => "commit",
+            IncognitoState::EndDiscard => "discard",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s.trim() {
+            "on" => IncognitoState::On,
+            "commit" => IncognitoState::EndCommit,
+            "discard" => IncognitoState::EndDiscard,
+            _ => IncognitoState::Off,
+        }
+    }
+}
+
+/// Get the path to the incognito state file
+pub fn get_state_file_path() -> Result<PathBuf> {
+    Ok(Database::get_state_dir()?.join("incognito"))
+}
+
+/// Read the requested incognito state (Off if there is no state file)
+pub fn read_state() -> IncognitoState {
+    get_state_file_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|s| IncognitoState::parse(&s))
+        .unwrap_or(IncognitoState::Off)
+}
+
+/// Request an incognito state. `Off` removes the state file.
+pub fn write_state(state: IncognitoState) -> Result<()> {
+    let path = get_state_file_path()?;
+    if state == IncognitoState::Off {
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+    } else {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, state.as_str())?;
+    }
+    Ok(())
+}
+
+/// A clip captured during incognito. Images keep their bytes in memory;
+/// nothing is written to the image store until commit.
+#[derive(Debug, Clone)]
+struct PendingClip {
+    entry: Entry,
+    image: Option<ClipboardImage>,
+}
+
+/// In-memory overlay the watcher captures into while incognito is on
+#[derive(Debug, Default)]
+pub struct IncognitoBuffer {
+    clips: Vec<PendingClip>,
+}
+
+impl IncognitoBuffer {
+    /// Number of buffered clips
+    pub fn len(&self) -> usize {
+        self.clips.len()
+    }
+
+    /// True if nothing has been captured
+    pub fn is_empty(&self) -> bool {
+        self.clips.is_empty()
+    }
+
+    /// True if a clip with this content hash is already buffered
+    pub fn contains_hash(&self, hash: &str) -> bool {
+        self.clips.iter().any(|c| c.entry.hash == hash)
+    }
+
+    /// Buffer a text clip. Returns false if it was already buffered.
+    pub fn push_text(&mut self, text: String) -> bool {
+        let entry = Entry::new_text(text);
+        if self.contains_hash(&entry.hash) {
+            return false;
+        }
+        self.clips.push(PendingClip { entry, image: None });
+        true
+    }
+
+    /// Buffer an image clip. Returns false if it was already buffered.
+    pub fn push_image(&mut self, image: ClipboardImage) -> bool {
+        if self.contains_hash(&image.hash) {
+            return false;
+        }
+        let entry = Entry::new_image(
+            image.hash.clone(),
+            image.bytes.len(),
+            image.extension.clone(),
+        );
+        self.clips.push(PendingClip {
+            entry,
+            image: Some(image),
+        });
+        true
+    }
+
+    /// Drop every buffered clip. Returns how many were dropped.
+    pub fn discard(&mut self) -> usize {
+        let count = self.clips.len();
+        self.clips.clear();
+        count
+    }
+
+    /// Write buffered clips to the database (skipping ones it already has)
+    /// and empty the buffer. Returns how many entries were inserted.
+    pub fn commit(&mut self, db: &mut Database, max_entries: usize) -> Result<usize> {
+        let mut inserted = 0;
+        for clip in std::mem::take(&mut self.clips) {
+            if db.exists_by_hash(&clip.entry.hash)? {
+                continue;
+            }
+            if let Some(image) = &clip.image {
+                Database::store_image_blob(&image.hash, &image.extension, &image.bytes)?;
+            }
+            db.insert(&clip.entry)?;
+            inserted += 1;
+        }
+        if inserted > 0 {
+            db.cleanup_old(max_entries)?;
+        }
+        Ok(inserted)
+    }
+}
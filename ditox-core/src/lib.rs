@@ -5,14 +5,30 @@
 
 pub mod actions;
 pub mod app;
+pub mod autostart;
+pub mod backup;
 pub mod clipboard;
 pub mod collection;
 pub mod config;
+pub mod config_edit;
+pub mod confirm;
 pub mod content_type;
 pub mod db;
+pub mod dedupe;
 pub mod entry;
 pub mod error;
+pub mod export;
+pub mod hooks;
+pub mod i18n;
+pub mod import;
+pub mod incognito;
+pub mod merge;
+pub mod ndjson;
+pub mod osc52;
+pub mod query;
+pub mod seed;
 pub mod stats;
+pub mod undo;
 pub mod watcher;
 
 // Re-export commonly used types
@@ -24,5 +40,6 @@ pub use config::Config;
 pub use db::Database;
 pub use entry::{Entry, EntryType};
 pub use error::{DitoxError, Result};
+pub use query::{Query, SearchHit};
 pub use stats::Stats;
 pub use watcher::Watcher;
@@ -0,0 +1,127 @@
+//! Merging another ditox database into this one
+//!
+//! For consolidating histories from a second machine without any sync
+//! setup: copy its `ditox.db` (and `images/` directory next to it) over and
+//! run `ditox merge-db`. Entries are matched by content hash, so merging
+//! the same file twice changes nothing:
+//!
+//! - new clips are added with their tags, notes and timestamps
+//! - clips present on both sides stay favorites if either side has them as
+//!   one, keep the earliest creation and latest use, and gain the other
+//!   side's tags (and notes, if they had none)
+//! - image blobs are copied from the other `images/` directory; clips whose
+//!   blob is missing or corrupt there are skipped
+//! - a new clip whose id is already used here by different content gets a
+//!   fresh id
+//!
+//! The merge runs in one transaction: if it fails partway, this database
+//! is left as it was (blobs already copied stay in the store for `ditox
+//! repair` to collect).
+//!
+//! Collections are per-database and are not carried over.
+
+use crate::db::Database;
+use crate::entry::{Entry, EntryType};
+use crate::error::{DitoxError, Result};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Outcome of [`merge_db`]
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    /// Clips added to this database
+    pub added: usize,
+    /// Clips already here whose favorite/time/notes were updated
+    pub updated: usize,
+    /// Clips already here with nothing to update
+    pub unchanged: usize,
+    /// Tag assignments added
+    pub tags: usize,
+    /// Image clips skipped because their blob is missing or corrupt
+    pub missing_images: Vec<String>,
+}
+
+/// Merge the database at `other` (another machine's `ditox.db`) into `db`.
+/// Image blobs are read from the `images/` directory next to it. The other
+/// database is only read.
+pub fn merge_db(db: &Database, other: &Path) -> Result<MergeReport> {
+    if !other.is_file() {
+        return Err(DitoxError::NotFound(format!(
+            "Database not found: {}",
+            other.display()
+        )));
+    }
+    let images_dir = other
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("images");
+    let source = Database::open_read_only(other)?;
+    db.in_transaction(|db| merge_entries(db, &source, &images_dir))
+}
+
+/// Fold every entry of `source` into `db`
+fn merge_entries(db: &Database, source: &Database, images_dir: &Path) -> Result<MergeReport> {
+    let mut report = MergeReport::default();
+    for entry in source.get_all(i64::MAX as usize)? {
+        let tags = source.get_tags(&entry.id)?;
+
+        let id = match db.get_by_hash(&entry.hash)? {
+            Some(local) => {
+                if db.merge_entry(&local.id, &entry)? {
+                    report.updated += 1;
+                } else {
+                    report.unchanged += 1;
+                }
+                local.id
+            }
+            None => {
+                if entry.entry_type == EntryType::Image && !copy_blob(&entry, images_dir)? {
+                    report.missing_images.push(entry.id);
+                    continue;
+                }
+                let mut entry = Entry {
+                    collection_id: None,
+                    ..entry
+                };
+                // Same id, different content: the ids were generated
+                // independently, so this one is free to change
+                if !db.insert(&entry)? {
+                    entry.id = Uuid::new_v4().to_string();
+                    db.insert(&entry)?;
+                }
+                report.added += 1;
+                entry.id
+            }
+        };
+
+        for tag in tags {
+            if db.add_tag(&id, &tag)? {
+                report.tags += 1;
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Copy an image clip's blob into the store after checking its hash.
+/// Returns false if the blob is missing or doesn't match.
+fn copy_blob(entry: &Entry, images_dir: &Path) -> Result<bool> {
+    let extension = entry.image_extension.as_deref().unwrap_or("png");
+    if entry.hash.len() < 2 {
+        return Ok(false);
+    }
+    let path = images_dir
+        .join(&entry.hash[..2])
+        .join(format!("{}.{}", entry.hash, extension));
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+    if Entry::compute_hash(&bytes) != entry.hash {
+        tracing::warn!("skipping corrupt image blob {}", path.display());
+        return Ok(false);
+    }
+    Database::store_image_blob(&entry.hash, extension, &bytes)?;
+    Ok(true)
+}
@@ -0,0 +1,250 @@
+//! Streaming NDJSON export and import
+//!
+//! One JSON object per line and per clip: the entry's fields plus its tags,
+//! metadata, copy count and collection name. Image payloads are either
+//! inlined as base64 (the default, so a single stream carries everything)
+//! or only referenced by hash, for when the image store travels separately
+//! (`ditox export-images`).
+//!
+//! Both directions stream, so `ditox export | ssh host ditox import -`
+//! works on large histories without holding them in memory. The export
+//! reads from one snapshot of the database; the import runs in one
+//! transaction.
+
+use crate::db::Database;
+use crate::entry::{Entry, EntryType};
+use crate::error::{DitoxError, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use uuid::Uuid;
+
+/// Clips read from the database per query while exporting
+const PAGE_SIZE: usize = 500;
+
+/// How image clips carry their bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImagePayload {
+    /// Base64 in the record
+    Inline,
+    /// Hash and extension only
+    Reference,
+}
+
+/// What to do with a record whose content is already in the history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep the existing clip as it is
+    Skip,
+    /// Fold the record in: favorite if either is, earliest creation, latest
+    /// use, notes if missing, plus its tags and missing metadata keys
+    Merge,
+    /// Replace the existing clip with the record
+    Replace,
+}
+
+/// One line of the stream
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    #[serde(flatten)]
+    entry: Entry,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    meta: BTreeMap<String, String>,
+    #[serde(default)]
+    usage_count: i64,
+    /// Collection name; IDs are per-database
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    collection: Option<String>,
+    /// Base64 image bytes for [`ImagePayload::Inline`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    image_data: Option<String>,
+}
+
+/// Outcome of [`import`]
+#[derive(Debug, Default)]
+pub struct NdjsonImportReport {
+    pub added: usize,
+    /// Existing clips merged into or replaced
+    pub updated: usize,
+    /// Records skipped because their content was already present
+    pub skipped: usize,
+    /// Image records whose bytes were neither inline nor in the store
+    pub missing_images: usize,
+}
+
+/// Whether `content` (or its start) looks like an NDJSON export
+pub fn detect(content: &str) -> bool {
+    let first = content.trim_start().lines().next().unwrap_or_default();
+    first.starts_with('{') && first.contains("\"entry_type\"")
+}
+
+/// Write every clip to `out`, newest first. Returns the number written.
+pub fn export(db: &Database, out: &mut impl Write, images: ImagePayload) -> Result<usize> {
+    db.in_read_transaction(|db| {
+        let mut written = 0;
+        loop {
+            let page = db.get_page(written, PAGE_SIZE)?;
+            for entry in &page {
+                write_entry(db, out, entry, images)?;
+            }
+            written += page.len();
+            if page.len() < PAGE_SIZE {
+                break;
+            }
+        }
+        out.flush()?;
+        Ok(written)
+    })
+}
+
+/// Write one clip as a line of the stream
+pub fn write_entry(
+    db: &Database,
+    out: &mut impl Write,
+    entry: &Entry,
+    images: ImagePayload,
+) -> Result<()> {
+    let image_data = match (entry.entry_type, images) {
+        (EntryType::Image, ImagePayload::Inline) => {
+            let ext = entry.image_extension.as_deref().unwrap_or("png");
+            std::fs::read(Database::image_path(&entry.hash, ext)?)
+                .ok()
+                .map(|bytes| STANDARD.encode(bytes))
+        }
+        _ => None,
+    };
+    let collection = match &entry.collection_id {
+        Some(id) => db.get_collection_by_id(id)?.map(|c| c.name),
+        None => None,
+    };
+    let record = Record {
+        tags: db.get_tags(&entry.id)?,
+        meta: db.get_all_meta(&entry.id)?,
+        usage_count: db.usage_count(&entry.id)?,
+        collection,
+        image_data,
+        entry: Entry {
+            collection_id: None,
+            ..entry.clone()
+        },
+    };
+    serde_json::to_writer(&mut *out, &record).map_err(json_error)?;
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Read records from `input` into `db`. Clips are matched by content; a
+/// new clip whose ID is taken by other content gets a fresh one.
+pub fn import(
+    db: &Database,
+    input: impl BufRead,
+    policy: ConflictPolicy,
+) -> Result<NdjsonImportReport> {
+    db.in_transaction(|db| {
+        let mut report = NdjsonImportReport::default();
+        for (n, line) in input.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: Record = serde_json::from_str(&line)
+                .map_err(|e| DitoxError::Other(format!("Line {}: {}", n + 1, e)))?;
+            import_record(db, record, policy, &mut report)
+                .map_err(|e| DitoxError::Other(format!("Line {}: {}", n + 1, e)))?;
+        }
+        Ok(report)
+    })
+}
+
+fn import_record(
+    db: &Database,
+    mut record: Record,
+    policy: ConflictPolicy,
+    report: &mut NdjsonImportReport,
+) -> Result<()> {
+    let entry = &mut record.entry;
+    if entry.entry_type == EntryType::Text {
+        entry.hash = Entry::compute_hash(entry.content.as_bytes());
+    } else if !store_image(entry, record.image_data.as_deref())? {
+        report.missing_images += 1;
+        return Ok(());
+    }
+
+    let existing = db.get_by_hash(&entry.hash)?;
+    match (existing, policy) {
+        (Some(_), ConflictPolicy::Skip) => {
+            report.skipped += 1;
+            return Ok(());
+        }
+        (Some(existing), ConflictPolicy::Merge) => {
+            db.merge_entry(&existing.id, entry)?;
+            let usage = db.usage_count(&existing.id)?.max(record.usage_count);
+            db.set_usage_count(&existing.id, usage)?;
+            for tag in &record.tags {
+                db.add_tag(&existing.id, tag)?;
+            }
+            for (key, value) in &record.meta {
+                if db.get_meta(&existing.id, key)?.is_none() {
+                    db.set_meta(&existing.id, key, value)?;
+                }
+            }
+            report.updated += 1;
+            return Ok(());
+        }
+        (Some(existing), ConflictPolicy::Replace) => {
+            // Same content, so an image row keeps pointing at the same blob
+            db.delete_dangling_row(&existing.id)?;
+            report.updated += 1;
+        }
+        (None, _) => report.added += 1,
+    }
+
+    if db.get_by_id(&entry.id)?.is_some() {
+        entry.id = Uuid::new_v4().to_string();
+    }
+    entry.collection_id = match &record.collection {
+        Some(name) => db.get_collection_by_name(name)?.map(|c| c.id),
+        None => None,
+    };
+    db.insert(entry)?;
+    db.set_usage_count(&entry.id, record.usage_count)?;
+    for tag in &record.tags {
+        db.add_tag(&entry.id, tag)?;
+    }
+    for (key, value) in &record.meta {
+        db.set_meta(&entry.id, key, value)?;
+    }
+    Ok(())
+}
+
+/// Put an image record's bytes in the store, checking them against its
+/// hash. False if there are none inline and the store lacks the blob.
+fn store_image(entry: &Entry, data: Option<&str>) -> Result<bool> {
+    let ext = entry.image_extension.as_deref().unwrap_or("png");
+    let Some(data) = data else {
+        return Ok(Database::image_path(&entry.hash, ext)?.is_file());
+    };
+    let bytes = STANDARD
+        .decode(data)
+        .map_err(|e| DitoxError::Other(format!("invalid image data: {}", e)))?;
+    if Entry::compute_hash(&bytes) != entry.hash {
+        return Err(DitoxError::Other(format!(
+            "image data does not match hash {}",
+            entry.hash
+        )));
+    }
+    Database::store_image_blob(&entry.hash, ext, &bytes)?;
+    Ok(true)
+}
+
+fn json_error(e: serde_json::Error) -> DitoxError {
+    // Keep write failures (a closed pipe) recognizable as I/O errors
+    if let Some(kind) = e.io_error_kind() {
+        return DitoxError::Io(kind.into());
+    }
+    DitoxError::Other(format!("JSON serialization error: {}", e))
+}
@@ -0,0 +1,112 @@
+//! OSC 52 clipboard writes
+//!
+//! Over SSH the remote host has no way to reach the local clipboard, but
+//! most terminals accept an OSC 52 escape sequence that sets it for us.
+//! [`Clipboard::set_text`](crate::clipboard::Clipboard::set_text) switches
+//! to this backend when `SSH_TTY` is set or `--osc52` forced it.
+//!
+//! Inside tmux the sequence is wrapped in a DCS passthrough; GNU screen
+//! caps DCS strings, so there the payload is split into several of them.
+
+use crate::error::{DitoxError, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Largest clip sent, in bytes. Terminals commonly drop OSC 52 payloads
+/// over 100 000 encoded bytes.
+pub const MAX_BYTES: usize = 74_994;
+
+/// Encoded bytes per DCS chunk under GNU screen
+const SCREEN_CHUNK: usize = 76;
+
+static FORCED: AtomicBool = AtomicBool::new(false);
+
+/// Always copy through OSC 52, even outside SSH
+pub fn force(on: bool) {
+    FORCED.store(on, Ordering::Relaxed);
+}
+
+/// Whether copies should go through OSC 52
+pub fn active() -> bool {
+    FORCED.load(Ordering::Relaxed) || std::env::var_os("SSH_TTY").is_some()
+}
+
+/// Terminal multiplexer the sequence has to pass through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Passthrough {
+    None,
+    Tmux,
+    Screen,
+}
+
+impl Passthrough {
+    /// Detect from `TMUX` and `TERM`
+    pub fn detect() -> Self {
+        if std::env::var_os("TMUX").is_some() {
+            Passthrough::Tmux
+        } else if std::env::var("TERM").is_ok_and(|term| term.starts_with("screen")) {
+            Passthrough::Screen
+        } else {
+            Passthrough::None
+        }
+    }
+}
+
+/// Build the escape sequence that puts `text` on the clipboard
+pub fn sequence(text: &str, passthrough: Passthrough) -> Result<String> {
+    if text.len() > MAX_BYTES {
+        return Err(DitoxError::Clipboard(format!(
+            "Clip is too large for OSC 52 ({} bytes, limit {})",
+            text.len(),
+            MAX_BYTES
+        )));
+    }
+    let encoded = STANDARD.encode(text);
+    Ok(match passthrough {
+        Passthrough::None => format!("\x1b]52;c;{}\x07", encoded),
+        // tmux forwards DCS contents verbatim once inner ESCs are doubled
+        Passthrough::Tmux => format!("\x1bPtmux;\x1b\x1b]52;c;{}\x07\x1b\\", encoded),
+        Passthrough::Screen => {
+            let mut out = String::from("\x1bP\x1b]52;c;");
+            // Base64 is ASCII, so byte chunks are valid str slices
+            for (i, chunk) in encoded.as_bytes().chunks(SCREEN_CHUNK).enumerate() {
+                if i > 0 {
+                    out.push_str("\x1b\\\x1bP");
+                }
+                out.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+            }
+            out.push_str("\x07\x1b\\");
+            out
+        }
+    })
+}
+
+/// Send `text` to the terminal's clipboard. Writes to the controlling
+/// terminal so it still works when stdout is piped.
+pub fn copy(text: &str) -> Result<()> {
+    let seq = sequence(text, Passthrough::detect())?;
+    let written = std::fs::OpenOptions::new()
+        .write(true)
+        .open(tty_path())
+        .and_then(|mut tty| tty.write_all(seq.as_bytes()));
+    if written.is_err() {
+        let mut stdout = std::io::stdout();
+        stdout
+            .write_all(seq.as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(|e| DitoxError::Clipboard(format!("Failed to write OSC 52: {}", e)))?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn tty_path() -> &'static str {
+    "/dev/tty"
+}
+
+#[cfg(windows)]
+fn tty_path() -> &'static str {
+    "CONOUT$"
+}
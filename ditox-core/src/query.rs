@@ -0,0 +1,510 @@
+//! Structured search queries
+//!
+//! A `Query` describes which entries to fetch independently of how the
+//! caller collected the criteria (CLI flags, TUI search box, GUI filters).
+//! `Database::query` turns it into a single SQL statement.
+//!
+//! `Query::parse` reads the search language shared by the CLI, the TUI and
+//! saved views:
+//!
+//! | Syntax | Meaning |
+//! |---|---|
+//! | `word`, `"a phrase"` | full-text match |
+//! | `tag:work` | tagged `work` |
+//! | `is:fav` | favorites |
+//! | `is:primary` | captured from the primary selection |
+//! | `kind:text`, `kind:image` | entry type |
+//! | `after:2026-01-31`, `after:7d` | created after a date / within 7 days |
+//! | `before:2026-01-31`, `before:2w` | created before a date / over 2 weeks ago |
+//! | `-term` | negates any of the above |
+
+use crate::entry::EntryType;
+use crate::error::{DitoxError, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Search criteria. Every populated field narrows the result set; an empty
+/// query matches every entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Query {
+    /// Full-text (FTS5) match against content and notes, prefix-matched
+    /// the same way as `Database::search_entries`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Full-text terms that must NOT match
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude_text: Vec<String>,
+    /// Regular expression matched against the content of text entries via
+    /// the `regexp()` SQL function. Case-sensitive unless the pattern
+    /// opts out with `(?i)`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    /// Restrict to a single entry type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry_type: Option<EntryType>,
+    /// Only favorite entries
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub favorites_only: bool,
+    /// Only entries that are not favorites
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub exclude_favorites: bool,
+    /// Only entries captured from the primary selection
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub primary_only: bool,
+    /// Only entries not captured from the primary selection
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub exclude_primary: bool,
+    /// Only entries in this collection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection_id: Option<String>,
+    /// Only entries carrying all of these tags
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Only entries carrying none of these tags
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exclude_tags: Vec<String>,
+    /// Only entries created within this span of when the query runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_ago: Option<Ago>,
+    /// Only entries created longer than this span before the query runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_ago: Option<Ago>,
+    /// Only entries created after this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<DateTime<Utc>>,
+    /// Only entries created before this time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<DateTime<Utc>>,
+}
+
+impl Query {
+    /// Query matching a regular expression
+    pub fn regex(pattern: impl Into<String>) -> Self {
+        Self {
+            regex: Some(pattern.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Parse the search language described in the module docs. Text that
+    /// isn't an operator becomes the full-text part, so plain input behaves
+    /// exactly like before operators existed.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut query = Query::default();
+        let mut text: Vec<String> = Vec::new();
+
+        for token in tokenize(input) {
+            let (negated, body) = match token.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, token.as_str()),
+            };
+
+            let Some((key, value)) = body.split_once(':').filter(|(k, _)| is_operator(k)) else {
+                if negated {
+                    query.exclude_text.push(body.to_string());
+                } else {
+                    text.push(body.to_string());
+                }
+                continue;
+            };
+            let value = unquote(value);
+            if value.is_empty() {
+                return Err(DitoxError::Other(format!("Missing value for '{}:'", key)));
+            }
+
+            match key {
+                "tag" if negated => query.exclude_tags.push(value.to_string()),
+                "tag" => query.tags.push(value.to_string()),
+                "is" => match value {
+                    "fav" | "favorite" | "favourite" => {
+                        if negated {
+                            query.exclude_favorites = true;
+                        } else {
+                            query.favorites_only = true;
+                        }
+                    }
+                    "primary" | "selection" => {
+                        if negated {
+                            query.exclude_primary = true;
+                        } else {
+                            query.primary_only = true;
+                        }
+                    }
+                    _ => {
+                        return Err(DitoxError::Other(format!(
+                            "Unknown 'is:{}' (expected is:fav or is:primary)",
+                            value
+                        )))
+                    }
+                },
+                "kind" => {
+                    let kind = EntryType::from_str(value).ok_or_else(|| {
+                        DitoxError::Other(format!(
+                            "Unknown 'kind:{}' (expected text or image)",
+                            value
+                        ))
+                    })?;
+                    query.entry_type = Some(match (negated, kind) {
+                        (false, kind) => kind,
+                        (true, EntryType::Text) => EntryType::Image,
+                        (true, EntryType::Image) => EntryType::Text,
+                    });
+                }
+                "after" | "before" => {
+                    // A negated bound is the opposite bound
+                    let is_after = (key == "after") != negated;
+                    match parse_when(value)? {
+                        When::Ago(ago) if is_after => query.after_ago = Some(ago),
+                        When::Ago(ago) => query.before_ago = Some(ago),
+                        When::At(time) if is_after => query.after = Some(time),
+                        When::At(time) => query.before = Some(time),
+                    }
+                }
+                _ => unreachable!("is_operator covers every key"),
+            }
+        }
+
+        if !text.is_empty() {
+            query.text = Some(text.join(" "));
+        }
+        Ok(query)
+    }
+
+    /// Like `parse`, but input that doesn't parse (e.g. a half-typed
+    /// `kind:ima`) is treated as plain full-text. Meant for
+    /// search-as-you-type, where an error on every keystroke is noise.
+    pub fn parse_or_text(input: &str) -> Self {
+        Self::parse(input).unwrap_or_else(|_| Self {
+            text: Some(input.trim().to_string()).filter(|t| !t.is_empty()),
+            ..Self::default()
+        })
+    }
+
+    /// The full-text part without phrase quotes, for fuzzy ranking
+    pub fn plain_text(&self) -> Option<String> {
+        self.text.as_ref().map(|t| t.replace('"', ""))
+    }
+
+    /// This query without its full-text part: the filters only
+    pub fn filters(&self) -> Self {
+        Self {
+            text: None,
+            ..self.clone()
+        }
+    }
+
+    /// True if no criteria are set
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Renders the query in the search language, so it can be shown to users
+/// and parsed back. Regex and collection criteria have no syntax and are
+/// left out.
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(text) = &self.text {
+            parts.push(text.clone());
+        }
+        parts.extend(self.exclude_text.iter().map(|t| format!("-{}", t)));
+        parts.extend(self.tags.iter().map(|t| format!("tag:{}", quote(t))));
+        parts.extend(
+            self.exclude_tags
+                .iter()
+                .map(|t| format!("-tag:{}", quote(t))),
+        );
+        if self.favorites_only {
+            parts.push("is:fav".to_string());
+        }
+        if self.exclude_favorites {
+            parts.push("-is:fav".to_string());
+        }
+        if self.primary_only {
+            parts.push("is:primary".to_string());
+        }
+        if self.exclude_primary {
+            parts.push("-is:primary".to_string());
+        }
+        if let Some(kind) = self.entry_type {
+            parts.push(format!("kind:{}", kind.as_str()));
+        }
+        if let Some(ago) = self.after_ago {
+            parts.push(format!("after:{}", ago));
+        }
+        if let Some(ago) = self.before_ago {
+            parts.push(format!("before:{}", ago));
+        }
+        if let Some(after) = self.after {
+            parts.push(format!("after:{}", after.to_rfc3339()));
+        }
+        if let Some(before) = self.before {
+            parts.push(format!("before:{}", before.to_rfc3339()));
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+fn is_operator(key: &str) -> bool {
+    matches!(key, "tag" | "is" | "kind" | "after" | "before")
+}
+
+/// Split on whitespace outside double quotes. Quotes are kept so phrases
+/// still read as phrases downstream.
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for ch in input.chars() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(ch);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .map(|v| v.strip_suffix('"').unwrap_or(v))
+        .unwrap_or(value)
+}
+
+fn quote(value: &str) -> String {
+    if value.contains(char::is_whitespace) {
+        format!("\"{}\"", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// A span back from when the query runs, as in `after:12h`. Saved views
+/// keep the span itself, so "last 7 days" never goes stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ago {
+    Hours(u32),
+    Days(u32),
+    Weeks(u32),
+}
+
+impl Ago {
+    /// The instant this span reaches back to from now. A span reaching
+    /// past the earliest representable time (only possible for a saved
+    /// view edited by hand; the parser rejects them) means all of history.
+    pub fn resolve(&self) -> DateTime<Utc> {
+        self.checked_resolve().unwrap_or(DateTime::<Utc>::MIN_UTC)
+    }
+
+    /// [`Ago::resolve`], or `None` if the span is out of range
+    pub fn checked_resolve(&self) -> Option<DateTime<Utc>> {
+        let span = match *self {
+            Ago::Hours(n) => Duration::try_hours(i64::from(n)),
+            Ago::Days(n) => Duration::try_days(i64::from(n)),
+            Ago::Weeks(n) => Duration::try_weeks(i64::from(n)),
+        }?;
+        Utc::now().checked_sub_signed(span)
+    }
+}
+
+impl fmt::Display for Ago {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Ago::Hours(n) => write!(f, "{}h", n),
+            Ago::Days(n) => write!(f, "{}d", n),
+            Ago::Weeks(n) => write!(f, "{}w", n),
+        }
+    }
+}
+
+/// A point in time for `before:`/`after:`
+enum When {
+    /// Relative to when the query runs
+    Ago(Ago),
+    At(DateTime<Utc>),
+}
+
+/// Accepts `7d`, `2w`, `12h`, `2026-01-31` or an RFC 3339 timestamp
+fn parse_when(value: &str) -> Result<When> {
+    let invalid = || {
+        DitoxError::Other(format!(
+            "Invalid date '{}' (expected e.g. 7d, 2w, 12h or 2026-01-31)",
+            value
+        ))
+    };
+
+    if let Some(unit) = value
+        .chars()
+        .last()
+        .filter(|c| matches!(c, 'd' | 'w' | 'h'))
+    {
+        if let Ok(n) = value[..value.len() - 1].parse::<u32>() {
+            let ago = match unit {
+                'd' => Ago::Days(n),
+                'w' => Ago::Weeks(n),
+                _ => Ago::Hours(n),
+            };
+            // e.g. 99999999d: before any date chrono can represent
+            ago.checked_resolve().ok_or_else(invalid)?;
+            return Ok(When::Ago(ago));
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let midnight = date.and_hms_opt(0, 0, 0).ok_or_else(invalid)?;
+        return Ok(When::At(midnight.and_utc()));
+    }
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| When::At(dt.with_timezone(&Utc)))
+        .map_err(|_| invalid())
+}
+
+/// Where a full-text search matched inside an entry, as reported by FTS5
+/// `snippet()` and `highlight()` on the content column.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SearchHit {
+    /// Excerpt of the content around the best match, with `…` marking
+    /// elided text
+    pub snippet: String,
+    /// Matched `(start, end)` char ranges within `snippet` (end exclusive)
+    pub ranges: Vec<(usize, usize)>,
+    /// Char offset of the first match in the full content, if the match
+    /// is in the content rather than only in the notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+/// Marker FTS5 is asked to put before a matched token
+pub(crate) const HIT_OPEN: char = '\u{1}';
+/// Marker FTS5 is asked to put after a matched token
+pub(crate) const HIT_CLOSE: char = '\u{2}';
+
+impl SearchHit {
+    /// Build a hit from FTS5 `snippet()` and `highlight()` output that used
+    /// `HIT_OPEN`/`HIT_CLOSE` as markers.
+    pub(crate) fn from_marked(snippet: &str, highlighted: &str) -> Self {
+        let (snippet, ranges) = strip_markers(snippet);
+        let offset = highlighted
+            .split(HIT_OPEN)
+            .next()
+            .filter(|head| head.len() < highlighted.len())
+            .map(|head| head.chars().filter(|&c| c != HIT_CLOSE).count());
+        Self {
+            snippet,
+            ranges,
+            offset,
+        }
+    }
+}
+
+/// Remove match markers, returning the plain text and the marked char ranges
+fn strip_markers(marked: &str) -> (String, Vec<(usize, usize)>) {
+    let mut text = String::with_capacity(marked.len());
+    let mut ranges = Vec::new();
+    let mut start = None;
+    let mut pos = 0;
+    for ch in marked.chars() {
+        match ch {
+            HIT_OPEN => start = Some(pos),
+            HIT_CLOSE => {
+                if let Some(s) = start.take() {
+                    ranges.push((s, pos));
+                }
+            }
+            _ => {
+                text.push(ch);
+                pos += 1;
+            }
+        }
+    }
+    (text, ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_text() {
+        let query = Query::parse("hello world").unwrap();
+        assert_eq!(query.text.as_deref(), Some("hello world"));
+        assert_eq!(query.filters(), Query::default());
+    }
+
+    #[test]
+    fn test_parse_operators() {
+        let query = Query::parse(r#"tag:work is:fav kind:text "exact phrase" -draft"#).unwrap();
+        assert_eq!(query.tags, vec!["work"]);
+        assert!(query.favorites_only);
+        assert_eq!(query.entry_type, Some(EntryType::Text));
+        assert_eq!(query.text.as_deref(), Some("\"exact phrase\""));
+        assert_eq!(query.plain_text().as_deref(), Some("exact phrase"));
+        assert_eq!(query.exclude_text, vec!["draft"]);
+    }
+
+    #[test]
+    fn test_parse_negation() {
+        let query = Query::parse(r#"-tag:"old stuff" -is:fav -kind:image -is:primary"#).unwrap();
+        assert_eq!(query.exclude_tags, vec!["old stuff"]);
+        assert!(query.exclude_favorites);
+        assert!(query.exclude_primary);
+        assert_eq!(query.entry_type, Some(EntryType::Text));
+        assert_eq!(query.text, None);
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        let query = Query::parse("after:2w before:2026-01-31").unwrap();
+        assert_eq!(query.after_ago, Some(Ago::Weeks(2)));
+        assert_eq!(
+            query.before.unwrap().to_rfc3339(),
+            "2026-01-31T00:00:00+00:00"
+        );
+
+        // Relative bounds stay relative until the query runs
+        let query = Query::parse("before:12h -before:3d").unwrap();
+        assert_eq!(query.before_ago, Some(Ago::Hours(12)));
+        assert_eq!(query.after_ago, Some(Ago::Days(3)));
+
+        assert!(Query::parse("after:yesterday").is_err());
+        // Spans reaching past the earliest representable date
+        assert!(Query::parse("after:99999999d").is_err());
+        assert!(Query::parse("after:4294967295w").is_err());
+        assert!(Query::parse("before:4294967295h").is_err());
+        assert_eq!(
+            Query::parse_or_text("after:99999999d").text.as_deref(),
+            Some("after:99999999d")
+        );
+        assert_eq!(Ago::Weeks(u32::MAX).resolve(), DateTime::<Utc>::MIN_UTC);
+        assert!(Query::parse("kind:video").is_err());
+        assert!(Query::parse("tag:").is_err());
+    }
+
+    #[test]
+    fn test_unknown_key_is_text() {
+        let query = Query::parse("http://example.com").unwrap();
+        assert_eq!(query.text.as_deref(), Some("http://example.com"));
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        let input =
+            r#"foo tag:"two words" -tag:old is:fav is:primary kind:image after:7d before:12h"#;
+        let query = Query::parse(input).unwrap();
+        assert_eq!(Query::parse(&query.to_string()).unwrap(), query);
+    }
+}
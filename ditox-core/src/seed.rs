@@ -0,0 +1,241 @@
+//! Synthetic clipboard history
+//!
+//! Fills a database with realistic-looking clips (links, commands, code,
+//! JSON, notes), small generated PNG images and tags, spread over a time
+//! window. Used by `ditox dev seed` and demo mode for screenshots, theme
+//! work, benchmarking and reproducing pagination bugs. The same seed value
+//! always produces the same history.
+
+use crate::db::Database;
+use crate::entry::Entry;
+use crate::error::{DitoxError, Result};
+use chrono::{Duration, Utc};
+use std::io::Cursor;
+
+/// What to generate
+#[derive(Debug, Clone)]
+pub struct SeedOptions {
+    /// Number of text clips
+    pub clips: usize,
+    /// Number of image clips
+    pub images: usize,
+    /// Number of distinct tags to spread over the clips
+    pub tags: usize,
+    /// Clips are dated within this many days back from now
+    pub days: u32,
+    /// Random seed
+    pub seed: u64,
+}
+
+impl Default for SeedOptions {
+    fn default() -> Self {
+        Self {
+            clips: 200,
+            images: 10,
+            tags: 8,
+            days: 90,
+            seed: 1,
+        }
+    }
+}
+
+/// What was inserted
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SeedReport {
+    pub clips: usize,
+    pub images: usize,
+    pub tags: usize,
+}
+
+const WORDS: &[&str] = &[
+    "alpha", "build", "cache", "deploy", "error", "feature", "gateway", "handler", "index", "join",
+    "kernel", "latency", "merge", "network", "output", "parser", "query", "release", "server",
+    "token", "update", "vector", "worker", "yaml", "zone",
+];
+const HOSTS: &[&str] = &[
+    "github.com",
+    "docs.rs",
+    "crates.io",
+    "news.ycombinator.com",
+    "en.wikipedia.org",
+    "example.com",
+];
+const COMMANDS: &[&str] = &[
+    "cargo test --workspace",
+    "git rebase -i HEAD~3",
+    "kubectl get pods -n",
+    "docker compose up -d",
+    "rg --hidden -g '!target'",
+    "ssh -L 8080:localhost:80",
+    "journalctl --user -fu",
+];
+const TAG_ROOTS: &[&str] = &["work", "personal", "snippets", "links", "todo", "infra"];
+
+/// Small xorshift generator; good enough for fake data and dependency-free
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // Zero is a fixed point of xorshift
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+
+    fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+}
+
+fn sentence(rng: &mut Rng, words: usize) -> String {
+    (0..words)
+        .map(|_| rng.pick(WORDS))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One text clip; `n` keeps every clip unique so none are deduplicated
+fn text_clip(rng: &mut Rng, n: usize) -> String {
+    match rng.below(6) {
+        0 => format!(
+            "https://{}/{}/{}?id={}",
+            rng.pick(HOSTS),
+            rng.pick(WORDS),
+            rng.pick(WORDS),
+            n
+        ),
+        1 => format!("{} {}-{}", rng.pick(COMMANDS), rng.pick(WORDS), n),
+        2 => format!(
+            "fn {}_{}(input: &str) -> Result<()> {{\n    let {} = parse(input)?;\n    {}.{}()\n}}",
+            rng.pick(WORDS),
+            n,
+            rng.pick(WORDS),
+            rng.pick(WORDS),
+            rng.pick(WORDS)
+        ),
+        3 => format!(
+            "{{\"id\": {}, \"name\": \"{}\", \"status\": \"{}\"}}",
+            n,
+            rng.pick(WORDS),
+            rng.pick(WORDS)
+        ),
+        4 => format!("{}.{}{}@example.com", rng.pick(WORDS), rng.pick(WORDS), n),
+        _ => {
+            let words = 5 + rng.below(40);
+            format!("{} ({})", sentence(rng, words), n)
+        }
+    }
+}
+
+/// A small gradient PNG in random colors
+fn png(rng: &mut Rng) -> Result<Vec<u8>> {
+    let (width, height) = (64 + rng.below(64) as u32, 48 + rng.below(48) as u32);
+    let from = [
+        rng.below(256) as u8,
+        rng.below(256) as u8,
+        rng.below(256) as u8,
+    ];
+    let to = [
+        rng.below(256) as u8,
+        rng.below(256) as u8,
+        rng.below(256) as u8,
+    ];
+    let img = image::RgbImage::from_fn(width, height, |x, y| {
+        let t = (x + y) as f32 / (width + height) as f32;
+        image::Rgb(std::array::from_fn(|i| {
+            (from[i] as f32 + (to[i] as f32 - from[i] as f32) * t) as u8
+        }))
+    });
+    let mut bytes = Vec::new();
+    img.write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)
+        .map_err(|e| DitoxError::Other(format!("Failed to encode image: {}", e)))?;
+    Ok(bytes)
+}
+
+/// Tag names: the roots first, then sub-tags like `work/project-3`
+fn tag_names(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| match TAG_ROOTS.get(i) {
+            Some(root) => root.to_string(),
+            None => format!(
+                "{}/project-{}",
+                TAG_ROOTS[i % TAG_ROOTS.len()],
+                i / TAG_ROOTS.len()
+            ),
+        })
+        .collect()
+}
+
+/// Insert synthetic history into `db`. Image blobs go to the image store
+/// of the current data directory.
+pub fn seed(db: &Database, options: &SeedOptions) -> Result<SeedReport> {
+    let mut rng = Rng::new(options.seed);
+    let now = Utc::now();
+    let window_secs = i64::from(options.days.max(1)) * 24 * 3600;
+    let tags = tag_names(options.tags);
+    let mut report = SeedReport {
+        tags: tags.len(),
+        ..SeedReport::default()
+    };
+
+    let total = options.clips + options.images;
+    for n in 0..total {
+        let mut entry = if n < options.clips {
+            Entry::new_text(text_clip(&mut rng, n))
+        } else {
+            let bytes = png(&mut rng)?;
+            let hash = Entry::compute_hash(&bytes);
+            Database::store_image_blob(&hash, "png", &bytes)?;
+            Entry::new_image(hash, bytes.len(), "png".to_string())
+        };
+        // Draw everything before the duplicate check so a skipped clip
+        // doesn't shift the sequence for the ones after it
+        let age = Duration::seconds((rng.next() % window_secs as u64) as i64);
+        entry.created_at = now - age;
+        // Some clips were reused after they were captured
+        entry.last_used = if rng.chance(20) {
+            entry.created_at + Duration::seconds(rng.below(age.num_seconds() as usize) as i64)
+        } else {
+            entry.created_at
+        };
+        entry.favorite = rng.chance(5);
+        if rng.chance(10) {
+            entry.notes = Some(sentence(&mut rng, 4));
+        }
+        let clip_tags: Vec<&String> = if !tags.is_empty() && rng.chance(40) {
+            (0..1 + rng.below(2))
+                .map(|_| &tags[rng.below(tags.len())])
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        if db.exists_by_hash(&entry.hash)? {
+            continue;
+        }
+        db.insert(&entry)?;
+        for tag in clip_tags {
+            db.add_tag(&entry.id, tag)?;
+        }
+        if n < options.clips {
+            report.clips += 1;
+        } else {
+            report.images += 1;
+        }
+    }
+
+    Ok(report)
+}
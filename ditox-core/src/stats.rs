@@ -2,6 +2,8 @@
 //!
 //! This module provides statistics computation and display for clipboard usage patterns.
 
+use crate::i18n::{t, tf};
+use crate::watcher::WatcherHealth;
 use serde::Serialize;
 
 /// Usage statistics for the clipboard manager
@@ -29,6 +31,29 @@ pub struct Stats {
     pub copies_month: usize,
     /// Total usage count across all entries
     pub total_usage: u64,
+    /// Clips captured on each of the last 30 days (UTC), oldest first
+    pub clips_per_day: Vec<DayCount>,
+    /// Most used tags, up to five
+    pub top_tags: Vec<TagCount>,
+    /// Average length of text clips, in characters
+    pub avg_text_length: f64,
+    /// Space taken by the full-text search index
+    pub fts_index_bytes: u64,
+}
+
+/// Clips captured on one day
+#[derive(Debug, Serialize)]
+pub struct DayCount {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub clips: usize,
+}
+
+/// A tag and how many entries carry it
+#[derive(Debug, Serialize)]
+pub struct TagCount {
+    pub name: String,
+    pub count: usize,
 }
 
 /// A top entry with its usage count
@@ -66,6 +91,11 @@ impl Stats {
                 self.image_count, image_pct
             ));
             output.push_str(&format!("  Favorites:       {}\n", self.favorites_count));
+            output.push_str(&tf(
+                "stats.avg_length",
+                &[("chars", &format!("{:.0}", self.avg_text_length))],
+            ));
+            output.push('\n');
         }
 
         output.push('\n');
@@ -80,6 +110,11 @@ impl Stats {
             "  Images:          {}\n",
             format_bytes(self.images_size_bytes)
         ));
+        output.push_str(&tf(
+            "stats.fts_index",
+            &[("size", &format_bytes(self.fts_index_bytes))],
+        ));
+        output.push('\n');
 
         output.push('\n');
 
@@ -110,12 +145,117 @@ impl Stats {
         ));
         output.push_str(&format!("  Total:           {} copies\n", self.total_usage));
 
+        let peak = self.clips_per_day.iter().max_by_key(|d| d.clips);
+        if let Some(peak) = peak.filter(|d| d.clips > 0) {
+            output.push('\n');
+            output.push_str(&t("stats.per_day"));
+            output.push('\n');
+            output.push_str(&format!("  {}\n", sparkline(&self.clips_per_day)));
+            output.push_str(&tf(
+                "stats.per_day_peak",
+                &[("count", &peak.clips), ("date", &peak.date)],
+            ));
+            output.push('\n');
+        }
+
+        if !self.top_tags.is_empty() {
+            output.push('\n');
+            output.push_str(&t("stats.top_tags"));
+            output.push('\n');
+            for tag in &self.top_tags {
+                output.push_str(&format!("  {:<16} {}\n", tag.name, tag.count));
+            }
+        }
+
+        output
+    }
+}
+
+impl Stats {
+    /// Prometheus text exposition of the storage gauges and, when a watcher
+    /// is running, its capture counters (reset when it restarts). Suitable
+    /// for node_exporter's textfile collector.
+    pub fn prometheus(&self, watcher: Option<&WatcherHealth>) -> String {
+        let mut output = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, u64)]| {
+            output.push_str(&format!("# HELP ditox_{} {}\n", name, help));
+            output.push_str(&format!("# TYPE ditox_{} {}\n", name, kind));
+            for (labels, value) in samples {
+                output.push_str(&format!("ditox_{}{} {}\n", name, labels, value));
+            }
+        };
+
+        metric(
+            "entries",
+            "gauge",
+            "Clipboard entries in the history.",
+            &[
+                ("{kind=\"text\"}", self.text_count as u64),
+                ("{kind=\"image\"}", self.image_count as u64),
+            ],
+        );
+        metric(
+            "favorites",
+            "gauge",
+            "Favorite entries.",
+            &[("", self.favorites_count as u64)],
+        );
+        metric(
+            "database_bytes",
+            "gauge",
+            "Size of the database file.",
+            &[("", self.db_size_bytes)],
+        );
+        metric(
+            "images_bytes",
+            "gauge",
+            "Size of the image store.",
+            &[("", self.images_size_bytes)],
+        );
+        metric(
+            "fts_index_bytes",
+            "gauge",
+            "Size of the full-text search index.",
+            &[("", self.fts_index_bytes)],
+        );
+        metric(
+            "watcher_up",
+            "gauge",
+            "Whether the clipboard watcher is running and healthy.",
+            &[("", watcher.is_some_and(WatcherHealth::is_healthy) as u64)],
+        );
+        if let Some(health) = watcher {
+            metric(
+                "clips_captured_total",
+                "counter",
+                "Clips captured by the watcher.",
+                &[("", health.captured)],
+            );
+            metric(
+                "duplicates_total",
+                "counter",
+                "Copies skipped because they were already in the history.",
+                &[("", health.duplicates)],
+            );
+            metric(
+                "clips_skipped_total",
+                "counter",
+                "Copies skipped by MIME hint or minimum length.",
+                &[("", health.skipped)],
+            );
+            metric(
+                "poll_errors_total",
+                "counter",
+                "Failed clipboard polls.",
+                &[("", health.poll_errors)],
+            );
+        }
         output
     }
 }
 
 /// Format bytes as human-readable string
-fn format_bytes(bytes: u64) -> String {
+pub fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -131,6 +271,15 @@ fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// One bar per day, scaled to the busiest day
+fn sparkline(days: &[DayCount]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = days.iter().map(|d| d.clips).max().unwrap_or(0).max(1);
+    days.iter()
+        .map(|d| BARS[d.clips * (BARS.len() - 1) / max])
+        .collect()
+}
+
 /// Truncate preview string for display
 fn truncate_preview(s: &str, max_len: usize) -> String {
     let s = s.replace('\n', " ").replace('\r', "");
@@ -0,0 +1,88 @@
+//! Restorable deletes
+//!
+//! [`delete_restorable`] snapshots a clip (with its tags, metadata,
+//! aliases, usage count and image blob) before deleting it, so the TUI can
+//! put it back with [`restore`] when the delete is undone.
+
+use crate::db::Database;
+use crate::entry::{Entry, EntryType};
+use crate::error::Result;
+use std::collections::BTreeMap;
+
+/// Everything needed to put a deleted clip back as it was
+#[derive(Debug, Clone)]
+pub struct DeletedEntry {
+    pub entry: Entry,
+    pub tags: Vec<String>,
+    pub meta: BTreeMap<String, String>,
+    pub aliases: Vec<String>,
+    pub usage_count: i64,
+    /// Image bytes, read before the blob was pruned
+    pub blob: Option<Vec<u8>>,
+}
+
+/// Delete an entry, returning a snapshot to [`restore`] it from, or None if
+/// there was no such entry
+pub fn delete_restorable(db: &mut Database, id: &str) -> Result<Option<DeletedEntry>> {
+    let Some(entry) = db.get_by_id(id)? else {
+        return Ok(None);
+    };
+    let blob = match (&entry.entry_type, &entry.image_extension) {
+        (EntryType::Image, ext) => {
+            let path = Database::image_path(&entry.hash, ext.as_deref().unwrap_or("png"))?;
+            std::fs::read(path).ok()
+        }
+        _ => None,
+    };
+    let deleted = DeletedEntry {
+        tags: db.get_tags(id)?,
+        meta: db.get_all_meta(id)?,
+        aliases: db
+            .get_aliases()?
+            .into_iter()
+            .filter(|(_, entry_id)| entry_id == id)
+            .map(|(name, _)| name)
+            .collect(),
+        usage_count: db.usage_count(id)?,
+        blob,
+        entry,
+    };
+    db.delete(id)?;
+    Ok(Some(deleted))
+}
+
+/// Put a deleted entry back. Returns false if it can't be: the same content
+/// has been captured again since, or an image's bytes were not kept.
+/// Aliases reassigned in the meantime are left pointing at their new clip.
+pub fn restore(db: &Database, deleted: &DeletedEntry) -> Result<bool> {
+    let entry = &deleted.entry;
+    if db.exists_by_hash(&entry.hash)? {
+        return Ok(false);
+    }
+    if entry.entry_type == EntryType::Image {
+        let Some(bytes) = &deleted.blob else {
+            return Ok(false);
+        };
+        let ext = entry.image_extension.as_deref().unwrap_or("png");
+        Database::store_image_blob(&entry.hash, ext, bytes)?;
+    }
+
+    db.in_transaction(|db| {
+        if !db.insert(entry)? {
+            return Ok(false);
+        }
+        db.set_usage_count(&entry.id, deleted.usage_count)?;
+        for tag in &deleted.tags {
+            db.add_tag(&entry.id, tag)?;
+        }
+        for (key, value) in &deleted.meta {
+            db.set_meta(&entry.id, key, value)?;
+        }
+        for name in &deleted.aliases {
+            if db.get_alias(name)?.is_none() {
+                db.set_alias(name, &entry.id)?;
+            }
+        }
+        Ok(true)
+    })
+}
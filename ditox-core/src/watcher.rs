@@ -1,22 +1,162 @@
 use crate::clipboard::Clipboard;
 use crate::config::Config;
-use crate::db::Database;
+use crate::db::{Database, SELECTION_META_KEY};
 use crate::entry::Entry;
-use crate::error::Result;
+use crate::error::{DitoxError, Result};
+use crate::hooks::HookEvent;
+use crate::incognito::{self, IncognitoBuffer, IncognitoState};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
-use std::time::Duration;
-use tracing::{debug, error, info};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, info, warn};
+
+/// Consecutive poll failures after which the watcher resets its clipboard
+/// state, as if it had just started. There is no long-lived clipboard
+/// handle to rebuild: every read spawns wl-paste (or opens a new arboard
+/// connection), so a restarted compositor is picked up by the next poll.
+const RESET_AFTER_ERRORS: u32 = 5;
+
+/// Upper bound for the delay between polls while the clipboard keeps failing
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// How often the health file is refreshed while everything is fine, so
+/// readers can tell a hung watcher from a healthy one
+const HEALTH_REFRESH: Duration = Duration::from_secs(30);
+
+/// How often the watcher checks whether an automatic backup is due
+const BACKUP_CHECK_EVERY: Duration = Duration::from_secs(15 * 60);
 
 pub struct Watcher {
     db: Database,
     config: Config,
     last_hash: Option<String>,
+    /// New clipboard contents waiting out `capture.coalesce_ms`, and when
+    /// they were first seen
+    pending_clip: Option<(String, Instant)>,
+    /// Primary selection last handled (captured or skipped)
+    last_primary_hash: Option<String>,
+    /// Primary selection seen on the previous poll; it is only captured
+    /// once it stops changing, so a selection still being dragged out
+    /// doesn't leave a trail of partial entries
+    pending_primary_hash: Option<String>,
+    health: WatcherHealth,
+    /// Clips captured while incognito; never written to disk unless committed
+    incognito: IncognitoBuffer,
+    incognito_active: bool,
+    /// Called with each clip written to the database
+    on_capture: Option<CaptureHook>,
+    /// When the scheduled prune last ran (or the watcher was created)
+    last_prune: Instant,
+    /// When a due backup was last checked for; None until the first
+    /// check, so a due backup is written at startup
+    last_backup_check: Option<Instant>,
+}
+
+/// See [`Watcher::on_capture`]
+type CaptureHook = Box<dyn FnMut(&Database, &Entry) -> Result<()> + Send>;
+
+/// Capture health as reported by a running `ditox watch`, persisted next to
+/// the PID file so `ditox status` and the TUI can show it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatcherHealth {
+    /// Last time a clipboard poll succeeded
+    pub last_ok: Option<DateTime<Utc>>,
+    /// Polls that have failed in a row (0 when healthy)
+    pub consecutive_errors: u32,
+    /// Message of the most recent failure
+    pub last_error: Option<String>,
+    /// Times the watcher recovered after failing polls
+    pub recoveries: u32,
+    /// When this report was written
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Whether capture currently goes to memory only
+    #[serde(default)]
+    pub incognito: bool,
+    /// Clips held in memory by incognito mode
+    #[serde(default)]
+    pub incognito_clips: usize,
+    /// Clips written to the database since the watcher started
+    #[serde(default)]
+    pub captured: u64,
+    /// New clipboard contents that were already in the history
+    #[serde(default)]
+    pub duplicates: u64,
+    /// Clips not captured because of `capture.ignore_mime_types` or
+    /// `capture.min_text_length`
+    #[serde(default)]
+    pub skipped: u64,
+    /// Failed polls since the watcher started
+    #[serde(default)]
+    pub poll_errors: u64,
+    /// When retention and blob cleanup last ran on schedule
+    #[serde(default)]
+    pub last_prune: Option<DateTime<Utc>>,
+    /// Entries evicted by the last scheduled prune
+    #[serde(default)]
+    pub last_prune_removed: usize,
+    /// Last automatic backup written (`backup.auto`)
+    #[serde(default)]
+    pub last_backup: Option<DateTime<Utc>>,
+    /// Last time a clip was written to the database
+    #[serde(default)]
+    pub last_capture: Option<DateTime<Utc>>,
+    /// Clipboard backend the watcher reads from
+    #[serde(default)]
+    pub backend: String,
+}
+
+impl WatcherHealth {
+    /// True if the last poll succeeded and the report isn't stale
+    pub fn is_healthy(&self) -> bool {
+        let fresh = self.updated_at.is_some_and(|t| {
+            Utc::now() - t < chrono::Duration::from_std(HEALTH_REFRESH * 3).unwrap_or_default()
+        });
+        fresh && self.consecutive_errors == 0
+    }
+}
+
+/// Get the path to the watcher health file
+pub fn get_health_file_path() -> Result<PathBuf> {
+    Ok(Database::get_state_dir()?.join("watcher.health"))
+}
+
+/// Read the health report of the running watcher, if there is one
+pub fn read_watcher_health() -> Option<WatcherHealth> {
+    if !is_watcher_running() {
+        return None;
+    }
+    let content = fs::read_to_string(get_health_file_path().ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Watcher state as shown in status bars
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatcherStatus {
+    Stopped,
+    Running,
+    /// Running, but its clipboard polls are failing
+    Failing,
+}
+
+/// Current watcher status. Reads the PID and health files, so UIs should
+/// cache it rather than call this on every frame.
+pub fn watcher_status() -> WatcherStatus {
+    if !is_watcher_running() {
+        return WatcherStatus::Stopped;
+    }
+    match read_watcher_health() {
+        Some(health) if !health.is_healthy() => WatcherStatus::Failing,
+        _ => WatcherStatus::Running,
+    }
 }
 
 /// Get the path to the watcher PID file
 pub fn get_pid_file_path() -> Result<PathBuf> {
-    Ok(Database::get_data_dir()?.join("watcher.pid"))
+    Ok(Database::get_state_dir()?.join("watcher.pid"))
 }
 
 /// Check if the watcher daemon is currently running
@@ -42,7 +182,12 @@ pub fn is_watcher_running() -> bool {
     };
 
     // Check if process is running - platform specific
-    is_process_running_by_pid(pid)
+    if is_process_running_by_pid(pid) {
+        return true;
+    }
+    // Left behind by a watcher that was killed without cleaning up
+    remove_pid_file();
+    false
 }
 
 /// Check if a process with the given PID is running
@@ -61,19 +206,230 @@ fn is_process_running_by_pid(pid: u32) -> bool {
     sys.process(sysinfo::Pid::from_u32(pid)).is_some()
 }
 
-/// Write the current process PID to the PID file
+/// Tell systemd the watcher is up (`Type=notify` units). A no-op when not
+/// started by systemd; failures are only logged.
+#[cfg(target_os = "linux")]
+fn notify_ready() {
+    use std::os::linux::net::SocketAddrExt;
+    use std::os::unix::net::{SocketAddr, UnixDatagram};
+
+    let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+        return;
+    };
+    let path = path.to_string_lossy().into_owned();
+    // A leading '@' names a socket in the abstract namespace
+    let addr = match path.strip_prefix('@') {
+        Some(name) => SocketAddr::from_abstract_name(name.as_bytes()),
+        None => SocketAddr::from_pathname(&path),
+    };
+    let sent = addr.and_then(|addr| {
+        let socket = UnixDatagram::unbound()?;
+        socket.send_to_addr(b"READY=1", &addr)
+    });
+    match sent {
+        Ok(_) => debug!("Notified systemd that the watcher is ready"),
+        Err(e) => warn!("Failed to notify systemd ({}): {}", path, e),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn notify_ready() {}
+
+fn already_running(pid: &str) -> DitoxError {
+    DitoxError::Locked(format!(
+        "ditox watch is already running (PID {}); use --replace to take over",
+        pid.trim()
+    ))
+}
+
+/// Write the current process PID to the PID file, refusing to start a
+/// second watcher. On Unix the returned file holds an exclusive lock for as
+/// long as it stays open, so two watchers starting at once can't both win.
+#[cfg(unix)]
+fn write_pid_file() -> Result<fs::File> {
+    use std::io::Write;
+    use std::os::unix::io::AsRawFd;
+
+    let pid_path = get_pid_file_path()?;
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&pid_path)?;
+    // SAFETY: flock on a descriptor we own
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+        return Err(already_running(
+            &fs::read_to_string(&pid_path).unwrap_or_default(),
+        ));
+    }
+    file.set_len(0)?;
+    write!(file, "{}", std::process::id())?;
+    Ok(file)
+}
+
+#[cfg(not(unix))]
 fn write_pid_file() -> Result<()> {
     let pid_path = get_pid_file_path()?;
-    let pid = std::process::id();
-    fs::write(&pid_path, pid.to_string())?;
+    if is_watcher_running() {
+        return Err(already_running(
+            &fs::read_to_string(&pid_path).unwrap_or_default(),
+        ));
+    }
+    fs::write(&pid_path, std::process::id().to_string())?;
     Ok(())
 }
 
-/// Remove the PID file
+/// Stop a running watcher (e.g. a hung one) so this process can take
+/// over: ask it to exit, and kill it if it hasn't within a few seconds.
+/// Returns false if none was running.
+pub fn stop_running_watcher() -> Result<bool> {
+    const GRACE: Duration = Duration::from_secs(5);
+
+    if !is_watcher_running() {
+        return Ok(false);
+    }
+    let pid: u32 = fs::read_to_string(get_pid_file_path()?)?
+        .trim()
+        .parse()
+        .map_err(|_| DitoxError::Other("Invalid watcher PID file".to_string()))?;
+    info!("Stopping running watcher (PID {})", pid);
+    terminate_process(pid, false);
+    let deadline = Instant::now() + GRACE;
+    while is_process_running_by_pid(pid) {
+        if Instant::now() >= deadline {
+            warn!("Watcher (PID {}) did not exit, killing it", pid);
+            terminate_process(pid, true);
+            std::thread::sleep(Duration::from_millis(200));
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Ok(true)
+}
+
+/// Processes capturing the clipboard into the history, as (PID, command):
+/// `ditox watch` and `ditox-gui`, which runs its own watcher. With more
+/// than one, clips are stored twice and their writes contend for the
+/// database. Empty where processes can't be listed (macOS).
+pub fn capturing_processes() -> Vec<(u32, String)> {
+    let mut found: Vec<(u32, String)> = process_commands()
+        .into_iter()
+        .filter_map(|(pid, args)| Some((pid, capturing_command(&args)?.to_string())))
+        .collect();
+    found.sort();
+    found
+}
+
+/// What a command line captures as, if it does
+fn capturing_command(args: &[String]) -> Option<&'static str> {
+    let program = Path::new(args.first()?).file_stem()?.to_str()?;
+    match program {
+        "ditox" if args[1..].iter().any(|arg| arg == "watch") => Some("ditox watch"),
+        "ditox-gui" => Some("ditox-gui"),
+        _ => None,
+    }
+}
+
+/// Every process's PID and command line
+#[cfg(target_os = "linux")]
+fn process_commands() -> Vec<(u32, Vec<String>)> {
+    let Ok(dir) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    dir.flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let cmdline = fs::read(entry.path().join("cmdline")).ok()?;
+            let args = cmdline
+                .split(|&b| b == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                .collect();
+            Some((pid, args))
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+fn process_commands() -> Vec<(u32, Vec<String>)> {
+    use sysinfo::{ProcessRefreshKind, System, UpdateKind};
+
+    let mut sys = System::new();
+    sys.refresh_processes_specifics(
+        sysinfo::ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing().with_cmd(UpdateKind::Always),
+    );
+    sys.processes()
+        .iter()
+        .map(|(pid, process)| {
+            let args = process
+                .cmd()
+                .iter()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect();
+            (pid.as_u32(), args)
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn process_commands() -> Vec<(u32, Vec<String>)> {
+    Vec::new()
+}
+
+#[cfg(unix)]
+fn terminate_process(pid: u32, force: bool) {
+    let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+    // SAFETY: sending a signal has no memory-safety requirements
+    unsafe {
+        libc::kill(pid as i32, signal);
+    }
+}
+
+#[cfg(windows)]
+fn terminate_process(pid: u32, _force: bool) {
+    use sysinfo::System;
+
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    if let Some(process) = sys.process(sysinfo::Pid::from_u32(pid)) {
+        process.kill();
+    }
+}
+
+/// Set by SIGINT/SIGTERM; the watch loop stops at its next check
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(unix)]
+extern "C" fn request_stop(_signal: libc::c_int) {
+    STOP_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Stop the watch loop on SIGINT/SIGTERM instead of dying mid-write, so the
+/// PID and health files get removed
+#[cfg(unix)]
+fn install_stop_handlers() {
+    let handler = request_stop as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    // SAFETY: the handler only stores to an atomic, which is signal-safe
+    unsafe {
+        libc::signal(libc::SIGINT, handler);
+        libc::signal(libc::SIGTERM, handler);
+    }
+}
+
+#[cfg(not(unix))]
+fn install_stop_handlers() {}
+
+/// Remove the PID and health files
 fn remove_pid_file() {
     if let Ok(pid_path) = get_pid_file_path() {
         let _ = fs::remove_file(pid_path);
     }
+    if let Ok(health_path) = get_health_file_path() {
+        let _ = fs::remove_file(health_path);
+    }
 }
 
 impl Watcher {
@@ -82,9 +438,30 @@ impl Watcher {
             db,
             config,
             last_hash: None,
+            pending_clip: None,
+            last_primary_hash: None,
+            pending_primary_hash: None,
+            health: WatcherHealth {
+                backend: Clipboard::BACKEND.to_string(),
+                ..Default::default()
+            },
+            incognito: IncognitoBuffer::default(),
+            incognito_active: false,
+            on_capture: None,
+            last_prune: Instant::now(),
+            last_backup_check: None,
         }
     }
 
+    /// Call `hook` with every clip written to the database. If it fails,
+    /// the watcher stops (e.g. `ditox watch --print | head`).
+    pub fn on_capture(
+        &mut self,
+        hook: impl FnMut(&Database, &Entry) -> Result<()> + Send + 'static,
+    ) {
+        self.on_capture = Some(Box::new(hook));
+    }
+
     /// Get the configured poll interval
     pub fn poll_interval_ms(&self) -> u64 {
         self.config.general.poll_interval_ms
@@ -93,18 +470,27 @@ impl Watcher {
     /// Poll clipboard once and return true if a new entry was captured
     /// This is designed for use in async contexts (GUI, etc.)
     pub fn poll_once(&mut self) -> Result<bool> {
+        self.sync_incognito();
+        self.run_scheduled_maintenance();
         self.poll_internal()
     }
 
-    /// Main loop - runs forever, polling clipboard
+    /// True while clips are captured to memory only
+    pub fn is_incognito(&self) -> bool {
+        self.incognito_active
+    }
+
+    /// Main loop - polls the clipboard until SIGINT/SIGTERM
     pub fn run(&mut self) -> Result<()> {
         info!(
             "Starting clipboard watcher (poll interval: {}ms)",
             self.config.general.poll_interval_ms
         );
 
-        // Write PID file
-        write_pid_file()?;
+        // Write PID file; the lock (Unix) is held until this returns
+        fs::create_dir_all(Database::get_state_dir()?)?;
+        let _pid_lock = write_pid_file()?;
+        install_stop_handlers();
 
         // Set up cleanup on exit
         let result = self.run_loop();
@@ -115,16 +501,245 @@ impl Watcher {
         result
     }
 
+    /// Poll until a stop is requested. A failing clipboard backend (e.g. the compositor
+    /// restarting) must not stop capture: failures back off exponentially,
+    /// and after `RESET_AFTER_ERRORS` in a row the clipboard state is
+    /// re-primed from scratch. Panics inside a poll are treated as failures.
     fn run_loop(&mut self) -> Result<()> {
         // Initialize last_hash with current clipboard content
         self.initialize_hash();
+        self.health.last_ok = Some(Utc::now());
+        self.write_health();
+        notify_ready();
+        let mut last_report = Instant::now();
 
-        loop {
-            if let Err(e) = self.poll_internal() {
-                error!("Error polling clipboard: {}", e);
+        let interval = Duration::from_millis(self.config.general.poll_interval_ms);
+        while !STOP_REQUESTED.load(Ordering::SeqCst) {
+            self.sync_incognito();
+            self.run_scheduled_maintenance();
+
+            let outcome = panic::catch_unwind(AssertUnwindSafe(|| self.poll_internal()))
+                .unwrap_or_else(|payload| {
+                    let msg = payload
+                        .downcast_ref::<&str>()
+                        .map(|s| s.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "unknown panic".to_string());
+                    Err(crate::error::DitoxError::Other(format!(
+                        "clipboard poll panicked: {}",
+                        msg
+                    )))
+                });
+
+            let delay = match outcome {
+                Ok(_) => {
+                    if self.health.consecutive_errors > 0 {
+                        info!(
+                            "Clipboard capture recovered after {} failed polls",
+                            self.health.consecutive_errors
+                        );
+                        self.health.consecutive_errors = 0;
+                        self.health.recoveries += 1;
+                        self.health.last_ok = Some(Utc::now());
+                        self.write_health();
+                        last_report = Instant::now();
+                    } else if last_report.elapsed() >= HEALTH_REFRESH {
+                        self.health.last_ok = Some(Utc::now());
+                        self.write_health();
+                        last_report = Instant::now();
+                    }
+                    interval
+                }
+                Err(e) => {
+                    self.health.consecutive_errors += 1;
+                    self.health.poll_errors += 1;
+                    self.health.last_error = Some(e.to_string());
+                    let errors = self.health.consecutive_errors;
+                    if errors == 1 {
+                        error!("Error polling clipboard: {}", e);
+                    } else {
+                        debug!("Error polling clipboard ({} in a row): {}", errors, e);
+                    }
+                    if errors.is_multiple_of(RESET_AFTER_ERRORS) {
+                        warn!(
+                            "Clipboard failed {} polls in a row, resetting watcher state",
+                            errors
+                        );
+                        self.last_hash = None;
+                        self.last_primary_hash = None;
+                        self.initialize_hash();
+                    }
+                    self.write_health();
+                    last_report = Instant::now();
+                    Self::backoff(interval, errors)
+                }
+            };
+
+            Self::sleep_unless_stopped(delay);
+        }
+
+        if !self.incognito.is_empty() {
+            info!(
+                "Discarding {} incognito clips on shutdown",
+                self.incognito.discard()
+            );
+        }
+        info!("Clipboard watcher stopped");
+        Ok(())
+    }
+
+    /// Sleep for `delay`, waking early if a stop was requested
+    fn sleep_unless_stopped(delay: Duration) {
+        const STEP: Duration = Duration::from_millis(100);
+        let deadline = Instant::now() + delay;
+        while !STOP_REQUESTED.load(Ordering::SeqCst) {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
             }
+            std::thread::sleep(left.min(STEP));
+        }
+    }
+
+    /// Evict entries beyond the per-tag limits, then beyond `max_entries`
+    fn enforce_retention(&mut self) -> Result<usize> {
+        let general = &self.config.general;
+        let mut removed = self
+            .db
+            .enforce_retention(general.max_entries, &general.tag_limits)?;
+        let max_mb = self.config.storage.max_storage_mb;
+        if max_mb > 0 {
+            removed += self.db.enforce_storage_cap(max_mb * 1024 * 1024)?;
+        }
+        if removed > 0 {
+            debug!("Cleaned up {} old entries", removed);
+        }
+        Ok(removed)
+    }
 
-            std::thread::sleep(Duration::from_millis(self.config.general.poll_interval_ms));
+    /// Run the scheduled prune and backup if they are due. Called before
+    /// every poll by both `run` and `poll_once`.
+    fn run_scheduled_maintenance(&mut self) {
+        let prune_every = Duration::from_secs(self.config.storage.prune_interval_mins * 60);
+        if !prune_every.is_zero() && self.last_prune.elapsed() >= prune_every {
+            self.prune();
+            self.last_prune = Instant::now();
+        }
+        if self
+            .last_backup_check
+            .is_none_or(|t| t.elapsed() >= BACKUP_CHECK_EVERY)
+        {
+            self.scheduled_backup();
+            self.last_backup_check = Some(Instant::now());
+        }
+    }
+
+    /// Scheduled maintenance: re-apply retention (limits may have been
+    /// lowered, or entries imported) and remove image blobs left queued
+    fn prune(&mut self) {
+        match self.enforce_retention() {
+            Ok(removed) => {
+                self.db.drain_pending_blob_prunes();
+                info!("Scheduled prune removed {} entries", removed);
+                self.health.last_prune = Some(Utc::now());
+                self.health.last_prune_removed = removed;
+                self.write_health();
+            }
+            Err(e) => warn!("Scheduled prune failed: {}", e),
+        }
+    }
+
+    /// Write an automatic backup if one is due
+    fn scheduled_backup(&mut self) {
+        match crate::backup::run_scheduled(&self.db, &self.config.backup) {
+            Ok(Some(path)) => {
+                info!("Wrote automatic backup {}", path.display());
+                self.health.last_backup = Some(Utc::now());
+                self.write_health();
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Automatic backup failed: {}", e),
+        }
+    }
+
+    /// Follow incognito requests made through the state file: start
+    /// buffering in memory, or end it by committing or discarding the buffer
+    fn sync_incognito(&mut self) {
+        let state = incognito::read_state();
+        let active = match state {
+            IncognitoState::On => true,
+            IncognitoState::Off => false,
+            IncognitoState::EndCommit => {
+                match self
+                    .incognito
+                    .commit(&mut self.db, self.config.general.max_entries)
+                {
+                    Ok(n) => {
+                        info!("Incognito ended, committed {} clips", n);
+                        if let Err(e) = self.enforce_retention() {
+                            error!("Failed to apply retention limits: {}", e);
+                        }
+                    }
+                    Err(e) => error!("Failed to commit incognito clips: {}", e),
+                }
+                let _ = incognito::write_state(IncognitoState::Off);
+                false
+            }
+            IncognitoState::EndDiscard => {
+                let n = self.incognito.discard();
+                info!("Incognito ended, discarded {} clips", n);
+                let _ = incognito::write_state(IncognitoState::Off);
+                false
+            }
+        };
+        if active && !self.incognito_active {
+            info!("Incognito started, capturing to memory only");
+        } else if !active && self.incognito_active && !self.incognito.is_empty() {
+            // State file removed by hand: never write what was meant to stay private
+            let n = self.incognito.discard();
+            info!("Incognito ended, discarded {} clips", n);
+        }
+        if active != self.incognito_active {
+            self.incognito_active = active;
+            self.write_health();
+        }
+    }
+
+    /// Delay before the next poll after `errors` consecutive failures
+    fn backoff(interval: Duration, errors: u32) -> Duration {
+        interval
+            .saturating_mul(1 << errors.min(16))
+            .min(MAX_BACKOFF.max(interval))
+    }
+
+    fn note_capture(&mut self, entry: &Entry) {
+        self.health.captured += 1;
+        self.health.last_capture = Some(Utc::now());
+        crate::hooks::spawn(&self.config.hooks, HookEvent::Add, entry);
+        if let Some(hook) = &mut self.on_capture {
+            if let Err(e) = hook(&self.db, entry) {
+                warn!("Capture hook failed, stopping: {}", e);
+                STOP_REQUESTED.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Persist the current health report. Best effort: a failure to write
+    /// it must not affect capture.
+    fn write_health(&mut self) {
+        self.health.updated_at = Some(Utc::now());
+        self.health.incognito = self.incognito_active;
+        self.health.incognito_clips = self.incognito.len();
+        let Ok(path) = get_health_file_path() else {
+            return;
+        };
+        match serde_json::to_string(&self.health) {
+            Ok(json) => {
+                if let Err(e) = fs::write(&path, json) {
+                    debug!("Failed to write watcher health: {}", e);
+                }
+            }
+            Err(e) => debug!("Failed to serialize watcher health: {}", e),
         }
     }
 
@@ -133,6 +748,11 @@ impl Watcher {
     /// on the clipboard doesn't cause us to re-capture it on the very next
     /// poll (that was bug #4 in the hunt).
     pub fn initialize_hash(&mut self) {
+        if self.config.capture.primary_selection {
+            if let Ok(Some(text)) = Clipboard::get_primary_text() {
+                self.last_primary_hash = Some(Clipboard::hash(text.as_bytes()));
+            }
+        }
         if let Ok(Some(img)) = Clipboard::read_image() {
             self.last_hash = Some(img.hash);
             debug!("Initialized last_hash from existing clipboard image");
@@ -155,15 +775,40 @@ impl Watcher {
     ///    the DB row. Either both succeed or neither does.
     /// 5. Run LRU eviction; evicted image rows' blobs are pruned via the
     ///    persistent queue in `Database`.
+    ///
+    /// The primary selection, if enabled, is checked after the clipboard.
     fn poll_internal(&mut self) -> Result<bool> {
+        let captured = self.poll_clipboard()?;
+        if self.config.capture.primary_selection {
+            return Ok(self.poll_primary()? || captured);
+        }
+        Ok(captured)
+    }
+
+    fn poll_clipboard(&mut self) -> Result<bool> {
         // Image path has priority over text: browsers put both a URL (text)
         // and the rendered image on the clipboard when you "Copy image",
         // and we want the image.
         if let Some(img) = Clipboard::read_image()? {
-            if self.last_hash.as_ref() == Some(&img.hash) {
+            if self.last_hash.as_ref() == Some(&img.hash) || !self.settled(&img.hash) {
+                return Ok(false);
+            }
+            if self.is_marked_sensitive(Clipboard::list_types)? {
+                self.health.skipped += 1;
+                self.last_hash = Some(img.hash);
                 return Ok(false);
             }
 
+            if self.incognito_active {
+                self.last_hash = Some(img.hash.clone());
+                let captured = self.incognito.push_image(img);
+                if captured {
+                    debug!("Buffered incognito image ({} held)", self.incognito.len());
+                    self.write_health();
+                }
+                return Ok(captured);
+            }
+
             let captured = if !self.db.exists_by_hash(&img.hash)? {
                 // Store the blob ONLY after we've decided we'll keep it.
                 let (_path, _new) =
@@ -178,14 +823,13 @@ impl Watcher {
                     img.extension
                 );
 
-                let removed = self.db.cleanup_old(self.config.general.max_entries)?;
-                if removed > 0 {
-                    debug!("Cleaned up {} old entries", removed);
-                }
+                self.note_capture(&entry);
+                self.enforce_retention()?;
                 true
             } else {
                 // Already on record. Update last_hash below so we don't
                 // keep re-checking on every poll.
+                self.health.duplicates += 1;
                 false
             };
             self.last_hash = Some(img.hash);
@@ -195,21 +839,35 @@ impl Watcher {
         // Text path.
         if let Some(text) = Clipboard::get_text()? {
             let hash = Clipboard::hash(text.as_bytes());
-            if self.last_hash.as_ref() == Some(&hash) {
+            if self.last_hash.as_ref() == Some(&hash) || !self.settled(&hash) {
                 return Ok(false);
             }
+            if self.is_marked_sensitive(Clipboard::list_types)? || self.too_short(&text) {
+                self.health.skipped += 1;
+                self.last_hash = Some(hash);
+                return Ok(false);
+            }
+
+            if self.incognito_active {
+                self.last_hash = Some(hash);
+                let captured = self.incognito.push_text(text);
+                if captured {
+                    debug!("Buffered incognito text ({} held)", self.incognito.len());
+                    self.write_health();
+                }
+                return Ok(captured);
+            }
 
             let captured = if !self.db.exists_by_hash(&hash)? {
                 let entry = Entry::new_text(text);
                 self.db.insert(&entry)?;
                 info!("Captured text entry: {} bytes", entry.byte_size);
 
-                let removed = self.db.cleanup_old(self.config.general.max_entries)?;
-                if removed > 0 {
-                    debug!("Cleaned up {} old entries", removed);
-                }
+                self.note_capture(&entry);
+                self.enforce_retention()?;
                 true
             } else {
+                self.health.duplicates += 1;
                 false
             };
             self.last_hash = Some(hash);
@@ -218,4 +876,80 @@ impl Watcher {
 
         Ok(false)
     }
+
+    /// True once new clipboard contents `hash` have stayed unchanged for
+    /// `capture.coalesce_ms`; contents replaced sooner are never captured
+    fn settled(&mut self, hash: &str) -> bool {
+        let window = Duration::from_millis(self.config.capture.coalesce_ms);
+        if window.is_zero() {
+            return true;
+        }
+        match &self.pending_clip {
+            Some((pending, since)) if pending == hash => since.elapsed() >= window,
+            _ => {
+                self.pending_clip = Some((hash.to_string(), Instant::now()));
+                false
+            }
+        }
+    }
+
+    /// True if the clipboard (or selection) contents, offered as the types
+    /// `list_types` returns, carry one of the configured `ignore_mime_types`,
+    /// i.e. a password manager asked not to record them
+    fn is_marked_sensitive(&self, list_types: fn() -> Result<Vec<String>>) -> Result<bool> {
+        let ignored = &self.config.capture.ignore_mime_types;
+        if ignored.is_empty() {
+            return Ok(false);
+        }
+        let marked = list_types()?.iter().any(|mime| ignored.contains(mime));
+        if marked {
+            debug!("Skipping clipboard contents marked as sensitive");
+        }
+        Ok(marked)
+    }
+
+    /// True if `text` is shorter than `capture.min_text_length`, not
+    /// counting surrounding whitespace
+    fn too_short(&self, text: &str) -> bool {
+        text.trim().chars().count() < self.config.capture.min_text_length
+    }
+
+    /// Capture the primary selection once it has settled. Text already in
+    /// the history (e.g. selected and then copied) is not captured again,
+    /// and nothing is captured or buffered while incognito. Selections
+    /// marked sensitive or shorter than `capture.min_text_length` are
+    /// skipped, as for the clipboard.
+    fn poll_primary(&mut self) -> Result<bool> {
+        let Some(text) = Clipboard::get_primary_text()? else {
+            return Ok(false);
+        };
+        let hash = Clipboard::hash(text.as_bytes());
+        if self.last_primary_hash.as_ref() == Some(&hash) {
+            return Ok(false);
+        }
+        if self.pending_primary_hash.as_ref() != Some(&hash) {
+            self.pending_primary_hash = Some(hash);
+            return Ok(false);
+        }
+        self.last_primary_hash = Some(hash.clone());
+        if self.incognito_active {
+            return Ok(false);
+        }
+        if self.is_marked_sensitive(Clipboard::list_primary_types)? || self.too_short(&text) {
+            self.health.skipped += 1;
+            return Ok(false);
+        }
+        if self.db.exists_by_hash(&hash)? {
+            self.health.duplicates += 1;
+            return Ok(false);
+        }
+
+        let entry = Entry::new_text(text);
+        self.db.insert(&entry)?;
+        self.db.set_meta(&entry.id, SELECTION_META_KEY, "primary")?;
+        info!("Captured primary selection: {} bytes", entry.byte_size);
+        self.note_capture(&entry);
+        self.enforce_retention()?;
+        Ok(true)
+    }
 }
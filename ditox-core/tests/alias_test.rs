@@ -0,0 +1,58 @@
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use tempfile::TempDir;
+
+fn text_entry(id: &str, content: &str) -> Entry {
+    let mut entry = Entry::new_text(content.to_string());
+    entry.id = id.to_string();
+    entry
+}
+
+#[test]
+fn test_unique_id_prefixes() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+
+    db.insert(&text_entry("abcd1234-0000", "one")).unwrap();
+    db.insert(&text_entry("abcd9999-0000", "two")).unwrap();
+    db.insert(&text_entry("ffff0000-0000", "three")).unwrap();
+
+    let prefixes = db
+        .unique_id_prefixes(&["abcd1234-0000", "ffff0000-0000"], 2)
+        .unwrap();
+    assert_eq!(prefixes, vec!["abcd1", "ff"]);
+
+    assert_eq!(db.find_ids_by_prefix("abcd", 2).unwrap().len(), 2);
+    assert_eq!(
+        db.find_ids_by_prefix("abcd9", 2).unwrap(),
+        vec!["abcd9999-0000"]
+    );
+}
+
+#[test]
+fn test_alias_roundtrip_and_cascade() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+
+    let first = text_entry("id-first", "221B Baker Street");
+    let second = text_entry("id-second", "742 Evergreen Terrace");
+    db.insert(&first).unwrap();
+    db.insert(&second).unwrap();
+
+    assert!(
+        db.set_alias("42", &first.id).is_err(),
+        "numbers read as indexes"
+    );
+
+    db.set_alias("addr", &first.id).unwrap();
+    db.set_alias("addr", &second.id).unwrap();
+    assert_eq!(db.get_alias("addr").unwrap().as_deref(), Some("id-second"));
+    assert_eq!(db.get_aliases().unwrap().len(), 1);
+
+    // Aliases follow their entry on delete
+    db.delete(&second.id).unwrap();
+    assert_eq!(db.get_alias("addr").unwrap(), None);
+    assert!(!db.delete_alias("addr").unwrap());
+}
@@ -0,0 +1,39 @@
+use ditox_core::backup;
+use ditox_core::config::{AutoBackup, BackupConfig};
+use ditox_core::db::Database;
+use ditox_core::Entry;
+use tempfile::TempDir;
+
+#[test]
+fn test_scheduled_backups_and_rotation() {
+    let data = TempDir::new().unwrap();
+    assert!(Database::override_data_dir(data.path().to_path_buf()));
+    let db = Database::open_at(data.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    db.insert(&Entry::new_text("keep me".to_string())).unwrap();
+
+    let dir = data.path().join("snapshots");
+    let mut config = BackupConfig {
+        auto: AutoBackup::Off,
+        dir: Some(dir.clone()),
+        keep: 2,
+    };
+    assert!(backup::run_scheduled(&db, &config).unwrap().is_none());
+    assert!(!dir.exists());
+
+    config.auto = AutoBackup::Daily;
+    let written = backup::run_scheduled(&db, &config).unwrap().unwrap();
+    assert!(written.starts_with(&dir));
+    // The newest is less than a day old
+    assert!(backup::run_scheduled(&db, &config).unwrap().is_none());
+
+    for stamp in ["20200101-000000", "20200102-000000", "20200103-000000"] {
+        std::fs::write(dir.join(format!("auto-{}.tar.gz", stamp)), b"old").unwrap();
+    }
+    std::fs::write(dir.join("pre-restore-20200101-000000.tar.gz"), b"safety").unwrap();
+    assert_eq!(backup::rotate(&dir, config.keep).unwrap(), 2);
+    let left: Vec<_> = backup::auto_backups(&dir).unwrap();
+    assert_eq!(left.len(), 2);
+    assert_eq!(left[1], written);
+    assert!(dir.join("pre-restore-20200101-000000.tar.gz").exists());
+}
@@ -0,0 +1,13 @@
+use ditox_core::autostart;
+use std::path::Path;
+
+#[test]
+fn test_service_files_run_watch() {
+    let exe = Path::new("/opt/a&b/ditox");
+    let unit = autostart::systemd_unit(exe);
+    assert!(unit.contains("ExecStart=/opt/a&b/ditox watch\n"));
+
+    let plist = autostart::launch_agent(exe);
+    assert!(plist.contains("<string>/opt/a&amp;b/ditox</string>\n        <string>watch</string>"));
+    assert!(plist.contains(&format!("<string>{}</string>", autostart::LAUNCHD_LABEL)));
+}
@@ -0,0 +1,100 @@
+use ditox_core::backup;
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use ditox_core::DitoxError;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// An archive holding only a database made by `build`
+fn crafted_archive(dir: &Path, name: &str, build: impl FnOnce(&Path)) -> std::path::PathBuf {
+    let db_path = dir.join(format!("{}.db", name));
+    let db = Database::open_at(db_path.clone()).unwrap();
+    db.init_schema().unwrap();
+    drop(db);
+    build(&db_path);
+
+    let archive = dir.join(format!("{}.tar.gz", name));
+    let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+        std::fs::File::create(&archive).unwrap(),
+        flate2::Compression::default(),
+    ));
+    tar.append_path_with_name(&db_path, "ditox.db").unwrap();
+    tar.into_inner().unwrap().finish().unwrap();
+    archive
+}
+
+#[test]
+fn test_backup_round_trip_with_images_and_safety_copy() {
+    let data = TempDir::new().unwrap();
+    assert!(Database::override_data_dir(data.path().to_path_buf()));
+    let mut db = Database::open_at(data.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+
+    let bytes = b"not really a png".to_vec();
+    let hash = Entry::compute_hash(&bytes);
+    Database::store_image_blob(&hash, "png", &bytes).unwrap();
+    db.insert(&Entry::new_image(
+        hash.clone(),
+        bytes.len(),
+        "png".to_string(),
+    ))
+    .unwrap();
+    let mut text = Entry::new_text("keep me".to_string());
+    text.id = "text".to_string();
+    db.insert(&text).unwrap();
+
+    let out = TempDir::new().unwrap();
+    let archive = out.path().join("backup.tar.gz");
+    let report = backup::create(&db, &archive).unwrap();
+    assert_eq!((report.entries, report.images), (2, 1));
+
+    // Lose the history, then bring it back
+    db.delete("text").unwrap();
+    db.insert(&Entry::new_text("added later".to_string()))
+        .unwrap();
+    std::fs::remove_file(Database::image_path(&hash, "png").unwrap()).unwrap();
+
+    let report = backup::restore(&mut db, &archive, true).unwrap();
+    assert_eq!(report.entries, 2);
+    assert!(report.safety_backup.is_file());
+    assert_eq!(db.get_by_id("text").unwrap().unwrap().content, "keep me");
+    assert_eq!(db.count().unwrap(), 2);
+    assert_eq!(
+        std::fs::read(Database::image_path(&hash, "png").unwrap()).unwrap(),
+        bytes
+    );
+
+    // Garbage is refused before anything is touched
+    let junk = out.path().join("junk.tar.gz");
+    std::fs::write(&junk, b"junk").unwrap();
+    assert!(backup::restore(&mut db, &junk, false).is_err());
+    assert_eq!(db.count().unwrap(), 2);
+
+    // Image names that would panic or escape the store
+    for (hash, ext) in [("a", "png"), ("ü", "png"), (hash.as_str(), "png/../../x")] {
+        let archive = crafted_archive(out.path(), "bad-image", |path| {
+            let db = Database::open_at(path.to_path_buf()).unwrap();
+            let mut entry = Entry::new_image(hash.to_string(), 1, ext.to_string());
+            entry.hash = hash.to_string();
+            db.insert(&entry).unwrap();
+        });
+        assert!(backup::restore(&mut db, &archive, false).is_err());
+        assert_eq!(db.count().unwrap(), 2);
+    }
+
+    // A database from a newer ditox is refused, not swapped in
+    let archive = crafted_archive(out.path(), "newer", |path| {
+        let conn = rusqlite::Connection::open(path).unwrap();
+        conn.execute(
+            "UPDATE schema_meta SET value = '99' WHERE key = 'version'",
+            [],
+        )
+        .unwrap();
+        conn.execute("DELETE FROM entries", []).unwrap();
+    });
+    assert!(matches!(
+        backup::restore(&mut db, &archive, false),
+        Err(DitoxError::MigrationNeeded(_))
+    ));
+    assert_eq!(db.count().unwrap(), 2);
+}
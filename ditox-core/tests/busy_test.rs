@@ -0,0 +1,33 @@
+use ditox_core::{Database, DitoxError, Entry};
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn test_writes_wait_for_another_writer() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("ditox.db");
+    let db = Database::open_at(path.clone()).unwrap();
+    db.init_schema().unwrap();
+
+    // Another process holding the write lock for a moment
+    let other = rusqlite::Connection::open(&path).unwrap();
+    other.execute_batch("BEGIN IMMEDIATE").unwrap();
+    let holder = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(300));
+        other.execute_batch("COMMIT").unwrap();
+    });
+
+    let entry = Entry::new_text("waited".to_string());
+    db.in_transaction(|db| db.insert(&entry)).unwrap();
+    holder.join().unwrap();
+    assert_eq!(db.count().unwrap(), 1);
+}
+
+#[test]
+fn test_busy_errors_count_as_locked() {
+    let busy =
+        rusqlite::Error::SqliteFailure(rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY), None);
+    assert!(DitoxError::Database(busy).is_locked());
+    assert!(DitoxError::Locked("watcher".into()).is_locked());
+    assert!(!DitoxError::Other("x".into()).is_locked());
+}
@@ -0,0 +1,53 @@
+use ditox_core::config_edit;
+use tempfile::TempDir;
+
+#[test]
+fn test_set_keeps_comments_and_checks_values() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("config.toml");
+    std::fs::write(&path, "# mine\n[ui]\nsort = \"recent\"  # newest first\n").unwrap();
+
+    config_edit::set(&path, "ui.sort", "created").unwrap();
+    config_edit::set(&path, "ui.preview_size", "40").unwrap();
+    // Quoted in TOML, but taken as a string when the bare number doesn't fit
+    config_edit::set(&path, "ui.theme.color_depth", "256").unwrap();
+    config_edit::set(&path, "transforms.slug", "tr ' ' -").unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("# mine\n[ui]\nsort = \"created\"  # newest first\n"));
+    assert!(content.contains("color_depth = \"256\""));
+
+    let before = content;
+    assert!(config_edit::set(&path, "ui.preview_size", "big").is_err());
+    assert!(config_edit::set(&path, "ui.no_such_key", "1").is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+
+    let get = |key: &str| config_edit::get(&path, key).unwrap();
+    assert_eq!(get("ui.sort"), Some(serde_json::json!("created")));
+    assert_eq!(get("ui.preview_size"), Some(serde_json::json!(40)));
+    assert_eq!(get("general.max_entries"), Some(serde_json::json!(500)));
+    assert_eq!(get("ui.locale"), None);
+    assert_eq!(get("transforms.slug"), Some(serde_json::json!("tr ' ' -")));
+}
+
+#[test]
+fn test_validate_reports_unknown_keys_and_bad_values() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("config.toml");
+    assert!(config_edit::validate(&path).unwrap().is_empty());
+
+    std::fs::write(
+        &path,
+        "[ui]\nthem = 1\n[keybindings]\np = \"toggle_preview\"\n[hooks]\non_add = \"true\"\n",
+    )
+    .unwrap();
+    assert_eq!(
+        config_edit::validate(&path).unwrap(),
+        ["Unknown key: ui.them"]
+    );
+
+    std::fs::write(&path, "[general]\nmax_entries = \"lots\"\n").unwrap();
+    assert_eq!(config_edit::validate(&path).unwrap().len(), 1);
+
+    std::fs::write(&path, "[general\n").unwrap();
+    assert_eq!(config_edit::validate(&path).unwrap().len(), 1);
+}
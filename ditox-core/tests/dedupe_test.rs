@@ -0,0 +1,43 @@
+use chrono::{Duration, Utc};
+use ditox_core::db::Database;
+use ditox_core::dedupe;
+use ditox_core::entry::Entry;
+use tempfile::TempDir;
+
+fn insert(db: &Database, id: &str, content: &str, days_ago: i64) {
+    let mut entry = Entry::new_text(content.to_string());
+    entry.id = id.to_string();
+    entry.created_at = Utc::now() - Duration::days(days_ago);
+    entry.last_used = entry.created_at;
+    db.insert(&entry).unwrap();
+}
+
+#[test]
+fn test_dedupe_folds_copies_into_oldest() {
+    let dir = TempDir::new().unwrap();
+    let db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    insert(&db, "old", "git status", 5);
+    insert(&db, "newline", "git status\n", 3);
+    insert(&db, "crlf", "  git status\r\n", 1);
+    insert(&db, "other", "git stash", 0);
+    db.add_tag("newline", "git").unwrap();
+    db.set_alias("gs", "crlf").unwrap();
+    db.toggle_favorite("crlf").unwrap();
+    db.touch("newline").unwrap();
+
+    let groups = dedupe::find_duplicates(&db).unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].keep.id, "old");
+    assert_eq!(db.count().unwrap(), 4);
+
+    dedupe::dedupe(&db).unwrap();
+    assert_eq!(db.count().unwrap(), 2);
+    let kept = db.get_by_id("old").unwrap().unwrap();
+    assert_eq!(kept.content, "git status");
+    assert!(kept.favorite);
+    assert_eq!(db.get_tags("old").unwrap(), vec!["git"]);
+    assert_eq!(db.get_alias("gs").unwrap().as_deref(), Some("old"));
+    assert_eq!(db.usage_count("old").unwrap(), 1);
+    assert!(dedupe::find_duplicates(&db).unwrap().is_empty());
+}
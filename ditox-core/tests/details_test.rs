@@ -0,0 +1,31 @@
+use ditox_core::app::App;
+use ditox_core::config::Config;
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use tempfile::TempDir;
+
+#[test]
+fn test_details_load_tags_and_usage_and_clamp_scroll() {
+    let dir = TempDir::new().unwrap();
+    let db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    let mut entry = Entry::new_text("one\ntwo\nthree".to_string());
+    entry.id = "clip".to_string();
+    db.insert(&entry).unwrap();
+    db.add_tag("clip", "work").unwrap();
+    db.touch("clip").unwrap();
+
+    let mut app = App::new(db, Config::default()).unwrap();
+    app.open_details().unwrap();
+    let details = app.details.as_ref().unwrap();
+    assert_eq!(details.tags, vec!["work"]);
+    assert_eq!(details.usage_count, 1);
+
+    app.scroll_details(10);
+    assert_eq!(app.details_scroll, 2);
+    app.scroll_details(-5);
+    assert_eq!(app.details_scroll, 0);
+
+    app.close_details();
+    assert!(app.details.is_none());
+}
@@ -0,0 +1,57 @@
+use ditox_core::app::{App, EditMode};
+use ditox_core::config::Config;
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use tempfile::TempDir;
+
+fn app_with(dir: &TempDir, clips: &[&str]) -> App {
+    let db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    for (i, text) in clips.iter().enumerate() {
+        let mut entry = Entry::new_text(text.to_string());
+        entry.id = format!("clip-{}", i);
+        db.insert(&entry).unwrap();
+    }
+    App::new(db, Config::default()).unwrap()
+}
+
+fn contents(app: &App) -> Vec<String> {
+    let mut contents: Vec<String> = app.entries.iter().map(|e| e.content.clone()).collect();
+    contents.sort();
+    contents
+}
+
+#[test]
+fn test_edit_in_place_replaces_text() {
+    let dir = TempDir::new().unwrap();
+    let mut app = app_with(&dir, &["draft"]);
+    app.apply_edit("clip-0", "draft", "final", EditMode::InPlace)
+        .unwrap();
+    assert_eq!(contents(&app), vec!["final"]);
+}
+
+#[test]
+fn test_edit_as_new_keeps_original() {
+    let dir = TempDir::new().unwrap();
+    let mut app = app_with(&dir, &["draft"]);
+    app.apply_edit("clip-0", "draft", "final", EditMode::AsNew)
+        .unwrap();
+    assert_eq!(contents(&app), vec!["draft", "final"]);
+}
+
+#[test]
+fn test_edit_to_existing_text_never_duplicates() {
+    let dir = TempDir::new().unwrap();
+    let mut app = app_with(&dir, &["final", "draft"]);
+
+    // In place would make two identical clips, so nothing changes
+    app.apply_edit("clip-1", "draft", "final", EditMode::InPlace)
+        .unwrap();
+    assert_eq!(contents(&app), vec!["draft", "final"]);
+
+    // As new moves the existing clip to the top instead
+    app.apply_edit("clip-1", "draft", "final", EditMode::AsNew)
+        .unwrap();
+    assert_eq!(contents(&app), vec!["draft", "final"]);
+    assert_eq!(app.entries[0].id, "clip-0");
+}
@@ -0,0 +1,43 @@
+#![cfg(unix)]
+
+use ditox_core::config::HooksConfig;
+use ditox_core::hooks::{self, HookEvent};
+use ditox_core::Entry;
+use tempfile::TempDir;
+
+#[test]
+fn test_hook_gets_clip_on_stdin_and_env() {
+    let dir = TempDir::new().unwrap();
+    let out = dir.path().join("out");
+    let hooks_config = HooksConfig {
+        on_copy: Some(format!(
+            "{{ echo \"$DITOX_EVENT $DITOX_KIND $DITOX_ID\"; cat; }} > '{}'",
+            out.display()
+        )),
+        ..Default::default()
+    };
+    let entry = Entry::new_text("hello\nworld".to_string());
+
+    // Not configured: nothing runs
+    hooks::run(&hooks_config, HookEvent::Add, &entry);
+    assert!(!out.exists());
+
+    hooks::run(&hooks_config, HookEvent::Copy, &entry);
+    assert_eq!(
+        std::fs::read_to_string(&out).unwrap(),
+        format!("copy text {}\nhello\nworld", entry.id)
+    );
+}
+
+#[test]
+fn test_failing_hook_is_not_an_error() {
+    let hooks_config = HooksConfig {
+        on_delete: Some("exit 7".to_string()),
+        ..Default::default()
+    };
+    hooks::run(
+        &hooks_config,
+        HookEvent::Delete,
+        &Entry::new_text("x".to_string()),
+    );
+}
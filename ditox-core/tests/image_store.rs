@@ -121,6 +121,30 @@ fn cleanup_old_prunes_evicted_blobs() {
     assert_eq!(remaining, 3, "blobs for retained entries should exist");
 }
 
+#[test]
+fn storage_cap_evicts_non_favorites_and_blobs() {
+    let (_dir, _lock, mut db) = setup();
+    let entries: Vec<Entry> = (20..25).map(|b| insert_image(&db, &fake_png(b))).collect();
+    let size = entries[0].byte_size as u64;
+    assert_eq!(db.stored_bytes().unwrap(), size * 5);
+    db.toggle_favorite(&entries[0].id).unwrap();
+
+    // Room for three clips, one of which is the favorite.
+    let evicted = db.enforce_storage_cap(size * 3).unwrap();
+    assert_eq!(evicted, 2);
+    assert_eq!(db.count().unwrap(), 3);
+    assert!(db.stored_bytes().unwrap() <= size * 3);
+    assert!(db.get_by_id(&entries[0].id).unwrap().is_some());
+
+    let paths: Vec<_> = entries.iter().map(|e| e.image_path().unwrap()).collect();
+    let remaining = paths.iter().filter(|p| p.exists()).count();
+    assert_eq!(remaining, 3, "evicted blobs should be unlinked");
+
+    // Favorites are kept even when they alone exceed the cap.
+    db.enforce_storage_cap(0).unwrap();
+    assert_eq!(db.count().unwrap(), 1);
+}
+
 #[test]
 fn startup_drains_pending_prune_queue() {
     // Simulate a crash: row gone, queue populated, file still on disk.
@@ -192,3 +216,162 @@ fn walkdir(p: &std::path::Path) -> Vec<std::path::PathBuf> {
     }
     out
 }
+
+#[test]
+fn export_images_mirrors_store_and_round_trips() {
+    use ditox_core::export::{
+        export_images, import_images, verify_export, Manifest, MANIFEST_FILE,
+    };
+
+    let (_dir, _lock, db) = setup();
+    let first = insert_image(&db, &fake_png(1));
+    let second = insert_image(&db, &fake_png(2));
+    let missing = insert_image(&db, &fake_png(3));
+    std::fs::remove_file(missing.image_path().unwrap()).unwrap();
+
+    let out = TempDir::new().unwrap();
+    let report = export_images(&db, out.path(), 4).unwrap();
+    assert_eq!(report.written, 2);
+    assert_eq!(report.missing, vec![missing.id.clone()]);
+
+    let manifest: Manifest =
+        serde_json::from_str(&std::fs::read_to_string(out.path().join(MANIFEST_FILE)).unwrap())
+            .unwrap();
+    assert_eq!(manifest.files.len(), 2);
+    let first_file = manifest
+        .files
+        .iter()
+        .find(|f| f.entries == vec![first.id.clone()])
+        .unwrap();
+    assert_eq!(first_file.sha256, first.hash);
+    assert_eq!(
+        first_file.file,
+        format!("images/{}/{}.png", &first.hash[..2], first.hash)
+    );
+    assert!(manifest
+        .files
+        .iter()
+        .any(|f| f.entries == vec![second.id.clone()]));
+    assert!(verify_export(out.path()).unwrap().is_empty());
+
+    // A second run leaves existing files alone
+    let report = export_images(&db, out.path(), 2).unwrap();
+    assert_eq!((report.written, report.unchanged), (0, 2));
+
+    // The layout mirrors the store, so a lost blob can be put back
+    let store_path = first.image_path().unwrap();
+    std::fs::remove_file(&store_path).unwrap();
+    let report = import_images(out.path()).unwrap();
+    assert_eq!((report.imported, report.present), (1, 1));
+    assert!(report.bad.is_empty());
+    assert_eq!(std::fs::read(&store_path).unwrap(), fake_png(1));
+
+    // Corruption is reported by verification (replace rather than write
+    // through, since the export may be hard-linked to the store)
+    let exported = out.path().join(&first_file.file);
+    std::fs::remove_file(&exported).unwrap();
+    std::fs::write(&exported, b"garbage").unwrap();
+    assert_eq!(
+        verify_export(out.path()).unwrap(),
+        vec![first_file.file.clone()]
+    );
+}
+
+#[test]
+fn import_images_rejects_untrusted_manifest_entries() {
+    use ditox_core::export::{import_images, Manifest, ManifestFile, MANIFEST_FILE};
+
+    let (dir, _lock, _db) = setup();
+    let out = TempDir::new().unwrap();
+    let good = fake_png(1);
+    let good_hash = Entry::compute_hash(&good);
+    let swapped = fake_png(2); // same size, different content
+
+    let record = |file: &str, sha256: &str, bytes: &[u8]| {
+        // Only create the files that really live inside the export
+        if !file.starts_with('/') && !file.contains("..") {
+            let path = out.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, bytes).unwrap();
+        }
+        ManifestFile {
+            file: file.to_string(),
+            sha256: sha256.to_string(),
+            bytes: bytes.len() as u64,
+            entries: vec!["id".to_string()],
+        }
+    };
+    let files = vec![
+        record(&format!("images/ab/{}.png", good_hash), &good_hash, &good),
+        record("swapped.png", &"ab".repeat(32), &swapped),
+        record("traversal.png", "../../../../escape", &good),
+        record("non-ascii.png", "é", &good),
+        record("../outside.png", &"cd".repeat(32), &good),
+        record("/etc/hostname", &"ef".repeat(32), &good),
+    ];
+    let manifest = Manifest {
+        created_at: chrono::Utc::now(),
+        files,
+    };
+    std::fs::write(
+        out.path().join(MANIFEST_FILE),
+        serde_json::to_string(&manifest).unwrap(),
+    )
+    .unwrap();
+
+    let report = import_images(out.path()).unwrap();
+    assert_eq!(report.imported, 1);
+    assert_eq!(report.bad.len(), 5);
+    assert_eq!(
+        std::fs::read(Database::image_path(&good_hash, "png").unwrap()).unwrap(),
+        good
+    );
+    // Nothing was stored under the hash the swapped file claimed
+    assert!(!Database::image_path(&"ab".repeat(32), "png")
+        .unwrap()
+        .exists());
+    let stored: Vec<_> = walkdir(&dir.path().join("ditox/images"));
+    assert_eq!(stored.len(), 1);
+}
+
+#[test]
+fn export_images_merges_identical_content() {
+    use ditox_core::export::{export_images, Manifest, MANIFEST_FILE};
+
+    let (_dir, _lock, db) = setup();
+    let first = insert_image(&db, &fake_png(1));
+    // A blob stored under a hash that isn't its content's
+    let claimed = "e".repeat(64);
+    Database::store_image_blob(&claimed, "png", &fake_png(1)).unwrap();
+    let copy = Entry::new_image(claimed, 8, "png".to_string());
+    db.insert(&copy).unwrap();
+
+    let out = TempDir::new().unwrap();
+    export_images(&db, out.path(), 2).unwrap();
+    let manifest: Manifest =
+        serde_json::from_str(&std::fs::read_to_string(out.path().join(MANIFEST_FILE)).unwrap())
+            .unwrap();
+    assert_eq!(manifest.files.len(), 1);
+    assert_eq!(manifest.files[0].sha256, first.hash);
+    let mut expected = vec![first.id.clone(), copy.id.clone()];
+    expected.sort();
+    assert_eq!(manifest.files[0].entries, expected);
+}
+
+#[test]
+fn image_dimensions_come_from_the_header() {
+    let (_dir, _lock, db) = setup();
+    let mut bytes = Vec::new();
+    image::RgbImage::new(7, 3)
+        .write_to(
+            &mut std::io::Cursor::new(&mut bytes),
+            image::ImageFormat::Png,
+        )
+        .unwrap();
+    let entry = insert_image(&db, &bytes);
+    assert_eq!(entry.image_dimensions(), Some((7, 3)));
+
+    // Not a decodable image, and text has no blob at all
+    assert_eq!(insert_image(&db, &fake_png(9)).image_dimensions(), None);
+    assert_eq!(Entry::new_text("x".into()).image_dimensions(), None);
+}
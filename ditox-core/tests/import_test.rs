@@ -0,0 +1,70 @@
+use ditox_core::db::Database;
+use ditox_core::import::{self, ImportFormat};
+use tempfile::TempDir;
+
+fn setup(file: &str, content: &str) -> (TempDir, Database, std::path::PathBuf) {
+    let dir = TempDir::new().unwrap();
+    let db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    let path = dir.path().join(file);
+    std::fs::write(&path, content).unwrap();
+    (dir, db, path)
+}
+
+fn contents(db: &Database) -> Vec<String> {
+    db.get_all(100)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.content)
+        .collect()
+}
+
+#[test]
+fn test_clipman_keeps_order_and_skips_duplicates() {
+    let (_dir, db, path) = setup("clipman.json", r#"["oldest", "middle", "newest"]"#);
+    db.insert(&ditox_core::Entry::new_text("middle".to_string()))
+        .unwrap();
+    let report = import::import(&db, &path, None).unwrap();
+    assert_eq!((report.imported, report.duplicates), (2, 1));
+    assert_eq!(contents(&db)[0], "newest");
+}
+
+#[test]
+fn test_clipse_timestamps_and_pins() {
+    let (_dir, db, path) = setup(
+        "clipboard_history.json",
+        r#"{"clipboardHistory": [
+            {"value": "pinned one", "recorded": "2024-05-11 16:23:57.123456789", "filePath": "null", "pinned": true},
+            {"value": "image", "recorded": "2024-05-10 09:00:00", "filePath": "/nonexistent/x.png", "pinned": false}
+        ]}"#,
+    );
+    let report = import::import(&db, &path, None).unwrap();
+    assert_eq!((report.imported, report.skipped), (1, 1));
+    let entry = &db.get_all(10).unwrap()[0];
+    assert!(entry.favorite);
+    assert_eq!(
+        entry.created_at.to_rfc3339(),
+        "2024-05-11T16:23:57.123456789+00:00"
+    );
+}
+
+#[test]
+fn test_gpaste_xml_skips_passwords() {
+    let (_dir, db, path) = setup(
+        "history.xml",
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<history version="2.0">
+  <item kind="Text" uuid="a"><value><![CDATA[newest a]]]]><![CDATA[>b]]></value></item>
+  <item kind="Password" name="pw" uuid="b"><value><![CDATA[hunter2]]></value></item>
+  <item kind="Text" uuid="c"><value><![CDATA[oldest
+line]]></value></item>
+</history>"#,
+    );
+    assert_eq!(
+        ImportFormat::detect(&std::fs::read_to_string(&path).unwrap()),
+        Some(ImportFormat::Gpaste)
+    );
+    let report = import::import(&db, &path, None).unwrap();
+    assert_eq!((report.imported, report.skipped), (2, 1));
+    assert_eq!(contents(&db), ["newest a]]>b", "oldest\nline"]);
+}
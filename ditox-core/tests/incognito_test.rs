@@ -0,0 +1,50 @@
+use ditox_core::config::Config;
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use ditox_core::incognito::{self, IncognitoBuffer, IncognitoState};
+use ditox_core::watcher::Watcher;
+use tempfile::TempDir;
+
+#[test]
+fn test_incognito_buffer_commit_and_discard() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+
+    db.insert(&Entry::new_text("already saved".to_string()))
+        .unwrap();
+
+    let mut buffer = IncognitoBuffer::default();
+    assert!(buffer.push_text("secret".to_string()));
+    assert!(!buffer.push_text("secret".to_string()), "deduplicated");
+    assert_eq!(buffer.discard(), 1);
+    assert!(buffer.is_empty());
+
+    buffer.push_text("keep me".to_string());
+    buffer.push_text("already saved".to_string());
+    assert_eq!(db.count().unwrap(), 1, "nothing written while buffered");
+
+    assert_eq!(buffer.commit(&mut db, 100).unwrap(), 1);
+    assert!(buffer.is_empty());
+    assert_eq!(db.count().unwrap(), 2);
+}
+
+#[test]
+fn test_poll_once_follows_incognito_state() {
+    let temp_dir = TempDir::new().unwrap();
+    assert!(Database::override_data_dir(temp_dir.path().to_path_buf()));
+    let db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    let mut watcher = Watcher::new(db, Config::default());
+
+    // There may be no clipboard here, so the poll itself can fail; the
+    // state file must be picked up before it either way
+    incognito::write_state(IncognitoState::On).unwrap();
+    let _ = watcher.poll_once();
+    assert!(watcher.is_incognito());
+
+    incognito::write_state(IncognitoState::EndDiscard).unwrap();
+    let _ = watcher.poll_once();
+    assert!(!watcher.is_incognito());
+    assert_eq!(incognito::read_state(), IncognitoState::Off);
+}
@@ -0,0 +1,48 @@
+use ditox_core::app::App;
+use ditox_core::config::Config;
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use tempfile::TempDir;
+
+fn open(dir: &TempDir) -> Database {
+    let db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    db
+}
+
+fn add(db: &Database, text: &str, minutes_ago: i64) {
+    let mut entry = Entry::new_text(text.to_string());
+    entry.last_used = chrono::Utc::now() - chrono::Duration::minutes(minutes_ago);
+    entry.created_at = entry.last_used;
+    db.insert(&entry).unwrap();
+}
+
+#[test]
+fn test_app_picks_up_clips_from_other_connections() {
+    let dir = TempDir::new().unwrap();
+    let writer = open(&dir);
+    for i in 0..3 {
+        add(&writer, &format!("clip {}", i), 10 - i);
+    }
+    let mut app = App::new(open(&dir), Config::default()).unwrap();
+    assert!(!app.check_for_changes().unwrap());
+
+    // At the top the new clip just appears there
+    add(&writer, "newest", 0);
+    assert!(app.check_for_changes().unwrap());
+    assert_eq!(app.selected_entry().unwrap().content, "newest");
+    assert_eq!(app.new_clips, 0);
+
+    // Scrolled down, the selection stays put and the arrival is counted
+    app.move_down();
+    app.move_down();
+    let selected = app.selected_entry().unwrap().id.clone();
+    add(&writer, "while scrolled", 0);
+    assert!(app.check_for_changes().unwrap());
+    assert_eq!(app.selected_entry().unwrap().id, selected);
+    assert_eq!(app.new_clips, 1);
+
+    app.go_top();
+    assert_eq!(app.new_clips, 0);
+    assert_eq!(app.selected_entry().unwrap().content, "while scrolled");
+}
@@ -0,0 +1,92 @@
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use ditox_core::merge::merge_db;
+use tempfile::TempDir;
+
+fn text_entry(id: &str, content: &str) -> Entry {
+    let mut entry = Entry::new_text(content.to_string());
+    entry.id = id.to_string();
+    entry
+}
+
+fn open(dir: &TempDir, name: &str) -> Database {
+    let db = Database::open_at(dir.path().join(name).join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    db
+}
+
+#[test]
+fn test_merge_db_adds_and_folds_entries() {
+    let dir = TempDir::new().unwrap();
+    let local = open(&dir, "local");
+    let other = open(&dir, "other");
+    let now = chrono::Utc::now();
+
+    let mut shared = text_entry("local-shared", "shared clip");
+    shared.last_used = now - chrono::Duration::days(2);
+    local.insert(&shared).unwrap();
+    local
+        .insert(&text_entry("local-only", "only here"))
+        .unwrap();
+
+    let mut remote_shared = text_entry("other-shared", "shared clip");
+    remote_shared.favorite = true;
+    remote_shared.last_used = now;
+    remote_shared.notes = Some("from laptop".to_string());
+    other.insert(&remote_shared).unwrap();
+    other.add_tag("other-shared", "work").unwrap();
+    other
+        .insert(&text_entry("other-only", "only there"))
+        .unwrap();
+    other.add_tag("other-only", "personal").unwrap();
+
+    let path = dir.path().join("other").join("ditox.db");
+    let report = merge_db(&local, &path).unwrap();
+    assert_eq!((report.added, report.updated, report.unchanged), (1, 1, 0));
+    assert_eq!(report.tags, 2);
+    assert_eq!(local.count().unwrap(), 3);
+
+    let merged = local.get_by_id("local-shared").unwrap().unwrap();
+    assert!(merged.favorite);
+    assert_eq!(merged.last_used.timestamp(), now.timestamp());
+    assert_eq!(merged.notes.as_deref(), Some("from laptop"));
+    assert_eq!(local.get_tags("local-shared").unwrap(), vec!["work"]);
+    assert_eq!(local.get_tags("other-only").unwrap(), vec!["personal"]);
+
+    // Merging again is a no-op
+    let report = merge_db(&local, &path).unwrap();
+    assert_eq!((report.added, report.updated, report.unchanged), (0, 0, 2));
+    assert_eq!(report.tags, 0);
+
+    assert!(merge_db(&local, &dir.path().join("missing.db")).is_err());
+}
+
+#[test]
+fn test_merge_db_renames_colliding_ids() {
+    let dir = TempDir::new().unwrap();
+    let local = open(&dir, "local");
+    let other = open(&dir, "other");
+
+    local.insert(&text_entry("same-id", "local clip")).unwrap();
+    other.insert(&text_entry("same-id", "remote clip")).unwrap();
+    other.add_tag("same-id", "remote").unwrap();
+
+    let path = dir.path().join("other").join("ditox.db");
+    let report = merge_db(&local, &path).unwrap();
+    assert_eq!((report.added, report.updated, report.unchanged), (1, 0, 0));
+    assert_eq!(local.count().unwrap(), 2);
+
+    // The local clip keeps its id; the remote one is added under a new id
+    let kept = local.get_by_id("same-id").unwrap().unwrap();
+    assert_eq!(kept.content, "local clip");
+    assert!(local.get_tags("same-id").unwrap().is_empty());
+    let hash = Entry::compute_hash("remote clip".as_bytes());
+    let added = local.get_by_hash(&hash).unwrap().unwrap();
+    assert_ne!(added.id, "same-id");
+    assert_eq!(local.get_tags(&added.id).unwrap(), vec!["remote"]);
+
+    // Found by hash the second time, so nothing is duplicated
+    let report = merge_db(&local, &path).unwrap();
+    assert_eq!((report.added, report.unchanged), (0, 1));
+    assert_eq!(local.count().unwrap(), 2);
+}
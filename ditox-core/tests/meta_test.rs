@@ -0,0 +1,61 @@
+use ditox_core::db::{Database, SELECTION_META_KEY};
+use ditox_core::entry::Entry;
+use ditox_core::query::Query;
+use std::collections::BTreeMap;
+use tempfile::TempDir;
+
+#[test]
+fn test_entry_meta_roundtrip_and_cascade() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+
+    let entry = Entry::new_text("https://example.com".to_string());
+    db.insert(&entry).unwrap();
+
+    db.set_meta(&entry.id, "source_app", "firefox").unwrap();
+    db.set_meta(&entry.id, "source_app", "librewolf").unwrap();
+    assert_eq!(
+        db.get_meta(&entry.id, "source_app").unwrap().as_deref(),
+        Some("librewolf"),
+        "setting an existing key replaces the value"
+    );
+
+    let mut imported = BTreeMap::new();
+    imported.insert("url_title".to_string(), "Example Domain".to_string());
+    imported.insert("ocr_text".to_string(), String::new());
+    assert_eq!(db.import_meta(&entry.id, &imported).unwrap(), 2);
+    assert_eq!(db.get_all_meta(&entry.id).unwrap().len(), 3);
+
+    assert!(db.delete_meta(&entry.id, "ocr_text").unwrap());
+    assert!(!db.delete_meta(&entry.id, "ocr_text").unwrap());
+
+    // Metadata follows its entry on delete
+    db.delete(&entry.id).unwrap();
+    assert!(db.get_all_meta(&entry.id).unwrap().is_empty());
+}
+
+#[test]
+fn test_primary_selection_marker_is_queryable() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+
+    let copied = Entry::new_text("copied text".to_string());
+    let selected = Entry::new_text("selected text".to_string());
+    db.insert(&copied).unwrap();
+    db.insert(&selected).unwrap();
+    db.set_meta(&selected.id, SELECTION_META_KEY, "primary")
+        .unwrap();
+
+    let ids = |q: &str| -> Vec<String> {
+        db.query(&Query::parse(q).unwrap(), 10)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.id)
+            .collect()
+    };
+    assert_eq!(ids("is:primary"), vec![selected.id.clone()]);
+    assert_eq!(ids("-is:primary"), vec![copied.id.clone()]);
+    assert_eq!(ids("text is:primary"), vec![selected.id]);
+}
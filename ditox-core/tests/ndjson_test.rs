@@ -0,0 +1,179 @@
+use ditox_core::db::Database;
+use ditox_core::ndjson::{self, ConflictPolicy, ImagePayload};
+use ditox_core::{Collection, Entry};
+use tempfile::TempDir;
+
+fn open(dir: &TempDir, name: &str) -> Database {
+    let db = Database::open_at(dir.path().join(name)).unwrap();
+    db.init_schema().unwrap();
+    db
+}
+
+fn export(db: &Database, images: ImagePayload) -> Vec<u8> {
+    let mut out = Vec::new();
+    ndjson::export(db, &mut out, images).unwrap();
+    out
+}
+
+#[test]
+fn test_round_trip_keeps_tags_meta_and_collection() {
+    let dir = TempDir::new().unwrap();
+    let source = open(&dir, "source.db");
+    let mut entry = Entry::new_text("ssh deploy@host".to_string());
+    entry.favorite = true;
+    entry.notes = Some("prod box".to_string());
+    let work = Collection::new("work".to_string());
+    source.create_collection(&work).unwrap();
+    entry.collection_id = Some(work.id.clone());
+    source.insert(&entry).unwrap();
+    source.add_tag(&entry.id, "ops").unwrap();
+    source.set_meta(&entry.id, "source", "terminal").unwrap();
+    source.set_usage_count(&entry.id, 4).unwrap();
+    source
+        .insert(&Entry::new_text("second".to_string()))
+        .unwrap();
+
+    let stream = export(&source, ImagePayload::Inline);
+    assert_eq!(stream.split(|b| *b == b'\n').count(), 3);
+    assert!(ndjson::detect(std::str::from_utf8(&stream).unwrap()));
+
+    let target = open(&dir, "target.db");
+    target
+        .create_collection(&Collection::new("work".to_string()))
+        .unwrap();
+    let report = ndjson::import(&target, &stream[..], ConflictPolicy::Skip).unwrap();
+    assert_eq!((report.added, report.skipped), (2, 0));
+
+    let copy = target.get_by_id(&entry.id).unwrap().unwrap();
+    assert!(copy.favorite);
+    assert_eq!(copy.notes.as_deref(), Some("prod box"));
+    let collection = target.get_collection_by_name("work").unwrap().unwrap();
+    assert_eq!(copy.collection_id, Some(collection.id));
+    assert_eq!(target.get_tags(&entry.id).unwrap(), ["ops"]);
+    assert_eq!(
+        target.get_meta(&entry.id, "source").unwrap().as_deref(),
+        Some("terminal")
+    );
+    assert_eq!(target.usage_count(&entry.id).unwrap(), 4);
+}
+
+#[test]
+fn test_conflict_policies() {
+    let dir = TempDir::new().unwrap();
+    let source = open(&dir, "source.db");
+    let mut entry = Entry::new_text("shared".to_string());
+    entry.favorite = true;
+    source.insert(&entry).unwrap();
+    source.add_tag(&entry.id, "incoming").unwrap();
+    let stream = export(&source, ImagePayload::Inline);
+
+    let target = open(&dir, "target.db");
+    let local = Entry::new_text("shared".to_string());
+    target.insert(&local).unwrap();
+    target.add_tag(&local.id, "local").unwrap();
+
+    let report = ndjson::import(&target, &stream[..], ConflictPolicy::Skip).unwrap();
+    assert_eq!((report.added, report.skipped), (0, 1));
+    assert!(!target.get_by_id(&local.id).unwrap().unwrap().favorite);
+
+    let report = ndjson::import(&target, &stream[..], ConflictPolicy::Merge).unwrap();
+    assert_eq!(report.updated, 1);
+    assert!(target.get_by_id(&local.id).unwrap().unwrap().favorite);
+    assert_eq!(target.get_tags(&local.id).unwrap(), ["incoming", "local"]);
+
+    let report = ndjson::import(&target, &stream[..], ConflictPolicy::Replace).unwrap();
+    assert_eq!(report.updated, 1);
+    assert!(target.get_by_id(&local.id).unwrap().is_none());
+    assert_eq!(target.get_tags(&entry.id).unwrap(), ["incoming"]);
+    assert_eq!(target.count().unwrap(), 1);
+}
+
+#[test]
+fn test_taken_id_gets_a_fresh_one() {
+    let dir = TempDir::new().unwrap();
+    let source = open(&dir, "source.db");
+    let mut entry = Entry::new_text("theirs".to_string());
+    entry.id = "same-id".to_string();
+    source.insert(&entry).unwrap();
+    let stream = export(&source, ImagePayload::Inline);
+
+    let target = open(&dir, "target.db");
+    let mut local = Entry::new_text("ours".to_string());
+    local.id = "same-id".to_string();
+    target.insert(&local).unwrap();
+
+    let report = ndjson::import(&target, &stream[..], ConflictPolicy::Skip).unwrap();
+    assert_eq!(report.added, 1);
+    assert_eq!(target.count().unwrap(), 2);
+    assert_eq!(
+        target.get_by_id("same-id").unwrap().unwrap().content,
+        "ours"
+    );
+}
+
+#[test]
+fn test_bad_line_rolls_back_and_names_the_line() {
+    let dir = TempDir::new().unwrap();
+    let source = open(&dir, "source.db");
+    source.insert(&Entry::new_text("fine".to_string())).unwrap();
+    let mut stream = export(&source, ImagePayload::Inline);
+    stream.extend_from_slice(b"{not json}\n");
+
+    let target = open(&dir, "target.db");
+    let err = ndjson::import(&target, &stream[..], ConflictPolicy::Skip).unwrap_err();
+    assert!(err.to_string().contains("Line 2"), "{}", err);
+    assert_eq!(target.count().unwrap(), 0);
+}
+
+#[test]
+fn test_images_inline_and_by_reference() {
+    let data = TempDir::new().unwrap();
+    assert!(Database::override_data_dir(data.path().to_path_buf()));
+    let source = open(&data, "source.db");
+    let bytes = b"not really a png".to_vec();
+    let hash = Entry::compute_hash(&bytes);
+    let (blob, _) = Database::store_image_blob(&hash, "png", &bytes).unwrap();
+    source
+        .insert(&Entry::new_image(
+            hash.clone(),
+            bytes.len(),
+            "png".to_string(),
+        ))
+        .unwrap();
+
+    let inline = export(&source, ImagePayload::Inline);
+    let reference = export(&source, ImagePayload::Reference);
+    assert!(inline.len() > reference.len());
+    std::fs::remove_file(&blob).unwrap();
+
+    // Without the blob, a reference has nothing to point at
+    let target = open(&data, "target.db");
+    let report = ndjson::import(&target, &reference[..], ConflictPolicy::Skip).unwrap();
+    assert_eq!((report.added, report.missing_images), (0, 1));
+
+    let report = ndjson::import(&target, &inline[..], ConflictPolicy::Skip).unwrap();
+    assert_eq!(report.added, 1);
+    assert_eq!(std::fs::read(&blob).unwrap(), bytes);
+
+    // Bytes that don't match the hash are refused
+    let tampered = String::from_utf8(inline)
+        .unwrap()
+        .replace(&hash, &"0".repeat(64));
+    let other = open(&data, "other.db");
+    assert!(ndjson::import(&other, tampered.as_bytes(), ConflictPolicy::Skip).is_err());
+}
+
+#[test]
+fn test_single_entry_line_imports() {
+    let dir = TempDir::new().unwrap();
+    let source = open(&dir, "source.db");
+    let entry = Entry::new_text("just captured".to_string());
+    source.insert(&entry).unwrap();
+    let mut line = Vec::new();
+    ndjson::write_entry(&source, &mut line, &entry, ImagePayload::Reference).unwrap();
+    assert_eq!(line.iter().filter(|b| **b == b'\n').count(), 1);
+
+    let target = open(&dir, "target.db");
+    let report = ndjson::import(&target, &line[..], ConflictPolicy::Skip).unwrap();
+    assert_eq!(report.added, 1);
+}
@@ -0,0 +1,28 @@
+use ditox_core::osc52::{self, Passthrough};
+
+#[test]
+fn test_plain_and_tmux_sequences() {
+    assert_eq!(
+        osc52::sequence("hello", Passthrough::None).unwrap(),
+        "\x1b]52;c;aGVsbG8=\x07"
+    );
+    assert_eq!(
+        osc52::sequence("hello", Passthrough::Tmux).unwrap(),
+        "\x1bPtmux;\x1b\x1b]52;c;aGVsbG8=\x07\x1b\\"
+    );
+}
+
+#[test]
+fn test_screen_splits_payload_into_dcs_chunks() {
+    let seq = osc52::sequence(&"x".repeat(200), Passthrough::Screen).unwrap();
+    // 200 bytes encode to 268 base64 chars: four chunks of at most 76
+    assert_eq!(seq.matches("\x1bP").count(), 4);
+    assert!(seq.starts_with("\x1bP\x1b]52;c;"));
+    assert!(seq.ends_with("\x07\x1b\\"));
+}
+
+#[test]
+fn test_rejects_oversized_clips() {
+    let big = "x".repeat(osc52::MAX_BYTES + 1);
+    assert!(osc52::sequence(&big, Passthrough::None).is_err());
+}
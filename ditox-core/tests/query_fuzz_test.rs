@@ -0,0 +1,227 @@
+//! Randomized search inputs. Seeded, so a failure names the input that
+//! caused it and reruns the same way.
+
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use ditox_core::query::{Ago, Query};
+use std::collections::BTreeSet;
+use tempfile::TempDir;
+
+const CASES: usize = 1000;
+
+/// Pieces search strings are built from: words, FTS5 and query syntax, and
+/// characters that need escaping somewhere
+const PIECES: &[&str] = &[
+    "rust",
+    "cargo",
+    "fn",
+    "über",
+    "日本",
+    "\"",
+    "\"\"",
+    "*",
+    "(",
+    ")",
+    ":",
+    "-",
+    "+",
+    "^",
+    "NEAR",
+    "AND",
+    "OR",
+    "NOT",
+    "tag:",
+    "tag:work",
+    "-tag:",
+    "is:fav",
+    "kind:text",
+    "kind:",
+    "after:7d",
+    "before:",
+    "'",
+    "\\",
+    "%",
+    "_",
+    "{",
+    "}",
+    "/",
+    ".*",
+    "\t",
+    " ",
+    " ",
+    " ",
+];
+
+const WORDS: &[&str] = &[
+    "rust",
+    "rustacean",
+    "cargo",
+    "crate",
+    "fn",
+    "über",
+    "unsafe",
+];
+const TAGS: &[&str] = &["work", "todo", "links"];
+
+/// xorshift, as in the seeder
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n.max(1) as u64) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+struct Fixture {
+    _dir: TempDir,
+    db: Database,
+    /// id, content words, tags
+    entries: Vec<(String, Vec<&'static str>, Vec<&'static str>)>,
+}
+
+fn fixture(trigram: bool) -> Fixture {
+    let dir = TempDir::new().unwrap();
+    let mut db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    let mut rng = Rng::new(7);
+    let mut entries = Vec::new();
+    for i in 0..60 {
+        let words: Vec<&str> = (0..1 + rng.below(4)).map(|_| rng.pick(WORDS)).collect();
+        let tags: Vec<&str> = TAGS.iter().copied().filter(|_| rng.below(3) == 0).collect();
+        let mut entry = Entry::new_text(format!("{} #{}", words.join(" "), i));
+        entry.id = format!("id-{}", i);
+        db.insert(&entry).unwrap();
+        for tag in &tags {
+            db.add_tag(&entry.id, tag).unwrap();
+        }
+        entries.push((entry.id, words, tags));
+    }
+    if trigram {
+        db.enable_trigram_index().unwrap();
+    }
+    Fixture {
+        _dir: dir,
+        db,
+        entries,
+    }
+}
+
+/// Counts for relative dates, around where spans stop fitting
+const COUNTS: &[&str] = &[
+    "0",
+    "1",
+    "99999999",
+    "95000000",
+    "2147483647",
+    "4294967295",
+    "4294967296",
+    "9223372036854775807",
+    "18446744073709551616",
+    "-1",
+];
+
+fn random_input(rng: &mut Rng) -> String {
+    (0..rng.below(8))
+        .map(|_| match rng.below(8) {
+            0 => format!(
+                "{}:{}{}",
+                rng.pick(&["after", "before", "-after", "-before"]),
+                rng.pick(COUNTS),
+                rng.pick(&["d", "w", "h", ""])
+            ),
+            _ => rng.pick(PIECES).to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(rng.pick(&["", " "]))
+}
+
+fn ids(entries: &[Entry]) -> BTreeSet<String> {
+    entries.iter().map(|e| e.id.clone()).collect()
+}
+
+#[test]
+fn test_any_search_string_runs() {
+    for trigram in [false, true] {
+        let fixture = fixture(trigram);
+        let db = &fixture.db;
+        let mut rng = Rng::new(42);
+        for _ in 0..CASES {
+            let input = random_input(&mut rng);
+            // Either outcome is fine for the strict parser; it mustn't panic
+            let _ = Query::parse(&input);
+            let query = Query::parse_or_text(&input);
+            if query.regex.is_some() {
+                continue;
+            }
+            let found = db.query(&query, 100);
+            assert!(found.is_ok(), "query {:?}: {:?}", input, found);
+            let count = db.count_query(&query);
+            assert!(count.is_ok(), "count {:?}: {:?}", input, count);
+            let hits = db.search_hits(&query, 100);
+            assert!(hits.is_ok(), "hits {:?}: {:?}", input, hits);
+            assert!(db.search_entries(&input, 100).is_ok(), "{:?}", input);
+        }
+    }
+}
+
+#[test]
+fn test_extreme_relative_dates_never_panic() {
+    for count in COUNTS {
+        for unit in ["d", "w", "h"] {
+            for key in ["after", "before"] {
+                let input = format!("{}:{}{}", key, count, unit);
+                if let Ok(query) = Query::parse(&input) {
+                    // Whatever parsed has to resolve
+                    for ago in [query.after_ago, query.before_ago].into_iter().flatten() {
+                        assert!(ago.checked_resolve().is_some(), "{:?}", input);
+                    }
+                }
+            }
+        }
+    }
+    for n in [u32::MAX, u32::MAX / 2, 95_000_000] {
+        for ago in [Ago::Hours(n), Ago::Days(n), Ago::Weeks(n)] {
+            ago.resolve();
+        }
+    }
+}
+
+#[test]
+fn test_word_and_tag_search_match_a_scan() {
+    let fixture = fixture(false);
+    let mut rng = Rng::new(1);
+    for _ in 0..CASES / 4 {
+        let word = rng.pick(WORDS);
+        let prefix: String = word.chars().take(1 + rng.below(word.len())).collect();
+        let tag = rng.pick(TAGS);
+        let input = format!("{} tag:{}", prefix, tag);
+
+        let expected: BTreeSet<String> = fixture
+            .entries
+            .iter()
+            .filter(|(_, words, tags)| {
+                // The FTS tokenizer folds diacritics
+                words
+                    .iter()
+                    .any(|w| w.replace('ü', "u").starts_with(&prefix.replace('ü', "u")))
+                    && tags.contains(&tag)
+            })
+            .map(|(id, _, _)| id.clone())
+            .collect();
+        let found = ids(&fixture.db.search_entries(&input, 100).unwrap());
+        assert_eq!(found, expected, "{:?}", input);
+        let query = Query::parse(&input).unwrap();
+        assert_eq!(fixture.db.count_query(&query).unwrap(), expected.len());
+    }
+}
@@ -0,0 +1,46 @@
+use ditox_core::db::{SchemaCompat, SCHEMA_VERSION};
+use ditox_core::{Database, DitoxError, Entry};
+use tempfile::TempDir;
+
+#[test]
+fn test_newer_schema_is_left_alone_but_readable() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("ditox.db");
+    let db = Database::open_at(path.clone()).unwrap();
+    assert_eq!(db.schema_compat(), SchemaCompat::Older(0));
+    db.init_schema().unwrap();
+    assert_eq!(db.schema_compat(), SchemaCompat::Current);
+    db.insert(&Entry::new_text("kept".to_string())).unwrap();
+    drop(db);
+
+    // A future build bumped the version and dropped an index we'd recreate
+    let newer = SCHEMA_VERSION + 1;
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    conn.execute(
+        "UPDATE schema_meta SET value = ?1 WHERE key = 'version'",
+        [newer.to_string()],
+    )
+    .unwrap();
+    conn.execute_batch("DROP TRIGGER entries_ai").unwrap();
+    drop(conn);
+
+    let db = Database::open_at(path.clone()).unwrap();
+    assert_eq!(db.schema_compat(), SchemaCompat::Newer(newer));
+    assert!(matches!(
+        db.init_schema(),
+        Err(DitoxError::MigrationNeeded(_))
+    ));
+    drop(db);
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    let triggers: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name = 'entries_ai'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(triggers, 0);
+
+    let db = Database::open_read_only(&path).unwrap();
+    assert_eq!(db.get_all(10).unwrap()[0].content, "kept");
+}
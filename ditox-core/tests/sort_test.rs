@@ -0,0 +1,108 @@
+use chrono::{Duration, Utc};
+use ditox_core::app::{App, DayGroup};
+use ditox_core::config::{Config, SortOrder};
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use tempfile::TempDir;
+
+/// "small" was used last, "large" captured last, "medium" copied most
+fn sample_db(dir: &TempDir) -> Database {
+    let db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    let now = Utc::now();
+    for (text, created_days, used_days) in [
+        ("small", 9, 0),
+        ("medium text", 3, 1),
+        ("large text here", 0, 2),
+    ] {
+        let mut entry = Entry::new_text(text.to_string());
+        entry.id = text.to_string();
+        entry.created_at = now - Duration::days(created_days);
+        entry.last_used = now - Duration::days(used_days);
+        db.insert(&entry).unwrap();
+    }
+    db.set_usage_count("medium text", 5).unwrap();
+    db
+}
+
+fn order(db: &Database, sort: SortOrder) -> Vec<String> {
+    db.get_page_sorted(0, 10, "all", None, sort)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.id)
+        .collect()
+}
+
+#[test]
+fn test_page_sort_orders() {
+    let dir = TempDir::new().unwrap();
+    let db = sample_db(&dir);
+    assert_eq!(
+        order(&db, SortOrder::Recent),
+        ["small", "medium text", "large text here"]
+    );
+    assert_eq!(
+        order(&db, SortOrder::Created),
+        ["large text here", "medium text", "small"]
+    );
+    assert_eq!(order(&db, SortOrder::MostUsed)[0], "medium text");
+    assert_eq!(order(&db, SortOrder::Largest)[0], "large text here");
+}
+
+#[test]
+fn test_cycle_sort_reorders_app() {
+    let dir = TempDir::new().unwrap();
+    let mut app = App::new(sample_db(&dir), Config::default()).unwrap();
+    assert_eq!(app.entries[0].id, "small");
+    app.cycle_sort().unwrap();
+    assert_eq!(app.sort, SortOrder::Created);
+    assert_eq!(app.entries[0].id, "large text here");
+}
+
+#[test]
+fn test_day_groups_follow_sort_time() {
+    let dir = TempDir::new().unwrap();
+    let mut config = Config::default();
+    config.ui.group_by_day = true;
+    let mut app = App::new(sample_db(&dir), config).unwrap();
+    assert_eq!(app.day_group(0), Some(DayGroup::Today));
+    assert_eq!(app.day_group(1), Some(DayGroup::Yesterday));
+    assert_eq!(app.day_group(2), Some(DayGroup::LastWeek));
+
+    app.cycle_sort().unwrap();
+    assert_eq!(app.day_group(2), Some(DayGroup::Older));
+
+    // Not grouped unless sorted by time
+    app.cycle_sort().unwrap();
+    assert_eq!(app.day_group(0), None);
+}
+
+#[test]
+fn test_sort_orders_and_type_tabs_use_an_index() {
+    let dir = TempDir::new().unwrap();
+    sample_db(&dir);
+    let conn = rusqlite::Connection::open(dir.path().join("ditox.db")).unwrap();
+    for (filter, order) in [
+        ("", "last_used DESC"),
+        ("", "created_at DESC"),
+        ("", "usage_count DESC, last_used DESC"),
+        ("", "byte_size DESC, last_used DESC"),
+        (" WHERE entry_type = 'image'", "last_used DESC"),
+        (" WHERE pinned = 1", "last_used DESC"),
+    ] {
+        let sql = format!(
+            "EXPLAIN QUERY PLAN SELECT id FROM entries{} ORDER BY {} LIMIT 100",
+            filter, order
+        );
+        let plan: Vec<String> = conn
+            .prepare(&sql)
+            .unwrap()
+            .query_map([], |row| row.get(3))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let plan = plan.join("; ");
+        assert!(plan.contains("USING INDEX"), "{}: {}", sql, plan);
+        assert!(!plan.contains("TEMP B-TREE"), "{}: {}", sql, plan);
+    }
+}
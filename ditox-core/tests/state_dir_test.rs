@@ -0,0 +1,37 @@
+use ditox_core::watcher;
+use ditox_core::Database;
+use tempfile::TempDir;
+
+#[test]
+fn test_state_files_move_out_of_the_data_dir() {
+    let data = TempDir::new().unwrap();
+    let state = TempDir::new().unwrap();
+    let state_dir = state.path().join("ditox");
+    assert!(Database::override_data_dir(data.path().to_path_buf()));
+    assert!(Database::override_state_dir(state_dir.clone()));
+
+    std::fs::write(data.path().join("incognito"), "discard").unwrap();
+    std::fs::create_dir(data.path().join("logs")).unwrap();
+    std::fs::write(data.path().join("logs/ditox.log"), "old").unwrap();
+    std::fs::write(data.path().join("ditox.db"), "").unwrap();
+
+    let moved = Database::migrate_state_files().unwrap();
+    assert_eq!(moved, ["incognito", "logs"]);
+    assert_eq!(
+        std::fs::read_to_string(state_dir.join("logs/ditox.log")).unwrap(),
+        "old"
+    );
+    assert!(data.path().join("ditox.db").exists());
+    assert_eq!(
+        watcher::get_pid_file_path().unwrap(),
+        state_dir.join("watcher.pid")
+    );
+
+    // The state dir's own copy wins; the old one is left alone
+    std::fs::write(data.path().join("incognito"), "commit").unwrap();
+    assert!(Database::migrate_state_files().unwrap().is_empty());
+    assert_eq!(
+        std::fs::read_to_string(state_dir.join("incognito")).unwrap(),
+        "discard"
+    );
+}
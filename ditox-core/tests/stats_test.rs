@@ -0,0 +1,62 @@
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use ditox_core::watcher::WatcherHealth;
+use tempfile::TempDir;
+
+#[test]
+fn test_prometheus_output() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    db.insert(&Entry::new_text("one".to_string())).unwrap();
+    db.insert(&Entry::new_text("two".to_string())).unwrap();
+    let stats = db.get_stats().unwrap();
+
+    let output = stats.prometheus(None);
+    assert!(output.contains("# TYPE ditox_entries gauge\n"));
+    assert!(output.contains("ditox_entries{kind=\"text\"} 2\n"));
+    assert!(output.contains("ditox_entries{kind=\"image\"} 0\n"));
+    assert!(output.contains("ditox_watcher_up 0\n"));
+    assert!(!output.contains("ditox_clips_captured_total"));
+
+    let health = WatcherHealth {
+        captured: 7,
+        duplicates: 3,
+        ..WatcherHealth::default()
+    };
+    let output = stats.prometheus(Some(&health));
+    assert!(output.contains("# TYPE ditox_clips_captured_total counter\n"));
+    assert!(output.contains("ditox_clips_captured_total 7\n"));
+    assert!(output.contains("ditox_duplicates_total 3\n"));
+    // Every sample line is "name[{labels}] value"
+    for line in output.lines().filter(|l| !l.starts_with('#')) {
+        let (name, value) = line.rsplit_once(' ').unwrap();
+        assert!(name.starts_with("ditox_"));
+        assert!(value.parse::<u64>().is_ok());
+    }
+}
+
+#[test]
+fn test_history_analytics() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    let mut old = Entry::new_text("ab".to_string());
+    old.created_at = chrono::Utc::now() - chrono::Duration::days(2);
+    db.insert(&old).unwrap();
+    let recent = Entry::new_text("abcd".to_string());
+    db.insert(&recent).unwrap();
+    db.add_tag(&old.id, "work").unwrap();
+    db.add_tag(&recent.id, "work").unwrap();
+    db.add_tag(&recent.id, "misc").unwrap();
+
+    let stats = db.get_stats().unwrap();
+    assert_eq!(stats.clips_per_day.len(), 30);
+    assert_eq!(stats.clips_per_day[29].clips, 1);
+    assert_eq!(stats.clips_per_day[27].clips, 1);
+    assert_eq!(stats.top_tags[0].name, "work");
+    assert_eq!(stats.top_tags[0].count, 2);
+    assert_eq!(stats.avg_text_length, 3.0);
+    assert!(stats.fts_index_bytes > 0);
+    assert!(stats.display().contains("Peak: 1 on"));
+}
@@ -0,0 +1,144 @@
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use ditox_core::query::Query;
+use tempfile::TempDir;
+
+fn text_entry(id: &str, content: &str) -> Entry {
+    let mut entry = Entry::new_text(content.to_string());
+    entry.id = id.to_string();
+    entry
+}
+
+fn setup() -> (TempDir, Database) {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    db.insert(&text_entry("id-a", "first")).unwrap();
+    db.insert(&text_entry("id-b", "second")).unwrap();
+    (temp_dir, db)
+}
+
+#[test]
+fn test_tag_add_list_and_query() {
+    let (_dir, db) = setup();
+
+    assert!(db.add_tag("id-a", " work ").unwrap());
+    assert!(!db.add_tag("id-a", "work").unwrap());
+    db.add_tag("id-a", "urgent").unwrap();
+    db.add_tag("id-b", "work").unwrap();
+    assert!(db.add_tag("id-b", "  ").is_err());
+
+    assert_eq!(db.get_tags("id-a").unwrap(), vec!["urgent", "work"]);
+    assert_eq!(
+        db.all_tags().unwrap(),
+        vec![("urgent".to_string(), 1), ("work".to_string(), 2)]
+    );
+
+    let results = db
+        .query(&Query::parse("tag:work -tag:urgent").unwrap(), 10)
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, "id-b");
+
+    assert!(db.remove_tag("id-a", "urgent").unwrap());
+    assert!(!db.remove_tag("id-a", "urgent").unwrap());
+}
+
+#[test]
+fn test_tag_batches_are_all_or_nothing() {
+    let (_dir, mut db) = setup();
+    let tags = |names: &[&str]| names.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+    // One invalid name: nothing is added
+    assert!(db.add_tags("id-a", &tags(&["work", "a//b"])).is_err());
+    assert!(db.get_tags("id-a").unwrap().is_empty());
+    assert_eq!(db.add_tags("id-a", &tags(&["work", "urgent"])).unwrap(), 2);
+
+    // One missing tag: nothing is removed
+    assert!(db.remove_tags("id-a", &tags(&["work", "missing"])).is_err());
+    assert_eq!(db.get_tags("id-a").unwrap(), vec!["urgent", "work"]);
+    db.remove_tags("id-a", &tags(&["work", "urgent"])).unwrap();
+    assert!(db.get_tags("id-a").unwrap().is_empty());
+}
+
+#[test]
+fn test_tag_rename_merge_and_delete() {
+    let (_dir, mut db) = setup();
+    db.add_tag("id-a", "wrk").unwrap();
+    db.add_tag("id-a", "work").unwrap();
+    db.add_tag("id-b", "wrk").unwrap();
+
+    // Renaming onto an existing tag merges without duplicates
+    assert_eq!(db.rename_tag("wrk", "work").unwrap(), 2);
+    assert_eq!(db.all_tags().unwrap(), vec![("work".to_string(), 2)]);
+    assert_eq!(db.rename_tag("missing", "work").unwrap(), 0);
+
+    assert_eq!(db.delete_tag("work").unwrap(), 2);
+    assert!(db.all_tags().unwrap().is_empty());
+
+    // Tags go with their entry
+    db.add_tag("id-a", "keep").unwrap();
+    db.delete("id-a").unwrap();
+    assert!(db.all_tags().unwrap().is_empty());
+}
+
+#[test]
+fn test_hierarchical_tags() {
+    let (_dir, mut db) = setup();
+    db.insert(&text_entry("id-c", "third")).unwrap();
+    db.add_tag("id-a", "work").unwrap();
+    db.add_tag("id-b", "work/project-x").unwrap();
+    db.add_tag("id-c", "workshop").unwrap();
+    assert!(db.add_tag("id-c", "work//x").is_err());
+
+    // A parent tag matches its sub-tags, but not tags merely sharing a prefix
+    let mut ids: Vec<String> = db
+        .query(&Query::parse("tag:work").unwrap(), 10)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.id)
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec!["id-a", "id-b"]);
+    let results = db.query(&Query::parse("-tag:work").unwrap(), 10).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, "id-c");
+
+    // Renaming moves the sub-tags along
+    assert_eq!(db.rename_tag("work", "job").unwrap(), 2);
+    assert_eq!(
+        db.all_tags().unwrap(),
+        vec![
+            ("job".to_string(), 1),
+            ("job/project-x".to_string(), 1),
+            ("workshop".to_string(), 1)
+        ]
+    );
+    assert!(db.rename_tag("job", "job/sub").is_err());
+}
+
+#[test]
+fn test_tag_limits_evict_oldest_tagged() {
+    let (_dir, mut db) = setup();
+    let now = chrono::Utc::now();
+    for i in 0..5 {
+        let mut entry = text_entry(&format!("log-{}", i), &format!("log line {}", i));
+        entry.last_used = now - chrono::Duration::minutes(10 - i);
+        db.insert(&entry).unwrap();
+        db.add_tag(&entry.id, if i % 2 == 0 { "log" } else { "log/app" })
+            .unwrap();
+    }
+    // The oldest log entry is a favorite and survives
+    db.toggle_favorite("log-0").unwrap();
+    assert_eq!(db.count_tagged("log").unwrap(), 5);
+
+    let mut limits = std::collections::HashMap::new();
+    limits.insert("log".to_string(), 2);
+    assert_eq!(db.enforce_retention(100, &limits).unwrap(), 2);
+
+    assert_eq!(db.count_tagged("log").unwrap(), 3);
+    for id in ["log-0", "log-3", "log-4", "id-a", "id-b"] {
+        assert!(db.get_by_id(id).unwrap().is_some(), "{} was evicted", id);
+    }
+    assert!(db.get_by_id("log-1").unwrap().is_none());
+}
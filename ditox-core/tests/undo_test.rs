@@ -0,0 +1,69 @@
+use ditox_core::app::App;
+use ditox_core::config::Config;
+use ditox_core::db::Database;
+use ditox_core::entry::Entry;
+use ditox_core::undo;
+use tempfile::TempDir;
+
+fn db_with(dir: &TempDir, clips: &[&str]) -> Database {
+    let db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+    for (i, text) in clips.iter().enumerate() {
+        let mut entry = Entry::new_text(text.to_string());
+        entry.id = format!("clip-{}", i);
+        db.insert(&entry).unwrap();
+    }
+    db
+}
+
+#[test]
+fn test_restore_brings_back_tags_meta_aliases_and_usage() {
+    let dir = TempDir::new().unwrap();
+    let mut db = db_with(&dir, &["ssh key"]);
+    db.add_tag("clip-0", "work").unwrap();
+    db.set_meta("clip-0", "source", "terminal").unwrap();
+    db.set_alias("key", "clip-0").unwrap();
+    db.touch("clip-0").unwrap();
+    db.touch("clip-0").unwrap();
+
+    let deleted = undo::delete_restorable(&mut db, "clip-0").unwrap().unwrap();
+    assert!(db.get_by_id("clip-0").unwrap().is_none());
+    assert_eq!(db.get_alias("key").unwrap(), None);
+
+    assert!(undo::restore(&db, &deleted).unwrap());
+    assert_eq!(db.get_by_id("clip-0").unwrap().unwrap().content, "ssh key");
+    assert_eq!(db.get_tags("clip-0").unwrap(), vec!["work"]);
+    assert_eq!(
+        db.get_meta("clip-0", "source").unwrap().as_deref(),
+        Some("terminal")
+    );
+    assert_eq!(db.get_alias("key").unwrap().as_deref(), Some("clip-0"));
+    assert_eq!(db.usage_count("clip-0").unwrap(), 2);
+}
+
+#[test]
+fn test_restore_skips_content_captured_again() {
+    let dir = TempDir::new().unwrap();
+    let mut db = db_with(&dir, &["hello"]);
+    let deleted = undo::delete_restorable(&mut db, "clip-0").unwrap().unwrap();
+    db.insert(&Entry::new_text("hello".to_string())).unwrap();
+
+    assert!(!undo::restore(&db, &deleted).unwrap());
+    assert_eq!(db.count().unwrap(), 1);
+}
+
+#[test]
+fn test_app_undo_restores_last_delete() {
+    let dir = TempDir::new().unwrap();
+    let db = db_with(&dir, &["one", "two"]);
+    let mut app = App::new(db, Config::default()).unwrap();
+
+    app.delete_selected().unwrap();
+    assert_eq!(app.entries.len(), 1);
+    app.undo_delete().unwrap();
+    assert_eq!(app.entries.len(), 2);
+
+    // Each delete can only be undone once
+    app.undo_delete().unwrap();
+    assert_eq!(app.entries.len(), 2);
+}
@@ -0,0 +1,74 @@
+use ditox_core::db::Database;
+use ditox_core::entry::{Entry, EntryType};
+use ditox_core::query::{Ago, Query};
+use tempfile::TempDir;
+
+#[test]
+fn test_saved_search_roundtrip_and_run() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+
+    let work = Entry::new_text("work meeting notes".to_string());
+    let mut old = Entry::new_text("work from last year".to_string());
+    old.created_at = chrono::Utc::now() - chrono::Duration::days(30);
+    db.insert(&work).unwrap();
+    db.insert(&old).unwrap();
+    db.toggle_favorite(&work.id).unwrap();
+    db.toggle_favorite(&old.id).unwrap();
+
+    let query = Query {
+        text: Some("work".to_string()),
+        entry_type: Some(EntryType::Text),
+        favorites_only: true,
+        after_ago: Some(Ago::Days(7)),
+        ..Query::default()
+    };
+    db.save_search("recent-work", &query).unwrap();
+
+    assert_eq!(db.get_saved_search("recent-work").unwrap(), Some(query));
+    assert_eq!(db.get_saved_searches().unwrap().len(), 1);
+
+    let query = db.get_saved_search("recent-work").unwrap().unwrap();
+    assert_eq!(db.count_query(&query).unwrap(), 1);
+    let results = db.query_page(&query, 0, 10).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, work.id);
+
+    assert!(db.delete_saved_search("recent-work").unwrap());
+    assert_eq!(db.get_saved_search("recent-work").unwrap(), None);
+}
+
+#[test]
+fn test_saved_view_keeps_relative_dates_relative() {
+    let temp_dir = TempDir::new().unwrap();
+    let db = Database::open_at(temp_dir.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+
+    let mut recent = Entry::new_text("from an hour ago".to_string());
+    recent.created_at = chrono::Utc::now() - chrono::Duration::hours(1);
+    let mut older = Entry::new_text("from two days ago".to_string());
+    older.created_at = chrono::Utc::now() - chrono::Duration::days(2);
+    db.insert(&recent).unwrap();
+    db.insert(&older).unwrap();
+
+    let query = Query::parse("after:24h").unwrap();
+    db.save_search("last-day", &query).unwrap();
+    let query = Query::parse("before:1d").unwrap();
+    db.save_search("before-yesterday", &query).unwrap();
+
+    // The stored view holds the span, not the instant it was saved at
+    let last_day = db.get_saved_search("last-day").unwrap().unwrap();
+    assert_eq!(last_day.after_ago, Some(Ago::Hours(24)));
+    assert_eq!(last_day.after, None);
+    assert_eq!(last_day.to_string(), "after:24h");
+    let results = db.query_page(&last_day, 0, 10).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, recent.id);
+
+    let before = db.get_saved_search("before-yesterday").unwrap().unwrap();
+    assert_eq!(before.before_ago, Some(Ago::Days(1)));
+    let results = db.query_page(&before, 0, 10).unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, older.id);
+}
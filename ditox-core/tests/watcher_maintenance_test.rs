@@ -0,0 +1,25 @@
+use ditox_core::backup;
+use ditox_core::config::{AutoBackup, Config};
+use ditox_core::db::Database;
+use ditox_core::watcher::Watcher;
+use tempfile::TempDir;
+
+#[test]
+fn test_poll_once_runs_scheduled_backups() {
+    let data = TempDir::new().unwrap();
+    assert!(Database::override_data_dir(data.path().to_path_buf()));
+    let db = Database::open_at(data.path().join("ditox.db")).unwrap();
+    db.init_schema().unwrap();
+
+    let dir = data.path().join("snapshots");
+    let mut config = Config::default();
+    config.backup.auto = AutoBackup::Daily;
+    config.backup.dir = Some(dir.clone());
+    let mut watcher = Watcher::new(db, config);
+
+    // There may be no clipboard here, so the poll itself can fail; the
+    // due backup is written before it either way, and only once
+    let _ = watcher.poll_once();
+    let _ = watcher.poll_once();
+    assert_eq!(backup::auto_backups(&dir).unwrap().len(), 1);
+}
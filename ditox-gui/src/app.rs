@@ -1,6 +1,8 @@
 //! Ditox iced GUI application - Modern redesign
 
 use ditox_core::app::TabFilter;
+use ditox_core::confirm::{ConfirmAction, ConfirmStep, ConfirmTracker};
+use ditox_core::i18n::t;
 use ditox_core::{Clipboard, Config, Database, Entry, EntryType, Result, Watcher};
 #[cfg(windows)]
 use global_hotkey::{
@@ -1011,7 +1013,7 @@ pub enum ViewMode {
     /// it with Tab on the focused entry; the main list stays visible to its
     /// left and the side panel takes the right portion of the window.
     EntryPanel(String), // entry_id
-    ConfirmDelete(String), // entry_id - confirmation for deleting favorites
+    ConfirmDelete(String), // entry_id - confirmation per the [confirm] config
 }
 
 #[derive(Debug, Clone)]
@@ -1077,7 +1079,7 @@ pub enum Message {
     CopyFromPreview,
 
     // Delete confirmation
-    RequestDelete(String), // entry_id - triggers confirmation for favorites
+    RequestDelete(String), // entry_id - asks for confirmation if configured
     ConfirmDeleteEntry(String), // entry_id - actually delete after confirmation
     CancelDelete,
 
@@ -1119,6 +1121,8 @@ pub struct DitoxApp {
     /// Current scroll viewport for smart scrolling
     scroll_viewport: Option<scrollable::Viewport>,
     is_searching: bool,
+    /// Applies the `[confirm]` policy to delete requests
+    confirm: ConfirmTracker,
 }
 
 impl DitoxApp {
@@ -1201,6 +1205,7 @@ impl DitoxApp {
             image_cache: HashMap::new(),
             scroll_viewport: None,
             is_searching: false,
+            confirm: ConfirmTracker::default(),
         };
 
         // One-shot mode: don't override the bottom-left position picked by
@@ -1248,19 +1253,23 @@ impl DitoxApp {
             }
 
             Message::RequestDelete(id) => {
-                // Check if entry is a favorite - if so, show confirmation dialog
-                if let Some(entry) = self.entries.iter().find(|e| e.id == id) {
-                    if entry.favorite {
-                        self.view_mode = ViewMode::ConfirmDelete(id);
-                    } else {
-                        // Non-favorite: delete directly
-                        return self.update(Message::ConfirmDeleteEntry(id));
+                // Same policy as the TUI. The dialog has buttons only, so a
+                // typed confirmation is asked as a plain one here; a first
+                // press under double-press waits for the second.
+                if self.entries.iter().any(|e| e.id == id) {
+                    match self
+                        .confirm
+                        .request(&self.config.confirm, ConfirmAction::DeleteSelected)
+                    {
+                        ConfirmStep::Run => return self.update(Message::ConfirmDeleteEntry(id)),
+                        ConfirmStep::Ask(_) => self.view_mode = ViewMode::ConfirmDelete(id),
+                        ConfirmStep::AwaitSecondPress => {}
                     }
                 }
             }
 
             Message::ConfirmDeleteEntry(id) => {
-                // Actually delete the entry (after confirmation for favorites)
+                // Actually delete the entry (after any confirmation)
                 let was_in_preview = matches!(self.view_mode, ViewMode::EntryPanel(_));
                 let was_in_confirm = matches!(self.view_mode, ViewMode::ConfirmDelete(_));
                 let _ = self.db.lock().unwrap().delete(&id);
@@ -2345,22 +2354,27 @@ impl DitoxApp {
         let content = if let Some(entry) = entry {
             let preview_text = entry.preview(40);
             let entry_id_confirm = entry.id.clone();
+            let (glyph, title, warning) = if entry.favorite {
+                (
+                    icons::STAR_FILL,
+                    t("confirm.delete_favorite"),
+                    t("confirm.favorite_warning"),
+                )
+            } else {
+                (icons::TRASH, t("confirm.delete_entry_title"), String::new())
+            };
 
             column![
                 // Header with warning icon
                 row![
-                    icon(icons::STAR_FILL).size(16).color(colors::WARNING),
+                    icon(glyph).size(16).color(colors::WARNING),
                     Space::new().width(8),
-                    text("Delete Favorite?")
-                        .size(16)
-                        .color(colors::TEXT_PRIMARY),
+                    text(title).size(16).color(colors::TEXT_PRIMARY),
                 ]
                 .align_y(iced::Alignment::Center),
                 Space::new().height(16),
-                // Warning message
-                text("This entry is marked as a favorite.")
-                    .size(12)
-                    .color(colors::TEXT_SECONDARY),
+                // Warning message, for favorites
+                text(warning).size(12).color(colors::TEXT_SECONDARY),
                 Space::new().height(8),
                 // Entry preview
                 container(text(preview_text).size(11).color(colors::TEXT_MUTED))
@@ -1,18 +1,150 @@
-use clap::{Parser, Subcommand};
+use crate::complete::Shell;
+use crate::exit::EXIT_CODES_HELP;
+use crate::logging::LogFormat;
+use crate::output::OutputFormat;
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(name = "ditox")]
 #[command(author, version, about = "Terminal clipboard manager for Wayland")]
 #[command(propagate_version = true)]
+#[command(after_help = EXIT_CODES_HELP)]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Run against a throwaway database filled with synthetic history
+    /// instead of your own (removed on exit)
+    #[arg(long)]
+    pub demo: bool,
+
+    /// Log level (error, warn, info, debug, trace) or filter directives
+    /// like `ditox_core=debug`. Defaults to RUST_LOG, else info.
+    #[arg(long, global = true)]
+    pub log_level: Option<String>,
+
+    /// Copy through the terminal with OSC 52 escape sequences (the default
+    /// when SSH_TTY is set)
+    #[arg(long, global = true)]
+    pub osc52: bool,
+
+    /// Log output format
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
+
+    /// Result format for list, search, get, stats, favorite, delete,
+    /// doctor, migrate and tag list (a command's --json means json)
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Plain)]
+    pub output: OutputFormat,
+}
+
+/// Clipboard managers `ditox import` reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportSource {
+    Clipman,
+    Clipse,
+    Gpaste,
+    /// A `ditox export` stream
+    Ndjson,
+}
+
+/// What `ditox import` does with a clip that is already in the history
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OnConflict {
+    /// Keep the existing clip
+    #[default]
+    Skip,
+    /// Add the incoming tags, metadata and favorite to the existing clip
+    Merge,
+    /// Overwrite the existing clip
+    Replace,
+}
+
+/// Formats `ditox export` writes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// One JSON object per clip and line
+    #[default]
+    Ndjson,
+}
+
+/// How `ditox export` includes image clips
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportImageMode {
+    /// Image bytes as base64 in each record
+    #[default]
+    Inline,
+    /// Hash and extension only (pair with `export-images`)
+    Reference,
+}
+
+/// What `ditox pick --print` writes for each clip
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum PrintField {
+    /// Full entry ID
+    Id,
+    /// Text content (the file path for images)
+    #[default]
+    Text,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Start clipboard watcher daemon
-    Watch,
+    Watch {
+        /// Stop an already running watcher and take over from it
+        #[arg(long)]
+        replace: bool,
+
+        /// Print each captured clip to stdout as a line of JSON, in the
+        /// `ditox export` format (images by reference)
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// Check the setup: data directory, clipboard tools, watcher, logs
+    Doctor {
+        /// Show recent warnings and errors from the watcher log
+        #[arg(long)]
+        logs: bool,
+
+        /// Number of log lines to show with --logs
+        #[arg(short = 'n', long, default_value = "20")]
+        lines: usize,
+
+        /// Show detailed database and watcher diagnostics
+        #[arg(long, conflicts_with = "logs")]
+        daemon: bool,
+    },
+
+    /// Manage the watcher as a service
+    #[command(subcommand)]
+    Daemon(DaemonCommands),
+
+    /// Choose clips in the picker, then copy them or print them for a
+    /// script (like dmenu/fzf). Exits with 1 if nothing was chosen.
+    Pick {
+        /// Write the chosen clips to stdout instead of copying them
+        #[arg(long)]
+        print: bool,
+
+        /// What to print for each chosen clip (implies --print)
+        #[arg(long, value_enum, value_name = "FIELD")]
+        print_field: Option<PrintField>,
+
+        /// Start with this search in the search box
+        #[arg(long, value_name = "QUERY")]
+        query: Option<String>,
+
+        /// Choose the top match of --query (or the latest clip) without
+        /// showing the picker
+        #[arg(long)]
+        first: bool,
+
+        /// Show which [ui.theme] key colors the cell under the mouse
+        #[arg(long)]
+        theme_edit: bool,
+    },
 
     /// List recent clipboard entries
     List {
@@ -27,22 +159,41 @@ pub enum Commands {
         /// Show only favorite entries
         #[arg(long)]
         favorites: bool,
+
+        /// Print complete entry IDs instead of short prefixes
+        #[arg(long)]
+        full_ids: bool,
     },
 
     /// Get full content of entry by index (1-based) or ID
     Get {
-        /// Entry index (1-based) or UUID
+        /// Entry index (1-based), alias, or UUID (or unambiguous UUID prefix)
         target: String,
 
         /// Output as JSON
-        #[arg(long)]
+        #[arg(long, conflicts_with_all = ["raw", "out"])]
         json: bool,
+
+        /// Write the exact bytes: the text as stored, or the image file
+        #[arg(long)]
+        raw: bool,
+
+        /// Write the exact bytes to a file instead of stdout
+        #[arg(short, long)]
+        out: Option<std::path::PathBuf>,
     },
 
     /// Fuzzy search clipboard entries
     Search {
         /// Search query
-        query: String,
+        #[arg(required_unless_present = "regex")]
+        query: Option<String>,
+
+        /// Only match text entries whose content matches this regular
+        /// expression (e.g. '\bAKIA[0-9A-Z]{16}\b'). Combined with a query,
+        /// the regex narrows the candidates before fuzzy ranking.
+        #[arg(long, value_name = "PATTERN")]
+        regex: Option<String>,
 
         /// Maximum number of results
         #[arg(short, long, default_value = "10")]
@@ -51,23 +202,49 @@ pub enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+
+        /// Print complete entry IDs instead of short prefixes
+        #[arg(long)]
+        full_ids: bool,
     },
 
     /// Copy entry to clipboard by index (1-based) or ID
     Copy {
-        /// Entry index (1-based) or UUID
-        target: String,
+        /// Entry index (1-based), alias, or UUID (or unambiguous UUID prefix)
+        #[arg(required_unless_present = "recent")]
+        target: Option<String>,
+
+        /// Copy the Nth most recent text clip instead (1 = latest). The
+        /// copied clip moves to the top, like yanking from a kill ring.
+        #[arg(
+            long,
+            value_name = "N",
+            conflicts_with = "target",
+            value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+        )]
+        recent: Option<usize>,
+
+        /// Also type the text into the focused window (wtype, ydotool or
+        /// xdotool), for apps that don't take a paste
+        #[arg(long = "type")]
+        type_text: bool,
+
+        /// Transform the text first: trim, upper, lower, json-pretty,
+        /// base64, strip-ansi, or a name from [transforms] in the config.
+        /// Repeat or separate with commas to chain.
+        #[arg(long, value_name = "NAME", value_delimiter = ',')]
+        transform: Vec<String>,
     },
 
     /// Delete entry by index (1-based) or ID
     Delete {
-        /// Entry index (1-based) or UUID
+        /// Entry index (1-based), alias, or UUID (or unambiguous UUID prefix)
         target: String,
     },
 
     /// Toggle favorite status of entry by index (1-based) or ID
     Favorite {
-        /// Entry index (1-based) or UUID
+        /// Entry index (1-based), alias, or UUID (or unambiguous UUID prefix)
         target: String,
     },
 
@@ -84,11 +261,46 @@ pub enum Commands {
     /// Show watcher status and statistics
     Status,
 
+    /// Serve a read-only web page for browsing and searching clips
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        addr: String,
+
+        /// Access token required by every request (random if not given)
+        #[arg(long)]
+        token: Option<String>,
+
+        /// Allow deleting and favoriting clips from the page
+        #[arg(long)]
+        writable: bool,
+    },
+
+    /// Print a shell completion script, e.g. `ditox completions bash >>
+    /// ~/.bashrc` (completes clip IDs, aliases and tags too)
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// Candidates for the word being completed; used by the completion
+    /// scripts
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        words: Vec<String>,
+    },
+
     /// Show usage statistics
     Stats {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+
+        /// Output as Prometheus metrics, including the watcher's capture
+        /// counters (e.g. for node_exporter's textfile collector)
+        #[arg(long, conflicts_with = "json")]
+        prometheus: bool,
     },
 
     /// Reconcile the image store with the database.
@@ -107,9 +319,242 @@ pub enum Commands {
         fix_hashes: bool,
     },
 
+    /// Copy all images into a directory laid out like the image store, with
+    /// a manifest.json of entry IDs and SHA-256 checksums. Files are
+    /// hard-linked when possible; re-running only adds what changed.
+    ExportImages {
+        /// Destination directory (created if needed)
+        dir: std::path::PathBuf,
+
+        /// Number of worker threads (default: CPU count, at most 8)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Check an existing export against its manifest instead
+        #[arg(long)]
+        verify: bool,
+    },
+
+    /// Restore image blobs from an `export-images` directory into the image
+    /// store, hard-linking when possible
+    ImportImages {
+        /// Export directory
+        dir: std::path::PathBuf,
+    },
+
+    /// Stream every clip with its tags, notes, metadata and images, one
+    /// JSON object per line. Read it back with `ditox import`.
+    Export {
+        /// Write to standard output (the default)
+        #[arg(long, conflicts_with = "out")]
+        stdout: bool,
+
+        /// Write to a file instead
+        #[arg(short, long)]
+        out: Option<std::path::PathBuf>,
+
+        #[arg(long, value_enum, default_value_t = ExportFormat::Ndjson)]
+        format: ExportFormat,
+
+        /// How image clips carry their bytes
+        #[arg(long, value_enum, default_value_t = ExportImageMode::Inline)]
+        images: ExportImageMode,
+    },
+
+    /// Import history from another clipboard manager (clipman, clipse or
+    /// GPaste) or a `ditox export` stream, skipping clips already in the
+    /// history
+    Import {
+        /// History file, e.g. ~/.local/share/clipman.json, or - for a
+        /// `ditox export` stream on standard input
+        path: std::path::PathBuf,
+
+        /// Format of the file (detected from its content by default)
+        #[arg(long, value_enum)]
+        format: Option<ImportSource>,
+
+        /// For `ditox export` streams: what to do with clips already in the
+        /// history
+        #[arg(long, value_enum, default_value_t = OnConflict::Skip)]
+        on_conflict: OnConflict,
+    },
+
+    /// Merge another ditox database (e.g. copied from a second machine)
+    /// into this one. Clips are matched by content; images are read from
+    /// the `images/` directory next to it.
+    MergeDb {
+        /// Path to the other `ditox.db`
+        path: std::path::PathBuf,
+    },
+
+    /// Fold duplicate text clips (same text up to line endings and
+    /// surrounding whitespace) into the oldest copy, keeping tags,
+    /// favorites and the latest use
+    Dedupe {
+        /// Only list the duplicates that would be removed
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Database upkeep
+    #[command(subcommand)]
+    Db(DbCommands),
+
+    /// Back up or restore the history (database and images)
+    #[command(subcommand)]
+    Backup(BackupCommands),
+
+    /// Read, change or check settings in the config file
+    #[command(subcommand)]
+    Config(ConfigCommands),
+
+    /// Set up ditox: ask for the common settings, create the database and
+    /// optionally install the autostart unit
+    Init {
+        /// Keep every setting as it is and only create the database
+        #[arg(long)]
+        defaults: bool,
+    },
+
+    /// Run optional database migrations. Without flags, shows which
+    /// optional indexes are built.
+    Migrate {
+        /// Build the trigram index so search also matches substrings
+        /// inside words (e.g. part of a hash or identifier)
+        #[arg(long, conflicts_with = "disable_trigram")]
+        enable_trigram: bool,
+
+        /// Drop the trigram index
+        #[arg(long)]
+        disable_trigram: bool,
+    },
+
     /// Manage collections
     #[command(subcommand)]
     Collection(CollectionCommands),
+
+    /// Manage key/value metadata attached to entries
+    #[command(subcommand)]
+    Meta(MetaCommands),
+
+    /// Manage entry aliases, usable anywhere an entry is expected
+    #[command(subcommand)]
+    Alias(AliasCommands),
+
+    /// Manage saved searches ("views"), also shown as tabs in the TUI
+    #[command(subcommand)]
+    Views(ViewCommands),
+
+    /// Keep new clips in the watcher's memory only, never on disk
+    #[command(subcommand)]
+    Incognito(IncognitoCommands),
+
+    /// Manage tags (search them with `tag:NAME`)
+    #[command(subcommand)]
+    Tag(TagCommands),
+
+    /// Development helpers
+    #[command(subcommand)]
+    Dev(DevCommands),
+}
+
+#[derive(Subcommand)]
+pub enum DevCommands {
+    /// Add synthetic clipboard history to the database
+    Seed {
+        /// Number of text clips
+        #[arg(long, default_value = "200")]
+        clips: usize,
+
+        /// Number of image clips
+        #[arg(long, default_value = "10")]
+        images: usize,
+
+        /// Number of distinct tags
+        #[arg(long, default_value = "8")]
+        tags: usize,
+
+        /// Spread clips over this many days
+        #[arg(long, default_value = "90")]
+        days: u32,
+
+        /// Random seed; the same seed produces the same history
+        #[arg(long, default_value = "1")]
+        seed: u64,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Check integrity, rebuild the search index, then ANALYZE, VACUUM and
+    /// checkpoint the WAL (skipped if the integrity check fails)
+    Maintain,
+}
+
+#[derive(Subcommand)]
+pub enum BackupCommands {
+    /// Write a consistent snapshot of the database plus its images to a
+    /// .tar.gz archive, safe to run while the watcher is capturing
+    Create {
+        /// Archive to write, e.g. ditox-backup.tar.gz
+        path: std::path::PathBuf,
+    },
+
+    /// Replace the history with an archive from `backup create`. The
+    /// archive is checked first, and the current history is saved under
+    /// backups/ in the data directory.
+    Restore {
+        /// Archive to restore
+        path: std::path::PathBuf,
+
+        /// Also check every image against its SHA-256
+        #[arg(long)]
+        verify: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Print the config file's path
+    Path,
+
+    /// Print a setting's effective value (the default if it isn't set)
+    Get {
+        /// Dotted key, e.g. ui.sort or ui.theme.selected
+        key: String,
+    },
+
+    /// Change a setting, keeping the rest of the file and its comments.
+    /// The file is only written if it still loads afterwards.
+    Set {
+        /// Dotted key, e.g. ui.sort
+        key: String,
+
+        /// New value, as TOML (true, 50, ["a"]) or a plain string
+        value: String,
+    },
+
+    /// Check the config file for syntax errors, bad values and unknown keys
+    Validate,
+}
+
+#[derive(Subcommand)]
+pub enum DaemonCommands {
+    /// Start `ditox watch` at login: a systemd user unit (Type=notify) on
+    /// Linux, a LaunchAgent on macOS, a Task Scheduler task on Windows
+    #[command(alias = "install-unit")]
+    Install {
+        /// Replace an existing unit, agent or task
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Stop starting the watcher at login (and stop it, where the service
+    /// manager runs it)
+    Uninstall,
+
+    /// Whether autostart is set up and the watcher is running
+    Status,
 }
 
 #[derive(Subcommand)]
@@ -179,3 +624,220 @@ pub enum CollectionCommands {
         json: bool,
     },
 }
+
+#[derive(Subcommand)]
+pub enum MetaCommands {
+    /// List all metadata of an entry
+    List {
+        /// Entry index (1-based) or ID
+        entry: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print a single metadata value
+    Get {
+        /// Entry index (1-based) or ID
+        entry: String,
+
+        /// Metadata key
+        key: String,
+    },
+
+    /// Set a metadata value
+    Set {
+        /// Entry index (1-based) or ID
+        entry: String,
+
+        /// Metadata key
+        key: String,
+
+        /// Metadata value
+        value: String,
+    },
+
+    /// Remove a metadata key
+    Remove {
+        /// Entry index (1-based) or ID
+        entry: String,
+
+        /// Metadata key
+        key: String,
+    },
+
+    /// Import metadata from a JSON object of string values (as printed by
+    /// `meta list --json`)
+    Import {
+        /// Entry index (1-based) or ID
+        entry: String,
+
+        /// JSON file to read, or "-" for stdin
+        file: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AliasCommands {
+    /// Point an alias at an entry
+    Set {
+        /// Alias name (must not be a number)
+        name: String,
+
+        /// Entry index (1-based) or ID
+        entry: String,
+    },
+
+    /// List all aliases
+    #[command(alias = "ls")]
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Remove an alias
+    #[command(alias = "rm")]
+    Remove {
+        /// Alias name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagCommands {
+    /// List all tags with their entry counts
+    #[command(alias = "ls")]
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Tag an entry
+    Add {
+        /// Entry index (1-based), alias, or UUID (or unambiguous UUID prefix)
+        entry: String,
+
+        /// Tag names
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+
+    /// Untag an entry
+    #[command(alias = "rm")]
+    Remove {
+        /// Entry index (1-based), alias, or UUID (or unambiguous UUID prefix)
+        entry: String,
+
+        /// Tag names
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+
+    /// Rename a tag on every entry
+    Rename {
+        /// Current tag name
+        old: String,
+
+        /// New tag name (merges into it if already in use)
+        new: String,
+    },
+
+    /// Merge tags into another one
+    Merge {
+        /// Tags to merge away
+        #[arg(required = true)]
+        from: Vec<String>,
+
+        /// Tag that remains
+        #[arg(long)]
+        into: String,
+    },
+
+    /// Remove a tag from every entry
+    Delete {
+        /// Tag name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ViewCommands {
+    /// Save a named search; every given filter must match
+    Save {
+        /// View name
+        name: String,
+
+        /// Search query, with the same syntax as `ditox search`
+        /// (e.g. 'deploy tag:work -is:fav after:7d')
+        #[arg(long)]
+        text: Option<String>,
+
+        /// Regular expression the content must match
+        #[arg(long, value_name = "PATTERN")]
+        regex: Option<String>,
+
+        /// Entry type ("text" or "image")
+        #[arg(long = "type", value_name = "TYPE")]
+        entry_type: Option<String>,
+
+        /// Only favorite entries
+        #[arg(long)]
+        favorites: bool,
+
+        /// Only entries in this collection (name or ID)
+        #[arg(long)]
+        collection: Option<String>,
+
+        /// Only entries created in the last N days
+        #[arg(long, value_name = "N")]
+        days: Option<u32>,
+    },
+
+    /// List saved views
+    #[command(alias = "list")]
+    Ls {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the entries a view matches
+    Run {
+        /// View name
+        name: String,
+
+        /// Maximum number of entries
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Delete a saved view
+    #[command(alias = "remove")]
+    Rm {
+        /// View name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum IncognitoCommands {
+    /// Start incognito: the watcher keeps capturing, but only in memory
+    On,
+
+    /// End incognito, discarding the clips captured meanwhile
+    Off {
+        /// Save the clips captured during incognito to history instead
+        #[arg(long)]
+        commit: bool,
+    },
+
+    /// Show whether incognito is on and how many clips it holds
+    Status,
+}
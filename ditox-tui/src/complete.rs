@@ -0,0 +1,275 @@
+//! Shell completions
+//!
+//! `ditox completions <shell>` prints a small script that hands the words
+//! typed so far to the hidden `ditox __complete --` command. That walks the
+//! clap command tree for subcommands, flags and enum values, and looks up
+//! clip IDs (with previews), aliases, tags and collections in the database,
+//! so `ditox copy <TAB>` offers actual clips.
+//!
+//! Candidates are printed one per line as `value<TAB>description`.
+
+use crate::cli::Cli;
+use clap::{Arg, ArgAction, CommandFactory, ValueEnum};
+use ditox_core::{Database, Result};
+
+/// Recent clips offered for entry arguments
+const CLIP_CANDIDATES: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+/// Completion script to source from the shell's startup file
+pub fn script(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash => BASH,
+        Shell::Zsh => ZSH,
+        Shell::Fish => FISH,
+        Shell::Powershell => POWERSHELL,
+    }
+}
+
+const BASH: &str = r#"_ditox() {
+    local IFS=$'\n'
+    COMPREPLY=($(ditox __complete -- "${COMP_WORDS[@]:1:COMP_CWORD}" 2>/dev/null | cut -f1))
+}
+complete -o default -F _ditox ditox
+"#;
+
+const ZSH: &str = r#"#compdef ditox
+_ditox() {
+    local -a items
+    local line
+    for line in "${(@f)$(ditox __complete -- "${(@)words[2,CURRENT]}" 2>/dev/null)}"; do
+        [[ -n $line ]] && items+=("${${line%%$'\t'*}//:/\\:}:${line#*$'\t'}")
+    done
+    if (( ${#items} )); then
+        _describe 'ditox' items
+    else
+        _files
+    fi
+}
+compdef _ditox ditox
+"#;
+
+const FISH: &str = r#"complete -c ditox -f -a '(ditox __complete -- (commandline -opc)[2..-1] (commandline -ct))'
+"#;
+
+const POWERSHELL: &str = r#"Register-ArgumentCompleter -Native -CommandName ditox -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $words = @($commandAst.CommandElements | Select-Object -Skip 1 | ForEach-Object { $_.ToString() })
+    if ($wordToComplete -eq '') { $words += '""' }
+    ditox __complete '--' @words 2>$null | ForEach-Object {
+        $value, $desc = $_ -split "`t", 2
+        if (-not $desc) { $desc = $value }
+        [System.Management.Automation.CompletionResult]::new($value, $value, 'ParameterValue', $desc)
+    }
+}
+"#;
+
+/// What a positional argument holds, judged by its name and subcommand
+#[derive(Debug, PartialEq, Eq)]
+enum ValueKind {
+    Clip,
+    Tag,
+    Collection,
+    Alias,
+    Other,
+}
+
+fn value_kind(path: &[String], arg: &str) -> ValueKind {
+    let first = path.first().map(String::as_str);
+    let last = path.last().map(String::as_str);
+    match (first, last, arg) {
+        (_, _, "entry") => ValueKind::Clip,
+        (Some("tag"), _, "tags" | "from" | "into" | "old") => ValueKind::Tag,
+        (Some("tag"), Some("delete"), "name") => ValueKind::Tag,
+        (Some("collection"), _, "target" | "collection") => ValueKind::Collection,
+        (Some("alias"), Some("remove"), "name") => ValueKind::Alias,
+        (_, _, "target") => ValueKind::Clip,
+        _ => ValueKind::Other,
+    }
+}
+
+/// Candidates for the last of `words` (the word being completed, possibly
+/// empty), given the words before it
+pub fn complete(
+    db: &Database,
+    id_length: usize,
+    words: &[String],
+) -> Result<Vec<(String, String)>> {
+    let (current, before) = match words.split_last() {
+        Some((current, before)) => (current.as_str(), before),
+        None => ("", &[][..]),
+    };
+
+    let mut cmd = Cli::command();
+    cmd.build();
+    let mut path = Vec::new();
+    let mut positionals = 0;
+    let mut pending: Option<Arg> = None;
+    for word in before {
+        if pending.take().is_some() {
+            continue;
+        }
+        if let Some(flag) = word.strip_prefix("--") {
+            let (name, inline) = match flag.split_once('=') {
+                Some((name, _)) => (name, true),
+                None => (flag, false),
+            };
+            let arg = cmd.get_arguments().find(|a| a.get_long() == Some(name));
+            pending = arg.filter(|a| !inline && takes_value(a)).cloned();
+        } else if word.starts_with('-') && word.len() > 1 {
+            let short = word.chars().last();
+            let arg = cmd.get_arguments().find(|a| a.get_short() == short);
+            pending = arg.filter(|a| word.len() == 2 && takes_value(a)).cloned();
+        } else if let Some(sub) = cmd.find_subcommand(word).cloned() {
+            path.push(sub.get_name().to_string());
+            cmd = sub;
+            positionals = 0;
+        } else {
+            positionals += 1;
+        }
+    }
+
+    let mut out: Vec<(String, String)> = Vec::new();
+    if let Some(arg) = pending {
+        out.extend(possible_values(&arg));
+        out.extend(db_values(
+            db,
+            id_length,
+            value_kind(&path, arg.get_id().as_str()),
+        )?);
+    } else if current.starts_with('-') {
+        for arg in cmd.get_arguments().filter(|a| !a.is_hide_set()) {
+            if let Some(long) = arg.get_long() {
+                let help = arg.get_help().map(|h| h.to_string()).unwrap_or_default();
+                out.push((format!("--{}", long), first_line(&help)));
+            }
+        }
+    } else if cmd.has_subcommands() {
+        for sub in cmd.get_subcommands().filter(|s| !s.is_hide_set()) {
+            let about = sub.get_about().map(|a| a.to_string()).unwrap_or_default();
+            out.push((sub.get_name().to_string(), first_line(&about)));
+        }
+    } else {
+        // Past the last positional, a repeatable one keeps taking values
+        let args: Vec<&Arg> = cmd.get_positionals().collect();
+        let arg = args.get(positionals).copied().or_else(|| {
+            args.last()
+                .copied()
+                .filter(|a| matches!(a.get_action(), ArgAction::Append))
+        });
+        if let Some(arg) = arg {
+            out.extend(possible_values(arg));
+            out.extend(db_values(
+                db,
+                id_length,
+                value_kind(&path, arg.get_id().as_str()),
+            )?);
+        }
+    }
+
+    out.retain(|(value, _)| value.starts_with(current));
+    Ok(out)
+}
+
+fn takes_value(arg: &Arg) -> bool {
+    arg.get_num_args().is_some_and(|n| n.takes_values())
+}
+
+fn possible_values(arg: &Arg) -> Vec<(String, String)> {
+    arg.get_possible_values()
+        .into_iter()
+        .filter(|v| !v.is_hide_set())
+        .map(|v| {
+            let help = v.get_help().map(|h| h.to_string()).unwrap_or_default();
+            (v.get_name().to_string(), first_line(&help))
+        })
+        .collect()
+}
+
+fn db_values(db: &Database, id_length: usize, kind: ValueKind) -> Result<Vec<(String, String)>> {
+    Ok(match kind {
+        ValueKind::Clip => {
+            let entries = db.get_page(0, CLIP_CANDIDATES)?;
+            let ids: Vec<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+            let prefixes = db.unique_id_prefixes(&ids, id_length.max(1))?;
+            let mut out: Vec<_> = prefixes
+                .into_iter()
+                .zip(&entries)
+                .map(|(id, entry)| (id, entry.preview(60)))
+                .collect();
+            out.extend(db.get_aliases()?);
+            out
+        }
+        ValueKind::Tag => db
+            .all_tags()?
+            .into_iter()
+            .map(|(name, count)| (name, count.to_string()))
+            .collect(),
+        ValueKind::Collection => db
+            .get_all_collections()?
+            .into_iter()
+            .map(|c| (c.name, String::new()))
+            .collect(),
+        ValueKind::Alias => db.get_aliases()?,
+        ValueKind::Other => Vec::new(),
+    })
+}
+
+fn first_line(text: &str) -> String {
+    text.lines().next().unwrap_or_default().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ditox_core::Entry;
+    use tempfile::TempDir;
+
+    fn sample_db(dir: &TempDir) -> Database {
+        let db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+        db.init_schema().unwrap();
+        let mut entry = Entry::new_text("deploy key".to_string());
+        entry.id = "abcdef12-0000".to_string();
+        db.insert(&entry).unwrap();
+        db.add_tag(&entry.id, "work").unwrap();
+        db
+    }
+
+    fn values(db: &Database, words: &[&str]) -> Vec<String> {
+        let words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        complete(db, 8, &words)
+            .unwrap()
+            .into_iter()
+            .map(|(value, _)| value)
+            .collect()
+    }
+
+    #[test]
+    fn test_subcommands_and_flags() {
+        let dir = TempDir::new().unwrap();
+        let db = sample_db(&dir);
+        let subs = values(&db, &["co"]);
+        assert!(subs.contains(&"copy".to_string()));
+        assert!(subs.contains(&"collection".to_string()));
+        assert!(!subs.contains(&"__complete".to_string()));
+
+        assert!(values(&db, &["copy", "--re"]).contains(&"--recent".to_string()));
+        assert_eq!(values(&db, &["pick", "--print-field", ""]), ["id", "text"]);
+    }
+
+    #[test]
+    fn test_clip_ids_and_tags_from_database() {
+        let dir = TempDir::new().unwrap();
+        let db = sample_db(&dir);
+        assert_eq!(values(&db, &["copy", ""]), ["abcdef12"]);
+        assert_eq!(values(&db, &["tag", "add", "abc", "w"]), ["work"]);
+        assert_eq!(values(&db, &["tag", "merge", ""]), ["work"]);
+    }
+}
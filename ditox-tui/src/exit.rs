@@ -0,0 +1,67 @@
+//! Process exit codes, so scripts can tell failures apart
+
+use ditox_core::DitoxError;
+
+/// Shown at the end of `ditox --help`
+pub const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0   Success
+  1   Any other error
+  2   Not found: no such entry, tag, collection or file
+  3   Store locked: the database is busy, or the watcher is already running
+  5   Migration needed: the database schema doesn't match this build
+  64  Invalid command line";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Failure = 1,
+    NotFound = 2,
+    Locked = 3,
+    MigrationNeeded = 5,
+    Usage = 64,
+}
+
+impl ExitCode {
+    pub fn of(error: &DitoxError) -> Self {
+        match error {
+            DitoxError::NotFound(_) => ExitCode::NotFound,
+            DitoxError::MigrationNeeded(_) => ExitCode::MigrationNeeded,
+            e if e.is_locked() => ExitCode::Locked,
+            _ => ExitCode::Failure,
+        }
+    }
+
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+/// Report a command-line error the way clap would, but with
+/// [`ExitCode::Usage`] instead of clap's 2, which means "not found" here.
+/// `--help` and `--version` still exit with 0.
+pub fn parse_error(error: clap::Error) -> ! {
+    if !error.use_stderr() {
+        error.exit();
+    }
+    let _ = error.print();
+    std::process::exit(ExitCode::Usage.code());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errors_map_to_codes() {
+        assert_eq!(ExitCode::of(&DitoxError::NotFound("x".into())).code(), 2);
+        assert_eq!(ExitCode::of(&DitoxError::Locked("x".into())).code(), 3);
+        assert_eq!(
+            ExitCode::of(&DitoxError::MigrationNeeded("x".into())).code(),
+            5
+        );
+        assert_eq!(
+            ExitCode::of(&DitoxError::Config("x".into())),
+            ExitCode::Failure
+        );
+    }
+}
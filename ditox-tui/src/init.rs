@@ -0,0 +1,115 @@
+//! `ditox init`: first-run setup
+//!
+//! Asks for the handful of settings most people change, showing the current
+//! value as the default, and writes answers through [`config_edit::set`] so
+//! an existing config keeps its comments. A bad answer is reported and asked
+//! again.
+
+use ditox_core::config_edit;
+use ditox_core::i18n::{t, tf};
+use ditox_core::Result;
+use serde_json::Value as Json;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+/// Settings asked about, with the i18n key of their question
+const QUESTIONS: &[(&str, &str)] = &[
+    ("general.max_entries", "cli.init.max_entries"),
+    ("storage.max_storage_mb", "cli.init.max_storage_mb"),
+    ("storage.prune_interval_mins", "cli.init.prune_interval"),
+    ("backup.auto", "cli.init.backup_auto"),
+    ("capture.primary_selection", "cli.init.primary_selection"),
+    ("ui.theme.selected", "cli.init.accent"),
+];
+
+/// Ask every question in [`QUESTIONS`] and save the answers to `path`.
+/// An empty answer (or end of input) keeps the current value.
+pub fn ask_settings(input: &mut impl BufRead, out: &mut impl Write, path: &Path) -> Result<()> {
+    for (key, question) in QUESTIONS {
+        loop {
+            let current = config_edit::get(path, key)?;
+            let shown = match &current {
+                Some(value) => config_edit::display(value)?,
+                None => String::new(),
+            };
+            write!(out, "{} [{}]: ", t(question), shown)?;
+            out.flush()?;
+            let answer = read_answer(input)?;
+            if answer.is_empty() {
+                break;
+            }
+            let answer = match (&current, yes_no(&answer)) {
+                (Some(Json::Bool(_)), Some(yes)) => yes.to_string(),
+                _ => answer,
+            };
+            match config_edit::set(path, key, &answer) {
+                Ok(()) => break,
+                Err(e) => writeln!(out, "{}", tf("cli.init.invalid", &[("error", &e)]))?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Ask a yes/no question; an empty or unrecognized answer is `default`
+pub fn confirm(
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+    question: &str,
+    default: bool,
+) -> Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    write!(out, "{} [{}]: ", question, hint)?;
+    out.flush()?;
+    Ok(yes_no(&read_answer(input)?).unwrap_or(default))
+}
+
+fn read_answer(input: &mut impl BufRead) -> Result<String> {
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+fn yes_no(answer: &str) -> Option<bool> {
+    match answer.to_ascii_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_answers_are_saved_and_bad_ones_asked_again() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        // max_entries, storage cap (bad, then good), prune interval kept,
+        // backups, primary selection as y/n, accent kept
+        let mut input = Cursor::new("1000\nlots\n200\n\nweekly\ny\n\n");
+        let mut out = Vec::new();
+        ask_settings(&mut input, &mut out, &path).unwrap();
+
+        let get = |key: &str| config_edit::get(&path, key).unwrap().unwrap();
+        assert_eq!(get("general.max_entries"), serde_json::json!(1000));
+        assert_eq!(get("storage.max_storage_mb"), serde_json::json!(200));
+        assert_eq!(get("storage.prune_interval_mins"), serde_json::json!(60));
+        assert_eq!(get("backup.auto"), serde_json::json!("weekly"));
+        assert_eq!(get("capture.primary_selection"), serde_json::json!(true));
+        assert!(String::from_utf8(out).unwrap().contains("[#7aa2f7]"));
+    }
+
+    #[test]
+    fn test_end_of_input_keeps_defaults() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        ask_settings(&mut Cursor::new(""), &mut Vec::new(), &path).unwrap();
+        assert!(!path.exists());
+        assert!(!confirm(&mut Cursor::new(""), &mut Vec::new(), "?", false).unwrap());
+        assert!(confirm(&mut Cursor::new("YES\n"), &mut Vec::new(), "?", false).unwrap());
+    }
+}
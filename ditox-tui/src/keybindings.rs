@@ -198,7 +198,9 @@ impl KeybindingResolver {
         // Operations
         self.bind_default("y", Action::Copy);
         self.bind_default("enter", Action::CopyAndQuit);
+        self.bind_default("alt+enter", Action::CopyAndType);
         self.bind_default("d", Action::Delete);
+        self.bind_default("u", Action::UndoDelete);
         self.bind_default("D", Action::ClearAll);
         self.bind_default("s", Action::ToggleFavorite);
         self.bind_default("r", Action::Refresh);
@@ -209,14 +211,19 @@ impl KeybindingResolver {
         self.bind_default("tab", Action::TogglePreview);
         self.bind_default("t", Action::ToggleExpanded);
         self.bind_default("?", Action::ToggleHelp);
+        self.bind_default("i", Action::ShowDetails);
 
         // Multi-select
         self.bind_default("m", Action::ToggleMultiSelect);
         self.bind_default("space", Action::SelectCurrent);
         self.bind_default("v", Action::SelectAll);
+        self.bind_default("M", Action::MergeSelected);
 
         // Annotations
         self.bind_default("n", Action::EditAnnotation);
+        self.bind_default("e", Action::EditInEditor);
+        self.bind_default("E", Action::EditInEditorAsNew);
+        self.bind_default("T", Action::EditTags);
 
         // Search modes
         self.bind_default("ctrl+r", Action::EnterRegexSearch);
@@ -228,6 +235,7 @@ impl KeybindingResolver {
 
         // Preview modes
         self.bind_default("p", Action::CyclePreviewMode);
+        self.bind_default("o", Action::CycleSort);
         self.bind_default("L", Action::ToggleLineNumbers);
 
         // Quick snippet slots (1-9)
@@ -241,6 +249,17 @@ impl KeybindingResolver {
         self.bind_default("8", Action::QuickSlot8);
         self.bind_default("9", Action::QuickSlot9);
 
+        // Saved views
+        self.bind_default("alt+1", Action::View1);
+        self.bind_default("alt+2", Action::View2);
+        self.bind_default("alt+3", Action::View3);
+        self.bind_default("alt+4", Action::View4);
+        self.bind_default("alt+5", Action::View5);
+        self.bind_default("alt+6", Action::View6);
+        self.bind_default("alt+7", Action::View7);
+        self.bind_default("alt+8", Action::View8);
+        self.bind_default("alt+9", Action::View9);
+
         // Tab navigation
         self.bind_default("[", Action::PrevTab);
         self.bind_default("]", Action::NextTab);
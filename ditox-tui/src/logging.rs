@@ -0,0 +1,273 @@
+//! Log setup
+//!
+//! Logs go to stderr at the level given by `--log-level` (or `RUST_LOG`),
+//! as text or, with `--log-format json`, one JSON object per line. The
+//! watcher also writes them to `logs/watcher.log` in the data directory,
+//! rotated by size, so `ditox doctor --logs` can show recent errors of a
+//! watcher running in the background.
+
+use clap::ValueEnum;
+use ditox_core::{Database, Result};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry};
+
+/// Name of the watcher's log file inside [`log_dir`]
+pub const LOG_FILE: &str = "watcher.log";
+
+/// Size at which the log file is rotated
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Rotated files kept next to the current one (`watcher.log.1`, ...)
+const KEEP_ROTATED: usize = 3;
+
+/// Default filter when neither `--log-level` nor `RUST_LOG` is set
+const DEFAULT_FILTER: &str = "ditox=info";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Directory holding the watcher's log files
+pub fn log_dir() -> Result<PathBuf> {
+    Ok(Database::get_state_dir()?.join("logs"))
+}
+
+/// Install the global subscriber. `level` is a level (`debug`) or a full
+/// filter directive (`ditox_core=trace`); with `to_file` logs are also
+/// written to the rotating log file.
+pub fn init(level: Option<&str>, format: LogFormat, to_file: bool) {
+    let filter = || match level {
+        // Target prefixes match, so `ditox` covers `ditox_core` too
+        Some(level) if !level.contains('=') => EnvFilter::new(format!("ditox={level}")),
+        Some(directives) => EnvFilter::new(directives),
+        None => {
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER))
+        }
+    };
+
+    let mut layers: Vec<Box<dyn Layer<Registry> + Send + Sync>> =
+        vec![layer(format, io::stderr, true)
+            .with_filter(filter())
+            .boxed()];
+    if to_file {
+        match log_dir().and_then(|dir| Ok(RotatingFile::open(&dir.join(LOG_FILE))?)) {
+            Ok(file) => layers.push(
+                layer(format, Mutex::new(file), false)
+                    .with_filter(filter())
+                    .boxed(),
+            ),
+            Err(e) => eprintln!("Not writing a log file: {}", e),
+        }
+    }
+    tracing_subscriber::registry().with(layers).init();
+}
+
+fn layer<W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+    W: for<'a> tracing_subscriber::fmt::MakeWriter<'a> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_ansi(ansi);
+    match format {
+        LogFormat::Text => layer.boxed(),
+        LogFormat::Json => layer.event_format(JsonFormat).boxed(),
+    }
+}
+
+/// Formats each event as `{"timestamp", "level", "target", "fields"}`
+struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut fields = serde_json::Map::new();
+        event.record(&mut JsonFields(&mut fields));
+        let meta = event.metadata();
+        let line = serde_json::json!({
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+            "level": meta.level().as_str(),
+            "target": meta.target(),
+            "fields": fields,
+        });
+        writeln!(writer, "{}", line)
+    }
+}
+
+struct JsonFields<'a>(&'a mut serde_json::Map<String, serde_json::Value>);
+
+impl Visit for JsonFields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+}
+
+/// Append-only log file that moves itself to `{path}.1` (shifting older
+/// ones up to `{path}.{KEEP_ROTATED}`) once it grows past a size limit
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+    keep: usize,
+}
+
+impl RotatingFile {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Self::with_limits(path, MAX_LOG_BYTES, KEEP_ROTATED)
+    }
+
+    fn with_limits(path: &Path, max_bytes: u64, keep: usize) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            written: file.metadata()?.len(),
+            file,
+            max_bytes,
+            keep,
+        })
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        for n in (1..self.keep).rev() {
+            let from = self.rotated(n);
+            if from.exists() {
+                fs::rename(&from, self.rotated(n + 1))?;
+            }
+        }
+        if self.keep > 0 {
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// The last `count` warning and error lines of the watcher log, oldest
+/// first, reading into the newest rotated file if needed
+pub fn recent_problems(count: usize) -> Result<Vec<String>> {
+    let dir = log_dir()?;
+    let mut lines: Vec<String> = Vec::new();
+    for name in [format!("{}.1", LOG_FILE), LOG_FILE.to_string()] {
+        match fs::read_to_string(dir.join(name)) {
+            Ok(text) => lines.extend(text.lines().filter(|l| is_problem(l)).map(String::from)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    let skip = lines.len().saturating_sub(count);
+    Ok(lines.split_off(skip))
+}
+
+/// Warning or error line in either log format
+fn is_problem(line: &str) -> bool {
+    ["ERROR", "WARN"].iter().any(|level| {
+        line.contains(&format!(" {} ", level)) || line.contains(&format!("\"level\":\"{}\"", level))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotating_file_keeps_limited_history() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("test.log");
+        let mut file = RotatingFile::with_limits(&path, 10, 2).unwrap();
+        for line in ["aaaaaaaa\n", "bbbbbbbb\n", "cccccccc\n", "dddddddd\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), "dddddddd\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("test.log.1")).unwrap(),
+            "cccccccc\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("test.log.2")).unwrap(),
+            "bbbbbbbb\n"
+        );
+        assert!(!dir.path().join("test.log.3").exists());
+    }
+
+    #[test]
+    fn test_is_problem() {
+        assert!(is_problem(
+            "2026-01-01T00:00:00Z ERROR ditox_core::watcher: boom"
+        ));
+        assert!(is_problem(r#"{"level":"WARN","target":"x"}"#));
+        assert!(!is_problem(
+            "2026-01-01T00:00:00Z  INFO ditox: ERRORS are fine"
+        ));
+    }
+}
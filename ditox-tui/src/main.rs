@@ -1,58 +1,321 @@
 mod cli;
+mod complete;
+mod exit;
+mod init;
 mod keybindings;
+mod logging;
+mod output;
+mod serve;
+mod transform;
 mod ui;
 
 use clap::Parser;
-use cli::{Cli, CollectionCommands, Commands};
+use cli::{
+    AliasCommands, BackupCommands, Cli, CollectionCommands, Commands, ConfigCommands,
+    DaemonCommands, DbCommands, DevCommands, ExportFormat, ExportImageMode, ImportSource,
+    IncognitoCommands, MetaCommands, OnConflict, PrintField, TagCommands, ViewCommands,
+};
+use ditox_core::autostart::Backend;
+use ditox_core::backup;
+use ditox_core::config_edit;
+use ditox_core::confirm::{ConfirmStyle, CONFIRM_PHRASE};
+use ditox_core::db::{SchemaCompat, SCHEMA_VERSION};
+use ditox_core::dedupe;
+use ditox_core::export;
+use ditox_core::hooks::{self, HookEvent};
+use ditox_core::i18n::{self, t, tf};
+use ditox_core::import::{self, ImportFormat};
+use ditox_core::incognito::{self, IncognitoState};
+use ditox_core::merge;
+use ditox_core::ndjson::{self, ConflictPolicy, ImagePayload};
+use ditox_core::query::Ago;
+use ditox_core::seed::{self, SeedOptions};
+use ditox_core::watcher;
 use ditox_core::{
-    Clipboard, Collection, Config, Database, DitoxError, Entry, EntryType, Result, Watcher,
+    Clipboard, Collection, Config, Database, DitoxError, Entry, EntryType, Query, Result,
+    SearchHit, Watcher,
 };
+use exit::ExitCode;
 use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
 use nucleo_matcher::{Config as MatcherConfig, Matcher};
-use tracing_subscriber::EnvFilter;
+use output::OutputFormat;
+use std::collections::HashMap;
 
 fn main() {
     if let Err(e) = run() {
         eprintln!("Error: {}", e);
-        std::process::exit(1);
+        std::process::exit(ExitCode::of(&e).code());
     }
 }
 
 fn run() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("ditox=info")),
-        )
-        .init();
+    let cli = Cli::try_parse().unwrap_or_else(|e| exit::parse_error(e));
+    let output = cli.output;
+
+    let demo_dir = cli
+        .demo
+        .then(|| std::env::temp_dir().join(format!("ditox-demo-{}", std::process::id())));
+    if let Some(dir) = &demo_dir {
+        Database::override_data_dir(dir.clone());
+    }
+    if cli.osc52 {
+        ditox_core::osc52::force(true);
+    }
+    // Custom directories must be in place before anything resolves a path
+    if demo_dir.is_none() {
+        if let Ok(config) = Config::load() {
+            Database::apply_storage_config(&config.storage);
+        }
+    }
+    let migrated = Database::migrate_state_files();
+
+    // The watcher usually runs in the background, so it also logs to a file
+    let to_file = matches!(cli.command, Some(Commands::Watch { .. }));
+    logging::init(cli.log_level.as_deref(), cli.log_format, to_file);
+    match migrated {
+        Ok(moved) if !moved.is_empty() => {
+            tracing::info!("Moved {} to the state directory", moved.join(", "))
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to move state files: {}", e),
+    }
+
+    // Before loading the config, so `validate` can report why it doesn't
+    let command = match cli.command {
+        Some(Commands::Config(subcmd)) => {
+            i18n::init(Config::load().ok().and_then(|c| c.ui.locale).as_deref());
+            return cmd_config(subcmd);
+        }
+        Some(Commands::Init { defaults }) => {
+            i18n::init(Config::load().ok().and_then(|c| c.ui.locale).as_deref());
+            return cmd_init(defaults);
+        }
+        command => command,
+    };
 
-    let cli = Cli::parse();
     let config = Config::load()?;
-    let mut db = Database::open()?;
+    i18n::init(config.ui.locale.as_deref());
+
+    if let Some(demo_dir) = demo_dir {
+        let result = Database::open().and_then(|db| {
+            db.init_schema()?;
+            seed::seed(&db, &SeedOptions::default())?;
+            run_command(command, db, config, output)
+        });
+        let _ = std::fs::remove_dir_all(&demo_dir);
+        return result;
+    }
+
+    let db = Database::open()?;
+    if let SchemaCompat::Newer(version) = db.schema_compat() {
+        if reads_only(&command) {
+            eprintln!(
+                "{}",
+                tf(
+                    "cli.schema.read_only",
+                    &[("version", &version), ("supported", &SCHEMA_VERSION)]
+                )
+            );
+            let db = Database::open_read_only(&Database::get_db_path()?)?;
+            return run_command(command, db, config, output);
+        }
+    }
     db.init_schema()?;
+    run_command(command, db, config, output)
+}
+
+/// Commands that can run against a database from a newer ditox, which is
+/// opened read-only instead of being migrated
+fn reads_only(command: &Option<Commands>) -> bool {
+    matches!(
+        command,
+        Some(
+            Commands::List { .. }
+                | Commands::Get { .. }
+                | Commands::Search { .. }
+                | Commands::Count
+                | Commands::Status
+                | Commands::Stats { .. }
+                | Commands::Export { .. }
+                | Commands::Migrate {
+                    enable_trigram: false,
+                    disable_trigram: false,
+                }
+                | Commands::Tag(TagCommands::List { .. })
+        )
+    )
+}
 
-    match cli.command {
+fn run_command(
+    command: Option<Commands>,
+    mut db: Database,
+    config: Config,
+    output: OutputFormat,
+) -> Result<()> {
+    match command {
         None => run_tui(db, config),
-        Some(Commands::Watch) => run_watcher(db, config),
+        Some(Commands::Watch { replace, print }) => run_watcher(db, config, replace, print),
+        Some(Commands::Doctor {
+            logs,
+            lines,
+            daemon,
+        }) => {
+            if daemon {
+                cmd_doctor_daemon(&db)
+            } else {
+                cmd_doctor(&db, output, logs, lines)
+            }
+        }
+        Some(Commands::Daemon(subcmd)) => cmd_daemon(subcmd),
+        Some(Commands::Pick {
+            print,
+            print_field,
+            query,
+            first,
+            theme_edit,
+        }) => cmd_pick(
+            db,
+            config,
+            print,
+            print_field,
+            ui::PickOptions {
+                query,
+                first,
+                theme_edit,
+            },
+        ),
         Some(Commands::List {
             limit,
             json,
             favorites,
-        }) => cmd_list(&db, limit, json, favorites),
-        Some(Commands::Get { target, json }) => cmd_get(&db, &target, json),
-        Some(Commands::Search { query, limit, json }) => cmd_search(&db, &query, limit, json),
-        Some(Commands::Copy { target }) => cmd_copy(&db, &target),
-        Some(Commands::Delete { target }) => cmd_delete(&mut db, &target),
-        Some(Commands::Favorite { target }) => cmd_favorite(&db, &target),
-        Some(Commands::Clear { confirm }) => cmd_clear(&mut db, confirm),
+            full_ids,
+        }) => {
+            let id_len = id_display_len(&config, full_ids);
+            cmd_list(&db, output.or_json(json), limit, favorites, id_len)
+        }
+        Some(Commands::Get {
+            target,
+            json,
+            raw,
+            out,
+        }) => cmd_get(&db, output.or_json(json), &target, raw, out.as_deref()),
+        Some(Commands::Search {
+            query,
+            regex,
+            limit,
+            json,
+            full_ids,
+        }) => cmd_search(
+            &db,
+            query.as_deref(),
+            regex.as_deref(),
+            limit,
+            output.or_json(json),
+            id_display_len(&config, full_ids),
+        ),
+        Some(Commands::Copy {
+            recent: Some(n),
+            type_text,
+            transform,
+            ..
+        }) => cmd_copy_recent(&db, &config, n, type_text, &transform),
+        Some(Commands::Copy {
+            target,
+            type_text,
+            transform,
+            ..
+        }) => cmd_copy(
+            &db,
+            &config,
+            target.as_deref().unwrap_or_default(),
+            type_text,
+            &transform,
+        ),
+        Some(Commands::Delete { target }) => cmd_delete(&mut db, &config, output, &target),
+        Some(Commands::Favorite { target }) => cmd_favorite(&db, output, &target),
+        Some(Commands::Clear { confirm }) => cmd_clear(&mut db, confirm, config.confirm.clear_all),
         Some(Commands::Count) => cmd_count(&db),
         Some(Commands::Status) => cmd_status(&db),
-        Some(Commands::Stats { json }) => cmd_stats(&db, json),
+        Some(Commands::Serve {
+            addr,
+            token,
+            writable,
+        }) => serve::serve(
+            &mut db,
+            &serve::ServeOptions {
+                addr,
+                token: token.unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string()),
+                writable,
+                hooks: config.hooks.clone(),
+            },
+        ),
+        Some(Commands::Completions { shell }) => {
+            print!("{}", complete::script(shell));
+            Ok(())
+        }
+        Some(Commands::Complete { words }) => {
+            for (value, description) in complete::complete(&db, config.ui.id_length, &words)? {
+                println!("{}\t{}", value, description);
+            }
+            Ok(())
+        }
+        Some(Commands::Stats { json, prometheus }) => {
+            cmd_stats(&db, output.or_json(json), prometheus)
+        }
         Some(Commands::Repair {
             dry_run,
             fix_hashes,
         }) => cmd_repair(&mut db, dry_run, fix_hashes),
+        Some(Commands::ExportImages { dir, jobs, verify }) => {
+            cmd_export_images(&db, &dir, jobs, verify)
+        }
+        Some(Commands::ImportImages { dir }) => cmd_import_images(&dir),
+        Some(Commands::MergeDb { path }) => cmd_merge_db(&db, &path),
+        Some(Commands::Export {
+            stdout: _,
+            out,
+            format,
+            images,
+        }) => cmd_export(&db, out.as_deref(), format, images),
+        Some(Commands::Import {
+            path,
+            format,
+            on_conflict,
+        }) => cmd_import(&db, &path, format, on_conflict),
+        Some(Commands::Dedupe { dry_run }) => cmd_dedupe(&db, dry_run),
+        Some(Commands::Db(DbCommands::Maintain)) => cmd_db_maintain(&mut db),
+        Some(Commands::Backup(subcmd)) => cmd_backup(&mut db, subcmd),
+        Some(Commands::Config(subcmd)) => cmd_config(subcmd),
+        Some(Commands::Init { defaults }) => cmd_init(defaults),
+        Some(Commands::Migrate {
+            enable_trigram,
+            disable_trigram,
+        }) => cmd_migrate(&mut db, output, enable_trigram, disable_trigram),
         Some(Commands::Collection(subcmd)) => cmd_collection(&db, subcmd),
+        Some(Commands::Meta(subcmd)) => cmd_meta(&mut db, subcmd),
+        Some(Commands::Alias(subcmd)) => cmd_alias(&db, subcmd),
+        Some(Commands::Incognito(subcmd)) => cmd_incognito(subcmd),
+        Some(Commands::Views(subcmd)) => {
+            let id_len = id_display_len(&config, false);
+            cmd_views(&db, subcmd, id_len)
+        }
+        Some(Commands::Tag(subcmd)) => cmd_tag(&mut db, output, subcmd),
+        Some(Commands::Dev(DevCommands::Seed {
+            clips,
+            images,
+            tags,
+            days,
+            seed,
+        })) => cmd_seed(
+            &db,
+            SeedOptions {
+                clips,
+                images,
+                tags,
+                days,
+                seed,
+            },
+        ),
     }
 }
 
@@ -60,86 +323,566 @@ fn run_tui(db: Database, config: Config) -> Result<()> {
     ui::run(db, config)
 }
 
-fn run_watcher(db: Database, config: Config) -> Result<()> {
+fn run_watcher(db: Database, config: Config, replace: bool, print: bool) -> Result<()> {
+    if replace && watcher::stop_running_watcher()? {
+        // Keep stdout to clips when printing them
+        if print {
+            eprintln!("{}", t("cli.watcher_stopped"));
+        } else {
+            println!("{}", t("cli.watcher_stopped"));
+        }
+    }
     let mut watcher = Watcher::new(db, config);
+    if print {
+        watcher.on_capture(|db, entry| {
+            let mut out = std::io::stdout().lock();
+            ndjson::write_entry(db, &mut out, entry, ImagePayload::Reference)?;
+            std::io::Write::flush(&mut out)?;
+            Ok(())
+        });
+    }
     watcher.run()
 }
 
-fn cmd_list(db: &Database, limit: usize, json: bool, favorites_only: bool) -> Result<()> {
+/// One line of `ditox doctor`
+#[derive(serde::Serialize)]
+struct Check {
+    check: &'static str,
+    status: &'static str,
+    detail: String,
+}
+
+fn cmd_doctor(db: &Database, output: OutputFormat, logs: bool, lines: usize) -> Result<()> {
+    if logs {
+        let problems = logging::recent_problems(lines)?;
+        return output::print(output, &problems, || {
+            if problems.is_empty() {
+                println!("{}", t("cli.doctor.no_log_problems"));
+            }
+            for line in &problems {
+                println!("{}", line);
+            }
+            Ok(())
+        });
+    }
+
+    const OK: &str = "ok";
+    const FAIL: &str = "fail";
+    let mut checks = Vec::new();
+    let mut check = |status: &'static str, what: &'static str, detail: String| {
+        checks.push(Check {
+            // `cli.doctor.data_dir` -> `data_dir`
+            check: what.rsplit('.').next().unwrap_or(what),
+            status,
+            detail,
+        });
+    };
+
+    let data_dir = Database::get_data_dir()?;
+    check(
+        if data_dir.is_dir() { OK } else { FAIL },
+        "cli.doctor.data_dir",
+        data_dir.display().to_string(),
+    );
+    match db.count() {
+        Ok(count) => check(
+            OK,
+            "cli.doctor.database",
+            tf("cli.doctor.entries", &[("count", &count)]),
+        ),
+        Err(e) => check(FAIL, "cli.doctor.database", e.to_string()),
+    }
+    match Clipboard::list_types() {
+        Ok(_) => check(OK, "cli.doctor.clipboard", t("cli.doctor.clipboard_ok")),
+        Err(e) => check(FAIL, "cli.doctor.clipboard", e.to_string()),
+    }
+    match watcher::read_watcher_health() {
+        Some(health) if health.is_healthy() => {
+            check(OK, "cli.doctor.watcher", t("cli.doctor.watcher_ok"))
+        }
+        Some(health) => check(
+            FAIL,
+            "cli.doctor.watcher",
+            tf(
+                "cli.doctor.watcher_failing",
+                &[(
+                    "error",
+                    &health
+                        .last_error
+                        .unwrap_or_else(|| t("cli.doctor.not_responding")),
+                )],
+            ),
+        ),
+        None => check(FAIL, "cli.doctor.watcher", t("cli.doctor.watcher_missing")),
+    }
+    let writers = watcher::capturing_processes();
+    if writers.len() > 1 {
+        let list = writers
+            .iter()
+            .map(|(pid, command)| format!("{} (PID {})", command, pid))
+            .collect::<Vec<_>>()
+            .join(", ");
+        check(
+            "warn",
+            "cli.doctor.writers",
+            tf("cli.doctor.writers_many", &[("list", &list)]),
+        );
+    } else {
+        check(OK, "cli.doctor.writers", t("cli.doctor.writers_ok"));
+    }
+    let log_file = logging::log_dir()?.join(logging::LOG_FILE);
+    let problems = logging::recent_problems(usize::MAX)?.len();
+    check(
+        if problems == 0 { OK } else { "warn" },
+        "cli.doctor.logs",
+        tf(
+            "cli.doctor.log_summary",
+            &[("path", &log_file.display()), ("count", &problems)],
+        ),
+    );
+    output::print(output, &checks, || {
+        for check in &checks {
+            let mark = match check.status {
+                OK => " ok ",
+                FAIL => "FAIL",
+                other => other,
+            };
+            let label = t(&format!("cli.doctor.{}", check.check));
+            println!("[{}] {:<12} {}", mark, label, check.detail);
+        }
+        Ok(())
+    })
+}
+
+/// Detailed report of the database and the running watcher
+fn cmd_doctor_daemon(db: &Database) -> Result<()> {
+    let local = |t: chrono::DateTime<chrono::Utc>| {
+        t.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    };
+
+    let db_path = Database::get_db_path()?;
+    let stats = db.get_stats()?;
+    let mut wal_path = db_path.clone().into_os_string();
+    wal_path.push("-wal");
+    let wal_bytes = std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+
+    println!("{}", t("cli.daemon.database"));
+    println!("{}", tf("cli.daemon.path", &[("path", &db_path.display())]));
+    println!(
+        "{}",
+        tf(
+            "cli.daemon.schema",
+            &[
+                ("version", &db.schema_version().unwrap_or(0)),
+                ("current", &ditox_core::db::SCHEMA_VERSION),
+            ],
+        )
+    );
+    println!(
+        "{}",
+        tf(
+            "cli.daemon.journal",
+            &[
+                ("mode", &db.journal_mode()?),
+                ("wal", &ditox_core::stats::format_bytes(wal_bytes)),
+            ],
+        )
+    );
+    println!(
+        "{}",
+        tf(
+            "cli.daemon.size",
+            &[(
+                "size",
+                &ditox_core::stats::format_bytes(stats.db_size_bytes)
+            )],
+        )
+    );
+    println!(
+        "{}",
+        tf(
+            "cli.daemon.clips",
+            &[
+                ("text", &stats.text_count),
+                ("images", &stats.image_count),
+                ("favorites", &stats.favorites_count),
+            ],
+        )
+    );
+    println!(
+        "{}",
+        tf(
+            "cli.daemon.images",
+            &[(
+                "size",
+                &ditox_core::stats::format_bytes(stats.images_size_bytes),
+            )],
+        )
+    );
+
+    println!();
+    println!("{}", t("cli.daemon.watcher"));
+    if !watcher::is_watcher_running() {
+        println!("{}", t("cli.daemon.not_running"));
+        return Ok(());
+    }
+    let pid = std::fs::read_to_string(watcher::get_pid_file_path()?).unwrap_or_default();
+    let pid = pid.trim();
+    let Some(health) = watcher::read_watcher_health() else {
+        println!("{}", tf("cli.daemon.no_health", &[("pid", &pid)]));
+        return Ok(());
+    };
+    let status = if health.is_healthy() {
+        t("cli.daemon.healthy")
+    } else if health.consecutive_errors > 0 {
+        t("cli.daemon.failing")
+    } else {
+        t("cli.daemon.not_responding")
+    };
+    println!(
+        "{}",
+        tf("cli.daemon.status", &[("pid", &pid), ("status", &status)])
+    );
+    if !health.backend.is_empty() {
+        println!(
+            "{}",
+            tf("cli.daemon.backend", &[("backend", &health.backend)])
+        );
+    }
+    let capture = if health.incognito {
+        tf(
+            "cli.daemon.capture_incognito",
+            &[("count", &health.incognito_clips)],
+        )
+    } else {
+        t("cli.daemon.capture_active")
+    };
+    println!("{}", tf("cli.daemon.capture", &[("state", &capture)]));
+    let captured_at = health
+        .last_capture
+        .map(local)
+        .unwrap_or_else(|| t("cli.daemon.none_yet"));
+    println!(
+        "{}",
+        tf("cli.daemon.captured_at", &[("time", &captured_at)])
+    );
+    if let Some(at) = health.last_ok {
+        println!("{}", tf("cli.daemon.last_poll", &[("time", &local(at))]));
+    }
+    println!(
+        "{}",
+        tf(
+            "cli.daemon.counters",
+            &[
+                ("captured", &health.captured),
+                ("duplicates", &health.duplicates),
+                ("skipped", &health.skipped),
+                ("errors", &health.poll_errors),
+                ("recoveries", &health.recoveries),
+            ],
+        )
+    );
+    if let Some(error) = &health.last_error {
+        println!("{}", tf("cli.daemon.last_error", &[("error", error)]));
+    }
+    if let Some(at) = health.last_prune {
+        println!(
+            "{}",
+            tf(
+                "cli.daemon.last_prune",
+                &[("time", &local(at)), ("count", &health.last_prune_removed)],
+            )
+        );
+    }
+    if let Some(at) = health.last_backup {
+        println!("{}", tf("cli.daemon.last_backup", &[("time", &local(at))]));
+    }
+    Ok(())
+}
+
+fn cmd_daemon(subcmd: DaemonCommands) -> Result<()> {
+    let backend = Backend::current()?;
+    let location = backend.location()?;
+    match subcmd {
+        DaemonCommands::Install { force } => cmd_install_autostart(backend, force)?,
+        DaemonCommands::Uninstall => {
+            if backend.uninstall()? {
+                println!("{}", tf("cli.unit.removed", &[("path", &location)]));
+            } else {
+                println!(
+                    "{}",
+                    tf("cli.unit.not_installed", &[("backend", &backend.name())])
+                );
+            }
+        }
+        DaemonCommands::Status => {
+            if backend.is_installed()? {
+                println!(
+                    "{}",
+                    tf(
+                        "cli.unit.installed",
+                        &[("backend", &backend.name()), ("path", &location)]
+                    )
+                );
+            } else {
+                println!(
+                    "{}",
+                    tf("cli.unit.not_installed", &[("backend", &backend.name())])
+                );
+            }
+            if watcher::is_watcher_running() {
+                let pid =
+                    std::fs::read_to_string(watcher::get_pid_file_path()?).unwrap_or_default();
+                println!("{}", tf("cli.unit.running", &[("pid", &pid.trim())]));
+            } else {
+                println!("{}", t("cli.unit.stopped"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn cmd_install_autostart(backend: Backend, force: bool) -> Result<()> {
+    let location = backend.location()?;
+    if !force && backend.is_installed()? {
+        return Err(DitoxError::Other(tf(
+            "cli.unit.exists",
+            &[("path", &location)],
+        )));
+    }
+    backend.install(&std::env::current_exe()?)?;
+    println!("{}", tf("cli.unit.written", &[("path", &location)]));
+    let hint = match backend {
+        Backend::Systemd => "cli.unit.enable_hint",
+        Backend::Launchd => "cli.unit.enable_hint_launchd",
+        Backend::TaskScheduler => "cli.unit.enable_hint_windows",
+    };
+    println!("{}", tf(hint, &[("path", &location)]));
+    Ok(())
+}
+
+fn cmd_list(
+    db: &Database,
+    output: OutputFormat,
+    limit: usize,
+    favorites_only: bool,
+    id_len: Option<usize>,
+) -> Result<()> {
     let mut entries = db.get_all(limit)?;
 
     if favorites_only {
         entries.retain(|e| e.favorite);
     }
 
-    if json {
-        let json_output = serde_json::to_string_pretty(&entries)
-            .map_err(|e| DitoxError::Other(format!("JSON serialization error: {}", e)))?;
-        println!("{}", json_output);
-    } else {
+    output::print(output, &entries, || {
         if entries.is_empty() {
-            println!("No clipboard entries found.");
+            println!("{}", t("cli.no_entries"));
             return Ok(());
         }
+        print_entry_table(db, &entries, id_len)
+    })
+}
+
+/// Print entries as the `list` table: index, ID, type, favorite, preview, age
+fn print_entry_table(db: &Database, entries: &[Entry], id_len: Option<usize>) -> Result<()> {
+    let ids = display_ids(db, entries, id_len)?;
+    let id_width = ids.iter().map(|id| id.len()).max().unwrap_or(0).max(2);
+
+    println!(
+        "{:>3} │ {:<id_width$} │ {:^4} │ {:^3} │ {:<40} │ {:>6}",
+        "#",
+        t("cli.column.id"),
+        t("cli.column.type"),
+        t("cli.column.favorite"),
+        t("cli.column.content"),
+        t("cli.column.age")
+    );
+    println!(
+        "────┼{}┼──────┼─────┼──────────────────────────────────────────┼────────",
+        "─".repeat(id_width + 2)
+    );
 
+    for (i, entry) in entries.iter().enumerate() {
         println!(
-            "{:>3} │ {:^4} │ {:^3} │ {:<40} │ {:>6}",
-            "#", "Type", "Fav", "Content", "Age"
+            "{:>3} │ {:<id_width$} │ {:^4} │ {:^3} │ {:<40} │ {:>6}",
+            i + 1,
+            ids[i],
+            entry.entry_type.short(),
+            if entry.favorite { "⭐" } else { "" },
+            entry.preview(40),
+            entry.relative_time()
         );
-        println!("────┼──────┼─────┼──────────────────────────────────────────┼────────");
-
-        for (i, entry) in entries.iter().enumerate() {
-            println!(
-                "{:>3} │ {:^4} │ {:^3} │ {:<40} │ {:>6}",
-                i + 1,
-                entry.entry_type.short(),
-                if entry.favorite { "⭐" } else { "" },
-                entry.preview(40),
-                entry.relative_time()
-            );
-        }
     }
 
     Ok(())
 }
 
-fn cmd_copy(db: &Database, target: &str) -> Result<()> {
+/// Minimum ID prefix length for listings, or `None` to print full IDs
+fn id_display_len(config: &Config, full_ids: bool) -> Option<usize> {
+    (!full_ids).then_some(config.ui.id_length.max(1))
+}
+
+/// IDs to print for a listing: full IDs, or the shortest prefixes of at
+/// least `min_len` chars that are unambiguous across the whole database
+fn display_ids<'a>(
+    db: &Database,
+    entries: impl IntoIterator<Item = &'a Entry>,
+    min_len: Option<usize>,
+) -> Result<Vec<String>> {
+    let ids: Vec<&str> = entries.into_iter().map(|e| e.id.as_str()).collect();
+    match min_len {
+        Some(min_len) => db.unique_id_prefixes(&ids, min_len),
+        None => Ok(ids.into_iter().map(String::from).collect()),
+    }
+}
+
+fn cmd_copy(
+    db: &Database,
+    config: &Config,
+    target: &str,
+    type_text: bool,
+    transforms: &[String],
+) -> Result<()> {
     let entry = resolve_target(db, target)?;
 
     match entry {
         Some(entry) => {
             match entry.entry_type {
                 EntryType::Text => {
-                    Clipboard::set_text(&entry.content)?;
-                    println!("Copied: {}", entry.preview(50));
+                    let text = transform::Registry::new(&config.transforms)
+                        .apply(transforms, &entry.content)?;
+                    Clipboard::set_text(&text)?;
+                    println!("{}", tf("cli.copied", &[("preview", &entry.preview(50))]));
+                    if type_text {
+                        Clipboard::type_text(&text)?;
+                    }
+                }
+                EntryType::Image if !transforms.is_empty() => {
+                    return Err(DitoxError::Other(t("cli.transform.image")));
                 }
                 EntryType::Image => {
                     let path = entry
                         .image_path()
-                        .ok_or_else(|| DitoxError::Other("image entry missing extension".into()))?;
+                        .ok_or_else(|| DitoxError::Other(t("cli.image_missing_extension")))?;
                     Clipboard::set_image(&path.to_string_lossy())?;
-                    println!("Copied image: {}", entry.preview(50));
+                    println!(
+                        "{}",
+                        tf("cli.copied_image", &[("preview", &entry.preview(50))])
+                    );
                 }
             }
+            hooks::run(&config.hooks, HookEvent::Copy, &entry);
             // Update last_used timestamp
             db.touch(&entry.id)?;
             Ok(())
         }
-        None => Err(DitoxError::NotFound(format!("Entry not found: {}", target))),
+        None => Err(DitoxError::NotFound(tf(
+            "cli.entry_not_found",
+            &[("target", &target)],
+        ))),
     }
 }
 
-fn cmd_clear(db: &mut Database, confirm: bool) -> Result<()> {
-    if !confirm {
-        print!("Clear all clipboard history? [y/N] ");
+/// Copy the `n`th most recent text clip
+fn cmd_copy_recent(
+    db: &Database,
+    config: &Config,
+    n: usize,
+    type_text: bool,
+    transforms: &[String],
+) -> Result<()> {
+    if n == 0 {
+        return Err(DitoxError::NotFound(t("cli.index_zero")));
+    }
+    let entry = db
+        .get_page_filtered(n - 1, 1, "text", None)?
+        .pop()
+        .ok_or_else(|| DitoxError::NotFound(tf("cli.no_recent_text", &[("n", &n)])))?;
+    let text = transform::Registry::new(&config.transforms).apply(transforms, &entry.content)?;
+    Clipboard::set_text(&text)?;
+    println!("{}", tf("cli.copied", &[("preview", &entry.preview(50))]));
+    if type_text {
+        Clipboard::type_text(&text)?;
+    }
+    hooks::run(&config.hooks, HookEvent::Copy, &entry);
+    db.touch(&entry.id)?;
+    Ok(())
+}
+
+fn cmd_pick(
+    db: Database,
+    config: Config,
+    print: bool,
+    print_field: Option<PrintField>,
+    options: ui::PickOptions,
+) -> Result<()> {
+    let entries = ui::pick(db, config, options)?;
+    if entries.is_empty() {
+        return Err(DitoxError::Other(t("cli.pick.nothing_picked")));
+    }
+
+    if print || print_field.is_some() {
+        let field = print_field.unwrap_or_default();
+        for entry in &entries {
+            match (field, entry.entry_type) {
+                (PrintField::Id, _) => println!("{}", entry.id),
+                (PrintField::Text, EntryType::Text) => println!("{}", entry.content),
+                (PrintField::Text, EntryType::Image) => {
+                    let path = entry.image_path().unwrap_or_default();
+                    println!("{}", path.display());
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    // Like the TUI: one image is copied as an image, texts are joined
+    match entries.as_slice() {
+        [entry] if entry.entry_type == EntryType::Image => {
+            let path = entry
+                .image_path()
+                .ok_or_else(|| DitoxError::Other(t("cli.image_missing_extension")))?;
+            Clipboard::set_image(&path.to_string_lossy())
+        }
+        _ => {
+            let texts: Vec<&str> = entries
+                .iter()
+                .filter(|e| e.entry_type == EntryType::Text)
+                .map(|e| e.content.as_str())
+                .collect();
+            Clipboard::set_text(&texts.join("\n"))
+        }
+    }
+}
+
+fn cmd_clear(db: &mut Database, confirm: bool, style: ConfirmStyle) -> Result<()> {
+    // `--confirm` skips the prompt; otherwise `[confirm] clear_all` picks
+    // it (a double press makes no sense on the command line, so it asks y/N)
+    if !confirm && style != ConfirmStyle::None {
+        let typed = style == ConfirmStyle::Typed;
+        if typed {
+            print!(
+                "{}",
+                tf("cli.clear_typed_prompt", &[("phrase", &CONFIRM_PHRASE)])
+            );
+        } else {
+            print!("{}", t("cli.clear_prompt"));
+        }
         use std::io::Write;
         std::io::stdout().flush()?;
 
         let mut input = String::new();
         std::io::stdin().read_line(&mut input)?;
 
-        if !input.trim().eq_ignore_ascii_case("y") {
-            println!("Cancelled.");
+        let accepted = if typed {
+            input.trim() == CONFIRM_PHRASE
+        } else {
+            input.trim().eq_ignore_ascii_case("y")
+        };
+        if !accepted {
+            println!("{}", t("cli.cancelled"));
             return Ok(());
         }
     }
@@ -149,7 +892,7 @@ fn cmd_clear(db: &mut Database, confirm: bool) -> Result<()> {
     // do) a separate `remove_dir_all` — that would clobber pinned images or
     // the quarantine directory managed by `ditox repair`.
     let count = db.clear_all()?;
-    println!("Cleared {} entries.", count);
+    println!("{}", tf("cli.cleared", &[("count", &count)]));
 
     Ok(())
 }
@@ -159,70 +902,197 @@ fn cmd_status(db: &Database) -> Result<()> {
     let data_dir = Database::get_data_dir()?;
     let images_dir = Database::get_images_dir()?;
 
-    println!("Ditox Status");
-    println!("────────────");
-    println!("Entries:     {}", count);
-    println!("Data dir:    {}", data_dir.display());
-    println!("Images dir:  {}", images_dir.display());
+    let title = t("cli.status.title");
+    println!("{}", title);
+    println!("{}", "─".repeat(title.chars().count()));
+    println!("{}", tf("cli.status.entries", &[("count", &count)]));
+    println!(
+        "{}",
+        tf("cli.status.data_dir", &[("path", &data_dir.display())])
+    );
+    println!(
+        "{}",
+        tf("cli.status.images_dir", &[("path", &images_dir.display())])
+    );
+    println!(
+        "{}",
+        tf(
+            "cli.status.state_dir",
+            &[("path", &Database::get_state_dir()?.display())]
+        )
+    );
+
+    let watcher = if !ditox_core::watcher::is_watcher_running() {
+        t("cli.watcher.not_running")
+    } else {
+        match ditox_core::watcher::read_watcher_health() {
+            Some(health) if health.is_healthy() => {
+                tf("cli.watcher.healthy", &[("count", &health.recoveries)])
+            }
+            Some(health) if health.consecutive_errors > 0 => tf(
+                "cli.watcher.failing",
+                &[
+                    ("count", &health.consecutive_errors),
+                    (
+                        "error",
+                        &health
+                            .last_error
+                            .unwrap_or_else(|| t("cli.watcher.unknown_error")),
+                    ),
+                ],
+            ),
+            Some(_) => t("cli.watcher.not_responding"),
+            None => t("cli.watcher.running"),
+        }
+    };
+    println!("{}", tf("cli.status.watcher", &[("status", &watcher)]));
+    if let Some(health) = ditox_core::watcher::read_watcher_health() {
+        if health.incognito {
+            println!(
+                "{}",
+                tf(
+                    "cli.status.incognito",
+                    &[("count", &health.incognito_clips)]
+                )
+            );
+        }
+        if let Some(at) = health.last_prune {
+            println!(
+                "{}",
+                tf(
+                    "cli.status.last_prune",
+                    &[
+                        (
+                            "time",
+                            &at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                        ),
+                        ("count", &health.last_prune_removed),
+                    ],
+                )
+            );
+        }
+        if let Some(at) = health.last_backup {
+            println!(
+                "{}",
+                tf(
+                    "cli.status.last_backup",
+                    &[(
+                        "time",
+                        &at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                    )],
+                )
+            );
+        }
+    }
 
     // Check if images directory exists and count files
     if images_dir.exists() {
         let image_count = std::fs::read_dir(&images_dir)
             .map(|entries| entries.count())
             .unwrap_or(0);
-        println!("Image files: {}", image_count);
+        println!(
+            "{}",
+            tf("cli.status.image_files", &[("count", &image_count)])
+        );
     }
 
     Ok(())
 }
 
-fn cmd_stats(db: &Database, json: bool) -> Result<()> {
+fn cmd_stats(db: &Database, output: OutputFormat, prometheus: bool) -> Result<()> {
     let stats = db.get_stats()?;
 
-    if json {
-        let json_output = serde_json::to_string_pretty(&stats)
-            .map_err(|e| DitoxError::Other(format!("JSON serialization error: {}", e)))?;
-        println!("{}", json_output);
-    } else {
-        print!("{}", stats.display());
+    if prometheus {
+        let health = watcher::read_watcher_health();
+        print!("{}", stats.prometheus(health.as_ref()));
+        return Ok(());
     }
-
-    Ok(())
+    output::print(output, &stats, || {
+        print!("{}", stats.display());
+        Ok(())
+    })
 }
 
-fn cmd_get(db: &Database, target: &str, json: bool) -> Result<()> {
+fn cmd_get(
+    db: &Database,
+    output: OutputFormat,
+    target: &str,
+    raw: bool,
+    out: Option<&std::path::Path>,
+) -> Result<()> {
+    use std::io::{IsTerminal, Write};
+
     let entry = resolve_target(db, target)?;
 
     match entry {
         Some(entry) => {
-            if json {
-                let json_output = serde_json::to_string_pretty(&entry)
-                    .map_err(|e| DitoxError::Other(format!("JSON serialization error: {}", e)))?;
-                println!("{}", json_output);
+            if raw || out.is_some() {
+                let bytes = match entry.entry_type {
+                    EntryType::Text => entry.content.into_bytes(),
+                    EntryType::Image => {
+                        let ext = entry.image_extension.as_deref().unwrap_or("png");
+                        std::fs::read(Database::image_path(&entry.hash, ext)?)?
+                    }
+                };
+                match out {
+                    Some(path) => std::fs::write(path, bytes)?,
+                    None => {
+                        let mut stdout = std::io::stdout().lock();
+                        if entry.entry_type == EntryType::Image && stdout.is_terminal() {
+                            return Err(DitoxError::Other(t("cli.get.binary_to_terminal")));
+                        }
+                        stdout.write_all(&bytes)?;
+                        stdout.flush()?;
+                    }
+                }
+            } else if output != OutputFormat::Plain {
+                // Include attached metadata alongside the entry fields
+                let mut value = serde_json::to_value(&entry)
+                    .map_err(|e| DitoxError::Other(tf("cli.json_error", &[("error", &e)])))?;
+                let meta = db.get_all_meta(&entry.id)?;
+                if !meta.is_empty() {
+                    value["meta"] = serde_json::json!(meta);
+                }
+                output::print(output, &value, || Ok(()))?;
             } else {
                 // Print raw content for piping
                 print!("{}", entry.content);
             }
             Ok(())
         }
-        None => Err(DitoxError::NotFound(format!("Entry not found: {}", target))),
+        None => Err(DitoxError::NotFound(tf(
+            "cli.entry_not_found",
+            &[("target", &target)],
+        ))),
     }
 }
 
-fn cmd_search(db: &Database, query: &str, limit: usize, json: bool) -> Result<()> {
-    // Load all entries and perform fuzzy search (same as TUI)
-    let entries = db.get_all(1000)?; // Load enough entries for searching
-
-    let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+/// Operators (tag:, is:fav, kind:, after:, before:) and a regex are
+/// evaluated in SQLite; the free text is fuzzy searched over the candidates
+/// (same as TUI)
+fn search_entries(
+    db: &Database,
+    parsed: &Query,
+    regex: Option<&str>,
+    limit: usize,
+) -> Result<Vec<Entry>> {
+    let mut filters = parsed.filters();
+    filters.regex = regex.map(str::to_string);
+    let entries = db.query(&filters, 1000)?; // Load enough entries for searching
+
+    let Some(text) = parsed.plain_text() else {
+        // Filters only: keep recency order
+        return Ok(entries.into_iter().take(limit).collect());
+    };
+    let pattern = Pattern::parse(&text, CaseMatching::Ignore, Normalization::Smart);
     let mut matcher = Matcher::new(MatcherConfig::DEFAULT);
 
-    let mut matches: Vec<(&Entry, u32)> = entries
-        .iter()
+    let mut matches: Vec<(Entry, u32)> = entries
+        .into_iter()
         .filter_map(|e| {
-            let haystack = &e.content;
             let mut buf = Vec::new();
             let score = pattern.score(
-                nucleo_matcher::Utf32Str::new(haystack, &mut buf),
+                nucleo_matcher::Utf32Str::new(&e.content, &mut buf),
                 &mut matcher,
             )?;
             Some((e, score))
@@ -233,82 +1103,410 @@ fn cmd_search(db: &Database, query: &str, limit: usize, json: bool) -> Result<()
     matches.sort_by_key(|m| std::cmp::Reverse(m.1));
 
     // Take only up to limit
-    let results: Vec<&Entry> = matches.iter().take(limit).map(|(e, _)| *e).collect();
+    Ok(matches.into_iter().take(limit).map(|(e, _)| e).collect())
+}
 
-    if json {
-        let json_output = serde_json::to_string_pretty(&results)
-            .map_err(|e| DitoxError::Other(format!("JSON serialization error: {}", e)))?;
-        println!("{}", json_output);
+fn cmd_search(
+    db: &Database,
+    query: Option<&str>,
+    regex: Option<&str>,
+    limit: usize,
+    output: OutputFormat,
+    id_len: Option<usize>,
+) -> Result<()> {
+    let parsed = query.map(Query::parse).transpose()?.unwrap_or_default();
+    let results = search_entries(db, &parsed, regex, limit)?;
+    let text = parsed.plain_text();
+
+    // Where each result matched, so long entries can show the relevant part.
+    // Fuzzy queries that aren't valid FTS syntax simply get no snippets.
+    let hits: HashMap<String, SearchHit> = match &text {
+        Some(_) => db
+            .search_hits(&parsed, 1000)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(entry, hit)| (entry.id, hit))
+            .collect(),
+        None => HashMap::new(),
+    };
+
+    if output != OutputFormat::Plain {
+        let values = results
+            .iter()
+            .map(|entry| {
+                let mut value = serde_json::to_value(entry)?;
+                if let Some(hit) = hits.get(&entry.id) {
+                    value["hit"] = serde_json::to_value(hit)?;
+                }
+                Ok(value)
+            })
+            .collect::<serde_json::Result<Vec<_>>>()
+            .map_err(|e| DitoxError::Other(tf("cli.json_error", &[("error", &e)])))?;
+        output::print(output, &values, || Ok(()))?;
     } else {
         if results.is_empty() {
-            println!("No matches found for: {}", query);
+            println!(
+                "{}",
+                tf(
+                    "cli.no_matches",
+                    &[("query", &query.or(regex).unwrap_or_default())]
+                )
+            );
             return Ok(());
         }
 
+        let ids = display_ids(db, &results, id_len)?;
+        let id_width = ids.iter().map(|id| id.len()).max().unwrap_or(0).max(2);
+
+        println!(
+            "{:>3} │ {:<id_width$} │ {:^4} │ {:^3} │ {:<40} │ {:>6}",
+            "#",
+            t("cli.column.id"),
+            t("cli.column.type"),
+            t("cli.column.favorite"),
+            t("cli.column.content"),
+            t("cli.column.age")
+        );
         println!(
-            "{:>3} │ {:^4} │ {:^3} │ {:<40} │ {:>6}",
-            "#", "Type", "Pin", "Content", "Age"
+            "────┼{}┼──────┼─────┼──────────────────────────────────────────┼────────",
+            "─".repeat(id_width + 2)
         );
-        println!("────┼──────┼─────┼──────────────────────────────────────────┼────────");
 
         for (i, entry) in results.iter().enumerate() {
+            // Show the snippet when the match is past the visible head
+            let preview = match hits.get(&entry.id) {
+                Some(hit)
+                    if entry.entry_type == EntryType::Text
+                        && hit.offset.is_some_and(|offset| offset >= 40) =>
+                {
+                    hit.snippet
+                        .chars()
+                        .map(|c| {
+                            if c.is_whitespace() || c.is_control() {
+                                ' '
+                            } else {
+                                c
+                            }
+                        })
+                        .take(40)
+                        .collect()
+                }
+                _ => entry.preview(40),
+            };
             println!(
-                "{:>3} │ {:^4} │ {:^3} │ {:<40} │ {:>6}",
+                "{:>3} │ {:<id_width$} │ {:^4} │ {:^3} │ {:<40} │ {:>6}",
                 i + 1,
+                ids[i],
                 entry.entry_type.short(),
                 if entry.favorite { "⭐" } else { "" },
-                entry.preview(40),
+                preview,
                 entry.relative_time()
             );
         }
     }
-
+
+    Ok(())
+}
+
+fn cmd_delete(
+    db: &mut Database,
+    config: &Config,
+    output: OutputFormat,
+    target: &str,
+) -> Result<()> {
+    let entry = resolve_target(db, target)?;
+
+    match entry {
+        Some(entry) => {
+            let preview = entry.preview(30);
+            let id = entry.id.clone();
+
+            // `Database::delete` handles the blob cleanup via the pending
+            // prune queue; don't unlink by hand here (doing so would race
+            // with the queue drain and could delete an unrelated blob if
+            // hashes ever collided).
+            db.delete(&id)?;
+            hooks::run(&config.hooks, HookEvent::Delete, &entry);
+            let result = serde_json::json!({ "id": id, "deleted": true });
+            output::print(output, &result, || {
+                println!("{}", tf("cli.deleted", &[("preview", &preview)]));
+                Ok(())
+            })
+        }
+        None => Err(DitoxError::NotFound(tf(
+            "cli.entry_not_found",
+            &[("target", &target)],
+        ))),
+    }
+}
+
+fn cmd_favorite(db: &Database, output: OutputFormat, target: &str) -> Result<()> {
+    let entry = resolve_target(db, target)?;
+
+    match entry {
+        Some(entry) => {
+            let preview = entry.preview(30);
+            let was_favorite = entry.favorite;
+            db.toggle_favorite(&entry.id)?;
+
+            let result = serde_json::json!({ "id": entry.id, "favorite": !was_favorite });
+            output::print(output, &result, || {
+                if was_favorite {
+                    println!("{}", tf("cli.unfavorited", &[("preview", &preview)]));
+                } else {
+                    println!("{}", tf("cli.favorited", &[("preview", &preview)]));
+                }
+                Ok(())
+            })
+        }
+        None => Err(DitoxError::NotFound(tf(
+            "cli.entry_not_found",
+            &[("target", &target)],
+        ))),
+    }
+}
+
+fn cmd_count(db: &Database) -> Result<()> {
+    let count = db.count()?;
+    println!("{}", count);
+    Ok(())
+}
+
+/// Copy every image blob into `dir` with a checksum manifest
+fn cmd_export_images(
+    db: &Database,
+    dir: &std::path::Path,
+    jobs: Option<usize>,
+    verify: bool,
+) -> Result<()> {
+    if verify {
+        let bad = export::verify_export(dir)?;
+        if bad.is_empty() {
+            println!("{}", t("cli.export.verified"));
+            return Ok(());
+        }
+        for file in &bad {
+            println!("{}", tf("cli.export.bad_file", &[("file", file)]));
+        }
+        return Err(DitoxError::Other(tf(
+            "cli.export.verify_failed",
+            &[("count", &bad.len())],
+        )));
+    }
+
+    let report = export::export_images(db, dir, jobs.unwrap_or_else(export::default_jobs))?;
+    println!(
+        "{}",
+        tf(
+            "cli.export.done",
+            &[
+                ("path", &dir.display()),
+                ("written", &report.written),
+                ("unchanged", &report.unchanged),
+            ],
+        )
+    );
+    if !report.missing.is_empty() {
+        println!(
+            "{}",
+            tf(
+                "cli.export.missing_blobs",
+                &[("count", &report.missing.len())]
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Restore image blobs from an `export-images` directory
+fn cmd_import_images(dir: &std::path::Path) -> Result<()> {
+    let report = export::import_images(dir)?;
+    println!(
+        "{}",
+        tf(
+            "cli.import.done",
+            &[
+                ("path", &dir.display()),
+                ("imported", &report.imported),
+                ("present", &report.present),
+            ],
+        )
+    );
+    if !report.bad.is_empty() {
+        for file in &report.bad {
+            println!("{}", tf("cli.import.bad_file", &[("file", file)]));
+        }
+        return Err(DitoxError::Other(tf(
+            "cli.import.failed",
+            &[("count", &report.bad.len())],
+        )));
+    }
+    Ok(())
+}
+
+fn cmd_merge_db(db: &Database, path: &std::path::Path) -> Result<()> {
+    let report = merge::merge_db(db, path)?;
+    println!(
+        "{}",
+        tf(
+            "cli.merge.done",
+            &[
+                ("path", &path.display()),
+                ("added", &report.added),
+                ("updated", &report.updated),
+                ("unchanged", &report.unchanged),
+                ("tags", &report.tags),
+            ],
+        )
+    );
+    if !report.missing_images.is_empty() {
+        println!(
+            "{}",
+            tf(
+                "cli.merge.missing_images",
+                &[("count", &report.missing_images.len())],
+            )
+        );
+    }
+    Ok(())
+}
+
+fn cmd_export(
+    db: &Database,
+    output: Option<&std::path::Path>,
+    format: ExportFormat,
+    images: ExportImageMode,
+) -> Result<()> {
+    let ExportFormat::Ndjson = format;
+    let images = match images {
+        ExportImageMode::Inline => ImagePayload::Inline,
+        ExportImageMode::Reference => ImagePayload::Reference,
+    };
+    match output {
+        Some(path) => {
+            let mut out = std::io::BufWriter::new(std::fs::File::create(path)?);
+            let count = ndjson::export(db, &mut out, images)?;
+            eprintln!(
+                "{}",
+                tf(
+                    "cli.ndjson.exported",
+                    &[("count", &count), ("path", &path.display())]
+                )
+            );
+        }
+        None => {
+            let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+            match ndjson::export(db, &mut out, images) {
+                // The reader stopped early, e.g. `ditox export | head`
+                Err(DitoxError::Io(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => {}
+                result => {
+                    result?;
+                }
+            }
+        }
+    }
     Ok(())
 }
 
-fn cmd_delete(db: &mut Database, target: &str) -> Result<()> {
-    let entry = resolve_target(db, target)?;
-
-    match entry {
-        Some(entry) => {
-            let preview = entry.preview(30);
-            let id = entry.id.clone();
+fn cmd_import(
+    db: &Database,
+    path: &std::path::Path,
+    format: Option<ImportSource>,
+    on_conflict: OnConflict,
+) -> Result<()> {
+    use std::io::BufRead;
 
-            // `Database::delete` handles the blob cleanup via the pending
-            // prune queue; don't unlink by hand here (doing so would race
-            // with the queue drain and could delete an unrelated blob if
-            // hashes ever collided).
-            db.delete(&id)?;
-            println!("Deleted: {}", preview);
-            Ok(())
-        }
-        None => Err(DitoxError::NotFound(format!("Entry not found: {}", target))),
+    let stdin = path.as_os_str() == "-";
+    let mut input: Box<dyn BufRead> = if stdin {
+        Box::new(std::io::stdin().lock())
+    } else {
+        Box::new(std::io::BufReader::new(std::fs::File::open(path)?))
+    };
+    let is_ndjson = match format {
+        Some(format) => format == ImportSource::Ndjson,
+        None => stdin || ndjson::detect(&String::from_utf8_lossy(input.fill_buf()?)),
+    };
+    if is_ndjson {
+        let policy = match on_conflict {
+            OnConflict::Skip => ConflictPolicy::Skip,
+            OnConflict::Merge => ConflictPolicy::Merge,
+            OnConflict::Replace => ConflictPolicy::Replace,
+        };
+        let report = ndjson::import(db, input, policy)?;
+        println!(
+            "{}",
+            tf(
+                "cli.ndjson.imported",
+                &[
+                    ("path", &path.display()),
+                    ("added", &report.added),
+                    ("updated", &report.updated),
+                    ("skipped", &report.skipped),
+                    ("missing", &report.missing_images),
+                ],
+            )
+        );
+        return Ok(());
     }
+    drop(input);
+
+    let format = format.map(|format| match format {
+        ImportSource::Clipman => ImportFormat::Clipman,
+        ImportSource::Clipse => ImportFormat::Clipse,
+        ImportSource::Gpaste => ImportFormat::Gpaste,
+        ImportSource::Ndjson => unreachable!(),
+    });
+    let report = import::import(db, path, format)?;
+    println!(
+        "{}",
+        tf(
+            "cli.import.history_done",
+            &[
+                ("path", &path.display()),
+                ("imported", &report.imported),
+                ("duplicates", &report.duplicates),
+                ("skipped", &report.skipped),
+            ],
+        )
+    );
+    Ok(())
 }
 
-fn cmd_favorite(db: &Database, target: &str) -> Result<()> {
-    let entry = resolve_target(db, target)?;
-
-    match entry {
-        Some(entry) => {
-            let preview = entry.preview(30);
-            let was_favorite = entry.favorite;
-            db.toggle_favorite(&entry.id)?;
+fn cmd_dedupe(db: &Database, dry_run: bool) -> Result<()> {
+    let groups = if dry_run {
+        dedupe::find_duplicates(db)?
+    } else {
+        dedupe::dedupe(db)?
+    };
+    if groups.is_empty() {
+        println!("{}", t("cli.dedupe.none"));
+        return Ok(());
+    }
 
-            if was_favorite {
-                println!("Removed from favorites: {}", preview);
-            } else {
-                println!("Added to favorites: {}", preview);
-            }
-            Ok(())
+    let removed: usize = groups.iter().map(|g| g.duplicates.len()).sum();
+    if dry_run {
+        for group in &groups {
+            println!(
+                "{}",
+                tf(
+                    "cli.dedupe.group",
+                    &[
+                        ("preview", &group.keep.preview(50)),
+                        ("count", &group.duplicates.len()),
+                    ],
+                )
+            );
         }
-        None => Err(DitoxError::NotFound(format!("Entry not found: {}", target))),
     }
-}
-
-fn cmd_count(db: &Database) -> Result<()> {
-    let count = db.count()?;
-    println!("{}", count);
+    let key = if dry_run {
+        "cli.dedupe.would_remove"
+    } else {
+        "cli.dedupe.removed"
+    };
+    println!(
+        "{}",
+        tf(key, &[("count", &removed), ("groups", &groups.len())])
+    );
     Ok(())
 }
 
@@ -425,15 +1623,226 @@ fn cmd_repair(db: &mut Database, dry_run: bool, fix_hashes: bool) -> Result<()>
     Ok(())
 }
 
+fn cmd_migrate(
+    db: &mut Database,
+    output: OutputFormat,
+    enable_trigram: bool,
+    disable_trigram: bool,
+) -> Result<()> {
+    // What changed, in the plain format
+    let message = if enable_trigram {
+        if db.has_trigram_index() {
+            t("cli.migrate.trigram_exists")
+        } else {
+            let indexed = db.enable_trigram_index()?;
+            tf("cli.migrate.trigram_built", &[("count", &indexed)])
+        }
+    } else if disable_trigram {
+        if db.disable_trigram_index()? {
+            t("cli.migrate.trigram_dropped")
+        } else {
+            t("cli.migrate.trigram_missing")
+        }
+    } else {
+        let status = if db.has_trigram_index() {
+            t("cli.migrate.enabled")
+        } else {
+            t("cli.migrate.disabled")
+        };
+        let schema = tf(
+            "cli.migrate.schema_version",
+            &[
+                ("version", &db.schema_version().unwrap_or(0)),
+                ("supported", &SCHEMA_VERSION),
+            ],
+        );
+        let trigram = tf("cli.migrate.trigram_status", &[("status", &status)]);
+        format!("{}\n{}", schema, trigram)
+    };
+    let result = serde_json::json!({
+        "schema_version": db.schema_version().unwrap_or(0),
+        "supported_schema_version": SCHEMA_VERSION,
+        "trigram_index": db.has_trigram_index(),
+    });
+    output::print(output, &result, || {
+        println!("{}", message);
+        Ok(())
+    })
+}
+
+fn cmd_db_maintain(db: &mut Database) -> Result<()> {
+    let report = db.maintain()?;
+    if !report.problems.is_empty() {
+        for problem in &report.problems {
+            eprintln!("  {}", problem);
+        }
+        return Err(DitoxError::Other(t("cli.maintain.corrupt")));
+    }
+    println!("{}", t("cli.maintain.integrity_ok"));
+    println!(
+        "{}",
+        tf("cli.maintain.reindexed", &[("count", &report.indexed)])
+    );
+    println!(
+        "{}",
+        tf(
+            "cli.maintain.size",
+            &[
+                (
+                    "before",
+                    &ditox_core::stats::format_bytes(report.size_before)
+                ),
+                ("after", &ditox_core::stats::format_bytes(report.size_after)),
+            ],
+        )
+    );
+    Ok(())
+}
+
+fn cmd_config(subcmd: ConfigCommands) -> Result<()> {
+    let path = Config::get_config_path()?;
+    match subcmd {
+        ConfigCommands::Path => println!("{}", path.display()),
+        ConfigCommands::Get { key } => {
+            if let Some(value) = config_edit::get(&path, &key)? {
+                println!("{}", config_edit::display(&value)?.trim_end());
+            }
+        }
+        ConfigCommands::Set { key, value } => {
+            config_edit::set(&path, &key, &value)?;
+            println!(
+                "{}",
+                tf(
+                    "cli.config.set",
+                    &[("key", &key), ("path", &path.display())]
+                )
+            );
+        }
+        ConfigCommands::Validate => {
+            let problems = config_edit::validate(&path)?;
+            if problems.is_empty() {
+                println!("{}", tf("cli.config.valid", &[("path", &path.display())]));
+                return Ok(());
+            }
+            for problem in &problems {
+                println!("{}", problem);
+            }
+            return Err(DitoxError::Config(tf(
+                "cli.config.invalid",
+                &[("count", &problems.len()), ("path", &path.display())],
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn cmd_init(defaults: bool) -> Result<()> {
+    let path = Config::get_config_path()?;
+    let stdin = std::io::stdin();
+    let mut input = stdin.lock();
+    let mut out = std::io::stdout();
+    if !defaults {
+        println!("{}", tf("cli.init.intro", &[("path", &path.display())]));
+        init::ask_settings(&mut input, &mut out, &path)?;
+    }
+    // Fails early on a config the wizard didn't write
+    Config::load_from(&path)?;
+
+    let db = Database::open()?;
+    db.init_schema()?;
+    println!(
+        "{}",
+        tf(
+            "cli.init.database",
+            &[("path", &Database::get_db_path()?.display())]
+        )
+    );
+
+    if let Ok(backend) = Backend::current() {
+        let question = tf("cli.init.install_unit", &[("backend", &backend.name())]);
+        if !defaults
+            && !backend.is_installed()?
+            && init::confirm(&mut input, &mut out, &question, false)?
+        {
+            cmd_install_autostart(backend, false)?;
+        }
+    }
+    Ok(())
+}
+
+fn cmd_backup(db: &mut Database, subcmd: BackupCommands) -> Result<()> {
+    match subcmd {
+        BackupCommands::Create { path } => {
+            let report = backup::create(db, &path)?;
+            println!(
+                "{}",
+                tf(
+                    "cli.backup.created",
+                    &[
+                        ("path", &path.display()),
+                        ("entries", &report.entries),
+                        ("images", &report.images),
+                        ("size", &ditox_core::stats::format_bytes(report.bytes)),
+                    ],
+                )
+            );
+            if report.missing_images > 0 {
+                println!(
+                    "{}",
+                    tf(
+                        "cli.backup.missing_images",
+                        &[("count", &report.missing_images)]
+                    )
+                );
+            }
+        }
+        BackupCommands::Restore { path, verify } => {
+            let report = backup::restore(db, &path, verify)?;
+            println!(
+                "{}",
+                tf(
+                    "cli.backup.restored",
+                    &[
+                        ("path", &path.display()),
+                        ("entries", &report.entries),
+                        ("images", &report.images),
+                    ],
+                )
+            );
+            println!(
+                "{}",
+                tf(
+                    "cli.backup.safety",
+                    &[("path", &report.safety_backup.display())]
+                )
+            );
+        }
+    }
+    Ok(())
+}
+
 /// Helper to resolve a target (index or ID) to an entry
 fn resolve_target(db: &Database, target: &str) -> Result<Option<Entry>> {
     if let Ok(index) = target.parse::<usize>() {
         if index == 0 {
-            return Err(DitoxError::NotFound("Index must be 1 or greater".into()));
+            return Err(DitoxError::NotFound(t("cli.index_zero")));
         }
-        db.get_by_index(index - 1)
-    } else {
-        db.get_by_id(target)
+        return db.get_by_index(index - 1);
+    }
+    if let Some(entry) = db.get_by_id(target)? {
+        return Ok(Some(entry));
+    }
+    if let Some(id) = db.get_alias(target)? {
+        return db.get_by_id(&id);
+    }
+    // Short ID as printed by `list`; must match exactly one entry
+    match db.find_ids_by_prefix(target, 2)?.as_slice() {
+        [id] => db.get_by_id(id),
+        [] => Ok(None),
+        _ => Err(DitoxError::Other(format!(
+            "Ambiguous ID prefix '{}': matches several entries",
+            target
+        ))),
     }
 }
 
@@ -475,17 +1884,21 @@ fn cmd_collection_list(db: &Database, json: bool) -> Result<()> {
 
     if json {
         let json_output = serde_json::to_string_pretty(&collections)
-            .map_err(|e| DitoxError::Other(format!("JSON serialization error: {}", e)))?;
+            .map_err(|e| DitoxError::Other(tf("cli.json_error", &[("error", &e)])))?;
         println!("{}", json_output);
     } else {
         if collections.is_empty() {
-            println!("No collections found. Create one with: ditox collection create <name>");
+            println!("{}", t("cli.collection.none"));
             return Ok(());
         }
 
         println!(
             "{:>3} │ {:<20} │ {:^7} │ {:^3} │ {:>6}",
-            "#", "Name", "Color", "Key", "Entries"
+            "#",
+            t("cli.column.name"),
+            t("cli.column.color"),
+            t("cli.column.key"),
+            t("cli.column.entries")
         );
         println!("────┼──────────────────────┼─────────┼─────┼────────");
 
@@ -519,9 +1932,9 @@ fn cmd_collection_create(
 ) -> Result<()> {
     // Check if collection with this name already exists
     if db.get_collection_by_name(&name)?.is_some() {
-        return Err(DitoxError::Other(format!(
-            "Collection '{}' already exists",
-            name
+        return Err(DitoxError::Other(tf(
+            "cli.collection.exists",
+            &[("name", &name)],
         )));
     }
 
@@ -532,7 +1945,7 @@ fn cmd_collection_create(
     let collection = Collection::with_options(name.clone(), color, keybind, position);
     db.create_collection(&collection)?;
 
-    println!("Created collection: {}", name);
+    println!("{}", tf("cli.collection.created", &[("name", &name)]));
     Ok(())
 }
 
@@ -543,12 +1956,12 @@ fn cmd_collection_delete(db: &Database, target: &str) -> Result<()> {
         Some(col) => {
             let name = col.name.clone();
             db.delete_collection(&col.id)?;
-            println!("Deleted collection: {}", name);
+            println!("{}", tf("cli.collection.deleted", &[("name", &name)]));
             Ok(())
         }
-        None => Err(DitoxError::NotFound(format!(
-            "Collection not found: {}",
-            target
+        None => Err(DitoxError::NotFound(tf(
+            "cli.collection.not_found",
+            &[("target", &target)],
         ))),
     }
 }
@@ -560,21 +1973,27 @@ fn cmd_collection_rename(db: &Database, target: &str, new_name: String) -> Resul
         Some(mut col) => {
             // Check if new name already exists
             if db.get_collection_by_name(&new_name)?.is_some() {
-                return Err(DitoxError::Other(format!(
-                    "Collection '{}' already exists",
-                    new_name
+                return Err(DitoxError::Other(tf(
+                    "cli.collection.exists",
+                    &[("name", &new_name)],
                 )));
             }
 
             let old_name = col.name.clone();
             col.name = new_name.clone();
             db.update_collection(&col)?;
-            println!("Renamed collection '{}' to '{}'", old_name, new_name);
+            println!(
+                "{}",
+                tf(
+                    "cli.collection.renamed",
+                    &[("old", &old_name), ("new", &new_name)]
+                )
+            );
             Ok(())
         }
-        None => Err(DitoxError::NotFound(format!(
-            "Collection not found: {}",
-            target
+        None => Err(DitoxError::NotFound(tf(
+            "cli.collection.not_found",
+            &[("target", &target)],
         ))),
     }
 }
@@ -586,16 +2005,22 @@ fn cmd_collection_add(db: &Database, entry_target: &str, collection_target: &str
     match (entry, collection) {
         (Some(entry), Some(col)) => {
             db.set_entry_collection(&entry.id, Some(&col.id))?;
-            println!("Added '{}' to collection '{}'", entry.preview(30), col.name);
+            println!(
+                "{}",
+                tf(
+                    "cli.collection.added",
+                    &[("preview", &entry.preview(30)), ("name", &col.name)]
+                )
+            );
             Ok(())
         }
-        (None, _) => Err(DitoxError::NotFound(format!(
-            "Entry not found: {}",
-            entry_target
+        (None, _) => Err(DitoxError::NotFound(tf(
+            "cli.entry_not_found",
+            &[("target", &entry_target)],
         ))),
-        (_, None) => Err(DitoxError::NotFound(format!(
-            "Collection not found: {}",
-            collection_target
+        (_, None) => Err(DitoxError::NotFound(tf(
+            "cli.collection.not_found",
+            &[("target", &collection_target)],
         ))),
     }
 }
@@ -606,12 +2031,15 @@ fn cmd_collection_remove(db: &Database, entry_target: &str) -> Result<()> {
     match entry {
         Some(entry) => {
             db.set_entry_collection(&entry.id, None)?;
-            println!("Removed '{}' from its collection", entry.preview(30));
+            println!(
+                "{}",
+                tf("cli.collection.removed", &[("preview", &entry.preview(30))])
+            );
             Ok(())
         }
-        None => Err(DitoxError::NotFound(format!(
-            "Entry not found: {}",
-            entry_target
+        None => Err(DitoxError::NotFound(tf(
+            "cli.entry_not_found",
+            &[("target", &entry_target)],
         ))),
     }
 }
@@ -625,18 +2053,22 @@ fn cmd_collection_show(db: &Database, target: &str, limit: usize, json: bool) ->
 
             if json {
                 let json_output = serde_json::to_string_pretty(&entries)
-                    .map_err(|e| DitoxError::Other(format!("JSON serialization error: {}", e)))?;
+                    .map_err(|e| DitoxError::Other(tf("cli.json_error", &[("error", &e)])))?;
                 println!("{}", json_output);
             } else {
                 if entries.is_empty() {
-                    println!("No entries in collection '{}'", col.name);
+                    println!("{}", tf("cli.collection.empty", &[("name", &col.name)]));
                     return Ok(());
                 }
 
-                println!("Collection: {}", col.name);
+                println!("{}", tf("cli.collection.title", &[("name", &col.name)]));
                 println!(
                     "{:>3} │ {:^4} │ {:^3} │ {:<40} │ {:>6}",
-                    "#", "Type", "Pin", "Content", "Age"
+                    "#",
+                    t("cli.column.type"),
+                    t("cli.column.favorite"),
+                    t("cli.column.content"),
+                    t("cli.column.age")
                 );
                 println!("────┼──────┼─────┼──────────────────────────────────────────┼────────");
 
@@ -653,9 +2085,408 @@ fn cmd_collection_show(db: &Database, target: &str, limit: usize, json: bool) ->
             }
             Ok(())
         }
-        None => Err(DitoxError::NotFound(format!(
-            "Collection not found: {}",
-            target
+        None => Err(DitoxError::NotFound(tf(
+            "cli.collection.not_found",
+            &[("target", &target)],
         ))),
     }
 }
+
+fn cmd_meta(db: &mut Database, subcmd: MetaCommands) -> Result<()> {
+    let target = match &subcmd {
+        MetaCommands::List { entry, .. }
+        | MetaCommands::Get { entry, .. }
+        | MetaCommands::Set { entry, .. }
+        | MetaCommands::Remove { entry, .. }
+        | MetaCommands::Import { entry, .. } => entry.clone(),
+    };
+    let entry = resolve_target(db, &target)?
+        .ok_or_else(|| DitoxError::NotFound(tf("cli.entry_not_found", &[("target", &target)])))?;
+
+    match subcmd {
+        MetaCommands::List { json, .. } => {
+            let meta = db.get_all_meta(&entry.id)?;
+            if json {
+                let json_output = serde_json::to_string_pretty(&meta)
+                    .map_err(|e| DitoxError::Other(tf("cli.json_error", &[("error", &e)])))?;
+                println!("{}", json_output);
+            } else if meta.is_empty() {
+                println!(
+                    "{}",
+                    tf("cli.meta.none", &[("preview", &entry.preview(30))])
+                );
+            } else {
+                for (key, value) in &meta {
+                    println!("{}: {}", key, value);
+                }
+            }
+        }
+        MetaCommands::Get { key, .. } => match db.get_meta(&entry.id, &key)? {
+            Some(value) => println!("{}", value),
+            None => {
+                return Err(DitoxError::NotFound(tf(
+                    "cli.meta.key_not_found",
+                    &[("key", &key)],
+                )))
+            }
+        },
+        MetaCommands::Set { key, value, .. } => {
+            db.set_meta(&entry.id, &key, &value)?;
+            println!(
+                "{}",
+                tf(
+                    "cli.meta.set",
+                    &[("key", &key), ("preview", &entry.preview(30))]
+                )
+            );
+        }
+        MetaCommands::Remove { key, .. } => {
+            if !db.delete_meta(&entry.id, &key)? {
+                return Err(DitoxError::NotFound(tf(
+                    "cli.meta.key_not_found",
+                    &[("key", &key)],
+                )));
+            }
+            println!(
+                "{}",
+                tf(
+                    "cli.meta.removed",
+                    &[("key", &key), ("preview", &entry.preview(30))]
+                )
+            );
+        }
+        MetaCommands::Import { file, .. } => {
+            let content = if file == "-" {
+                std::io::read_to_string(std::io::stdin())?
+            } else {
+                std::fs::read_to_string(&file)?
+            };
+            let meta: std::collections::BTreeMap<String, String> = serde_json::from_str(&content)
+                .map_err(|e| {
+                DitoxError::Other(tf("cli.meta.invalid_json", &[("error", &e)]))
+            })?;
+            let count = db.import_meta(&entry.id, &meta)?;
+            println!(
+                "{}",
+                tf(
+                    "cli.meta.imported",
+                    &[("count", &count), ("preview", &entry.preview(30))]
+                )
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_alias(db: &Database, subcmd: AliasCommands) -> Result<()> {
+    match subcmd {
+        AliasCommands::Set { name, entry } => {
+            let target = resolve_target(db, &entry)?.ok_or_else(|| {
+                DitoxError::NotFound(tf("cli.entry_not_found", &[("target", &entry)]))
+            })?;
+            db.set_alias(&name, &target.id)?;
+            println!("{} -> {}", name, target.preview(40));
+            Ok(())
+        }
+        AliasCommands::List { json } => {
+            let aliases = db.get_aliases()?;
+            if json {
+                let map: std::collections::BTreeMap<_, _> = aliases.into_iter().collect();
+                let json_output = serde_json::to_string_pretty(&map)
+                    .map_err(|e| DitoxError::Other(tf("cli.json_error", &[("error", &e)])))?;
+                println!("{}", json_output);
+            } else if aliases.is_empty() {
+                println!("{}", t("cli.alias.none"));
+            } else {
+                for (name, id) in aliases {
+                    let preview = db
+                        .get_by_id(&id)?
+                        .map(|e| e.preview(40))
+                        .unwrap_or_default();
+                    println!("{:<16} │ {}", name, preview);
+                }
+            }
+            Ok(())
+        }
+        AliasCommands::Remove { name } => {
+            if db.delete_alias(&name)? {
+                println!("{}", tf("cli.alias.removed", &[("name", &name)]));
+                Ok(())
+            } else {
+                Err(DitoxError::NotFound(tf(
+                    "cli.alias.not_found",
+                    &[("name", &name)],
+                )))
+            }
+        }
+    }
+}
+
+fn cmd_seed(db: &Database, options: SeedOptions) -> Result<()> {
+    let report = seed::seed(db, &options)?;
+    println!(
+        "{}",
+        tf(
+            "cli.seed.done",
+            &[
+                ("clips", &report.clips),
+                ("images", &report.images),
+                ("tags", &report.tags),
+                ("days", &options.days),
+            ],
+        )
+    );
+    Ok(())
+}
+
+fn cmd_tag(db: &mut Database, output: OutputFormat, subcmd: TagCommands) -> Result<()> {
+    match subcmd {
+        TagCommands::List { json } => {
+            let tags = db.all_tags()?;
+            let map: std::collections::BTreeMap<_, _> = tags.iter().cloned().collect();
+            output::print(output.or_json(json), &map, || {
+                if tags.is_empty() {
+                    println!("{}", t("cli.tag.none"));
+                }
+                for (name, count) in &tags {
+                    println!("{:<24} │ {:>5} {}", name, count, t("cli.entries_column"));
+                }
+                Ok(())
+            })
+        }
+        TagCommands::Add { entry, tags } => {
+            let target = resolve_target(db, &entry)?.ok_or_else(|| {
+                DitoxError::NotFound(tf("cli.entry_not_found", &[("target", &entry)]))
+            })?;
+            db.add_tags(&target.id, &tags)?;
+            println!(
+                "{} │ {}",
+                target.preview(40),
+                db.get_tags(&target.id)?.join(", ")
+            );
+            Ok(())
+        }
+        TagCommands::Remove { entry, tags } => {
+            let target = resolve_target(db, &entry)?.ok_or_else(|| {
+                DitoxError::NotFound(tf("cli.entry_not_found", &[("target", &entry)]))
+            })?;
+            db.remove_tags(&target.id, &tags)?;
+            println!(
+                "{}",
+                tf(
+                    "cli.tag.removed",
+                    &[("count", &tags.len()), ("preview", &target.preview(40))]
+                )
+            );
+            Ok(())
+        }
+        TagCommands::Rename { old, new } => {
+            let count = db.rename_tag(&old, &new)?;
+            if count == 0 {
+                return Err(DitoxError::NotFound(tf(
+                    "cli.tag.not_found",
+                    &[("name", &old)],
+                )));
+            }
+            println!(
+                "{}",
+                tf(
+                    "cli.tag.renamed",
+                    &[("old", &old), ("new", &new), ("count", &count)]
+                )
+            );
+            Ok(())
+        }
+        TagCommands::Merge { from, into } => {
+            let mut total = 0;
+            for tag in &from {
+                total += db.rename_tag(tag, &into)?;
+            }
+            println!(
+                "{}",
+                tf(
+                    "cli.tag.merged",
+                    &[
+                        ("from", &from.join(", ")),
+                        ("into", &into),
+                        ("count", &total)
+                    ],
+                )
+            );
+            Ok(())
+        }
+        TagCommands::Delete { name } => {
+            let count = db.delete_tag(&name)?;
+            if count == 0 {
+                return Err(DitoxError::NotFound(tf(
+                    "cli.tag.not_found",
+                    &[("name", &name)],
+                )));
+            }
+            println!(
+                "{}",
+                tf("cli.tag.deleted", &[("name", &name), ("count", &count)])
+            );
+            Ok(())
+        }
+    }
+}
+
+fn cmd_views(db: &Database, subcmd: ViewCommands, id_len: Option<usize>) -> Result<()> {
+    match subcmd {
+        ViewCommands::Save {
+            name,
+            text,
+            regex,
+            entry_type,
+            favorites,
+            collection,
+            days,
+        } => {
+            let entry_type = entry_type
+                .map(|name| {
+                    EntryType::from_str(&name).ok_or_else(|| {
+                        DitoxError::Other(tf("cli.views.unknown_type", &[("type", &name)]))
+                    })
+                })
+                .transpose()?;
+            let collection_id = collection
+                .map(|target| {
+                    resolve_collection(db, &target)?
+                        .map(|col| col.id)
+                        .ok_or_else(|| {
+                            DitoxError::NotFound(tf(
+                                "cli.views.collection_not_found",
+                                &[("target", &target)],
+                            ))
+                        })
+                })
+                .transpose()?;
+            // Flags add to (and override) what the query string says
+            let mut query = text
+                .as_deref()
+                .map(Query::parse)
+                .transpose()?
+                .unwrap_or_default();
+            query.regex = regex.or(query.regex);
+            query.entry_type = entry_type.or(query.entry_type);
+            query.favorites_only |= favorites;
+            query.collection_id = collection_id.or(query.collection_id);
+            query.after_ago = days.map(Ago::Days).or(query.after_ago);
+            // Validate before saving (e.g. a bad regex)
+            let matches = db.count_query(&query)?;
+            db.save_search(&name, &query)?;
+            println!(
+                "{}",
+                tf("cli.views.saved", &[("name", &name), ("count", &matches)])
+            );
+            Ok(())
+        }
+        ViewCommands::Ls { json } => {
+            let views = db.get_saved_searches()?;
+            if json {
+                let map: serde_json::Map<String, serde_json::Value> = views
+                    .into_iter()
+                    .map(|(name, query)| Ok((name, serde_json::to_value(query)?)))
+                    .collect::<serde_json::Result<_>>()
+                    .map_err(|e| DitoxError::Other(tf("cli.json_error", &[("error", &e)])))?;
+                let json_output = serde_json::to_string_pretty(&map)
+                    .map_err(|e| DitoxError::Other(tf("cli.json_error", &[("error", &e)])))?;
+                println!("{}", json_output);
+            } else if views.is_empty() {
+                println!("{}", t("cli.views.none"));
+            } else {
+                for (name, query) in views {
+                    let count = db.count_query(&query)?;
+                    println!(
+                        "{:<16} │ {:>5} {} │ {}",
+                        name,
+                        count,
+                        t("cli.entries_column"),
+                        query
+                    );
+                }
+            }
+            Ok(())
+        }
+        ViewCommands::Run { name, limit, json } => {
+            let query = db.get_saved_search(&name)?.ok_or_else(|| {
+                DitoxError::NotFound(tf("cli.views.not_found", &[("name", &name)]))
+            })?;
+            let entries = db.query(&query, limit)?;
+            if json {
+                let json_output = serde_json::to_string_pretty(&entries)
+                    .map_err(|e| DitoxError::Other(tf("cli.json_error", &[("error", &e)])))?;
+                println!("{}", json_output);
+            } else if entries.is_empty() {
+                println!("{}", tf("cli.views.no_matches", &[("name", &name)]));
+            } else {
+                print_entry_table(db, &entries, id_len)?;
+            }
+            Ok(())
+        }
+        ViewCommands::Rm { name } => {
+            if db.delete_saved_search(&name)? {
+                println!("{}", tf("cli.views.deleted", &[("name", &name)]));
+                Ok(())
+            } else {
+                Err(DitoxError::NotFound(tf(
+                    "cli.views.not_found",
+                    &[("name", &name)],
+                )))
+            }
+        }
+    }
+}
+
+fn cmd_incognito(subcmd: IncognitoCommands) -> Result<()> {
+    let running = ditox_core::watcher::is_watcher_running();
+    match subcmd {
+        IncognitoCommands::On => {
+            incognito::write_state(IncognitoState::On)?;
+            if running {
+                println!("{}", t("cli.incognito.on"));
+            } else {
+                println!("{}", t("cli.incognito.on_pending"));
+            }
+        }
+        IncognitoCommands::Off { commit } => {
+            if incognito::read_state() == IncognitoState::Off {
+                println!("{}", t("cli.incognito.not_on"));
+            } else if running {
+                // The watcher picks the request up on its next poll
+                incognito::write_state(if commit {
+                    IncognitoState::EndCommit
+                } else {
+                    IncognitoState::EndDiscard
+                })?;
+                if commit {
+                    println!("{}", t("cli.incognito.off_commit"));
+                } else {
+                    println!("{}", t("cli.incognito.off_discard"));
+                }
+            } else {
+                // No watcher, so nothing was captured
+                incognito::write_state(IncognitoState::Off)?;
+                println!("{}", t("cli.incognito.off"));
+            }
+        }
+        IncognitoCommands::Status => match incognito::read_state() {
+            IncognitoState::Off => println!("{}", t("cli.incognito.status_off")),
+            _ => match ditox_core::watcher::read_watcher_health() {
+                Some(health) if health.incognito => {
+                    println!(
+                        "{}",
+                        tf(
+                            "cli.incognito.status_clips",
+                            &[("count", &health.incognito_clips)]
+                        )
+                    )
+                }
+                _ => println!("{}", t("cli.incognito.status_on")),
+            },
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,139 @@
+//! `--output`: printing command results for people or scripts
+//!
+//! Commands hand their result to [`print`] as a serializable value plus a
+//! closure for the human-readable form. JSON is pretty-printed; TSV prints
+//! one line per item (a header first for lists of objects), with tabs and
+//! newlines in values escaped and nested values as compact JSON.
+
+use clap::ValueEnum;
+use ditox_core::i18n::tf;
+use ditox_core::{DitoxError, Result};
+use serde::Serialize;
+use serde_json::Value as Json;
+use std::io::Write;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Tables and messages
+    #[default]
+    Plain,
+    /// A JSON document
+    Json,
+    /// Tab-separated values
+    Tsv,
+}
+
+impl OutputFormat {
+    /// The format for a command that also has its own `--json` flag
+    pub fn or_json(self, json: bool) -> Self {
+        if json {
+            OutputFormat::Json
+        } else {
+            self
+        }
+    }
+}
+
+/// Print `value` in `format`, or run `plain` for the plain format
+pub fn print<T: Serialize>(
+    format: OutputFormat,
+    value: &T,
+    plain: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    if format == OutputFormat::Plain {
+        return plain();
+    }
+    let value = serde_json::to_value(value).map_err(json_error)?;
+    let lines = match format {
+        OutputFormat::Json => vec![serde_json::to_string_pretty(&value).map_err(json_error)?],
+        _ => tsv_lines(&value),
+    };
+    let mut stdout = std::io::stdout().lock();
+    let written = lines
+        .iter()
+        .try_for_each(|line| writeln!(stdout, "{}", line))
+        .and_then(|_| stdout.flush());
+    match written {
+        // The reader stopped early, e.g. `ditox list --output tsv | head`
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        other => Ok(other?),
+    }
+}
+
+/// `value` as TSV: lists one item per line, objects one key per line
+pub fn tsv_lines(value: &Json) -> Vec<String> {
+    match value {
+        Json::Array(items) => match items.first() {
+            Some(Json::Object(first)) => {
+                let columns: Vec<&String> = first.keys().collect();
+                let header = columns
+                    .iter()
+                    .map(|c| c.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\t");
+                let rows = items.iter().map(|item| {
+                    columns
+                        .iter()
+                        .map(|column| cell(item.get(column.as_str()).unwrap_or(&Json::Null)))
+                        .collect::<Vec<_>>()
+                        .join("\t")
+                });
+                std::iter::once(header).chain(rows).collect()
+            }
+            _ => items.iter().map(cell).collect(),
+        },
+        Json::Object(map) => map
+            .iter()
+            .map(|(key, value)| format!("{}\t{}", escape(key), cell(value)))
+            .collect(),
+        other => vec![cell(other)],
+    }
+}
+
+fn cell(value: &Json) -> String {
+    match value {
+        Json::Null => String::new(),
+        Json::String(s) => escape(s),
+        other => other.to_string(),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn json_error(e: serde_json::Error) -> DitoxError {
+    DitoxError::Other(tf("cli.json_error", &[("error", &e)]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_tsv_rows_objects_and_escaping() {
+        let rows = json!([
+            {"id": "a", "content": "x\ty\nz", "tags": ["t"], "note": null},
+            {"id": "b", "content": "plain", "tags": [], "note": "n"},
+        ]);
+        // serde_json keeps object keys sorted
+        assert_eq!(
+            tsv_lines(&rows),
+            [
+                "content\tid\tnote\ttags",
+                "x\\ty\\nz\ta\t\t[\"t\"]",
+                "plain\tb\tn\t[]"
+            ]
+        );
+        assert_eq!(
+            tsv_lines(&json!({"rust": 3, "todo": 1})),
+            ["rust\t3", "todo\t1"]
+        );
+        assert_eq!(tsv_lines(&json!(["one", "two"])), ["one", "two"]);
+        assert_eq!(tsv_lines(&json!(true)), ["true"]);
+    }
+}
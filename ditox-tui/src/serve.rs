@@ -0,0 +1,459 @@
+//! `ditox serve`: a small web UI over the clip history
+//!
+//! Meant for headless boxes: one page that lists and searches clips (with
+//! image thumbnails), plus the JSON endpoint it reads from. Every request
+//! needs the access token, either as `?token=` or an `Authorization: Bearer`
+//! header. The server is read-only unless started with `--writable`, which
+//! enables deleting and (un)favoriting clips.
+//!
+//! Requests are served one at a time on the calling thread, which is plenty
+//! for a single user and keeps the database on one connection. So that one
+//! client can't hold up the rest, the request head is capped in size and
+//! header count, and every connection has a deadline.
+
+use ditox_core::config::HooksConfig;
+use ditox_core::hooks::{self, HookEvent};
+use ditox_core::i18n::{t, tf};
+use ditox_core::{Database, EntryType, Query, Result};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+/// Clips returned when the page doesn't ask for a limit
+const DEFAULT_LIMIT: usize = 100;
+
+/// Upper bound on `?limit=`
+const MAX_LIMIT: usize = 1000;
+
+/// How long a client may go quiet while sending its request or reading
+/// the response
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long one connection may take in all, however steadily it trickles
+const CONNECTION_DEADLINE: Duration = Duration::from_secs(30);
+
+/// Upper bound on the request line plus headers, in bytes
+const MAX_HEAD_BYTES: u64 = 16 * 1024;
+
+/// Upper bound on the number of headers
+const MAX_HEADERS: usize = 64;
+
+const PAGE: &str = include_str!("serve.html");
+
+pub struct ServeOptions {
+    pub addr: String,
+    pub token: String,
+    pub writable: bool,
+    /// For `on_delete` when a clip is deleted through the page
+    pub hooks: HooksConfig,
+}
+
+pub fn serve(db: &mut Database, options: &ServeOptions) -> Result<()> {
+    let listener = TcpListener::bind(&options.addr)?;
+    let addr = listener.local_addr()?;
+    println!(
+        "{}",
+        tf(
+            "cli.serve.listening",
+            &[("url", &format!("http://{}/?token={}", addr, options.token))]
+        )
+    );
+    if options.writable {
+        println!("{}", t("cli.serve.writable"));
+    }
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!("serve: accept failed: {}", e);
+                continue;
+            }
+        };
+        if let Err(e) = handle_connection(db, options, stream) {
+            tracing::debug!("serve: connection error: {}", e);
+        }
+    }
+    Ok(())
+}
+
+/// Parsed request line and the headers we care about
+#[derive(Debug, Default)]
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    bearer: Option<String>,
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn new(status: &'static str, content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            content_type,
+            body: body.into(),
+        }
+    }
+
+    fn text(status: &'static str) -> Self {
+        Self::new(status, "text/plain; charset=utf-8", status)
+    }
+
+    /// 204, which must not have a body
+    fn no_content() -> Self {
+        Self::new("204 No Content", "text/plain; charset=utf-8", Vec::new())
+    }
+
+    fn json(value: &serde_json::Value) -> Self {
+        Self::new(
+            "200 OK",
+            "application/json",
+            serde_json::to_vec(value).unwrap_or_default(),
+        )
+    }
+}
+
+/// A connection whose reads and writes fail once its deadline has passed
+struct Deadline<'a> {
+    stream: &'a TcpStream,
+    until: Instant,
+}
+
+impl Deadline<'_> {
+    /// Time left for the next read or write, at most [`IO_TIMEOUT`]
+    fn timeout(&self) -> std::io::Result<Duration> {
+        match self.until.checked_duration_since(Instant::now()) {
+            Some(left) if !left.is_zero() => Ok(left.min(IO_TIMEOUT)),
+            _ => Err(std::io::ErrorKind::TimedOut.into()),
+        }
+    }
+}
+
+impl Read for Deadline<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.stream.set_read_timeout(Some(self.timeout()?))?;
+        self.stream.read(buf)
+    }
+}
+
+impl Write for Deadline<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.stream.set_write_timeout(Some(self.timeout()?))?;
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+fn handle_connection(db: &mut Database, options: &ServeOptions, stream: TcpStream) -> Result<()> {
+    let until = Instant::now() + CONNECTION_DEADLINE;
+    let reader = BufReader::new(Deadline {
+        stream: &stream,
+        until,
+    });
+    let response = match read_request(reader)? {
+        Some(request) => handle(db, options, &request),
+        None => Response::text("400 Bad Request"),
+    };
+
+    let mut stream = Deadline {
+        stream: &stream,
+        until,
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Read the request line and headers. `None` for a malformed request, or
+/// one whose head is over [`MAX_HEAD_BYTES`] or [`MAX_HEADERS`].
+fn read_request(reader: impl BufRead) -> Result<Option<Request>> {
+    let mut reader = reader.take(MAX_HEAD_BYTES);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    if !line.ends_with('\n') {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(None);
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = Request {
+        method: method.to_string(),
+        path: percent_decode(path),
+        query: parse_query(query),
+        bearer: None,
+    };
+
+    for _ in 0..=MAX_HEADERS {
+        line.clear();
+        reader.read_line(&mut line)?;
+        if !line.ends_with('\n') {
+            // Cut off by the size cap or the client
+            return Ok(None);
+        }
+        if line.trim().is_empty() {
+            return Ok(Some(request));
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("authorization") {
+                request.bearer = value
+                    .trim()
+                    .strip_prefix("Bearer ")
+                    .map(|token| token.trim().to_string());
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn handle(db: &mut Database, options: &ServeOptions, request: &Request) -> Response {
+    let token = request
+        .bearer
+        .as_deref()
+        .or(request.query.get("token").map(String::as_str));
+    if !token.is_some_and(|token| constant_time_eq(token, &options.token)) {
+        return Response::text("401 Unauthorized");
+    }
+
+    let segments: Vec<&str> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+    let result = match (request.method.as_str(), segments.as_slice()) {
+        ("GET", []) => Ok(Response::new(
+            "200 OK",
+            "text/html; charset=utf-8",
+            PAGE.replace("__WRITABLE__", &options.writable.to_string()),
+        )),
+        ("GET", ["api", "clips"]) => list_clips(db, &request.query),
+        ("GET", ["image", id]) => image(db, id),
+        ("POST", ["api", "clips", id, "favorite"]) if options.writable => {
+            db.toggle_favorite(id).map(|_| Response::no_content())
+        }
+        ("DELETE", ["api", "clips", id]) if options.writable => delete_clip(db, options, id),
+        ("POST" | "DELETE", _) => Ok(Response::text("403 Forbidden")),
+        _ => Ok(Response::text("404 Not Found")),
+    };
+    result.unwrap_or_else(|e| {
+        tracing::warn!("serve: {} {} failed: {}", request.method, request.path, e);
+        Response::text("500 Internal Server Error")
+    })
+}
+
+fn delete_clip(db: &mut Database, options: &ServeOptions, id: &str) -> Result<Response> {
+    let Some(entry) = db.get_by_id(id)? else {
+        return Ok(Response::text("404 Not Found"));
+    };
+    if !db.delete(id)? {
+        return Ok(Response::text("404 Not Found"));
+    }
+    hooks::run(&options.hooks, HookEvent::Delete, &entry);
+    Ok(Response::no_content())
+}
+
+fn list_clips(db: &Database, params: &HashMap<String, String>) -> Result<Response> {
+    let limit = params
+        .get("limit")
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(DEFAULT_LIMIT)
+        .min(MAX_LIMIT);
+    let query = params.get("q").map(String::as_str).unwrap_or_default();
+    let parsed = match Query::parse(query) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            return Ok(Response::new(
+                "400 Bad Request",
+                "text/plain",
+                e.to_string(),
+            ))
+        }
+    };
+    let entries = crate::search_entries(db, &parsed, None, limit)?;
+    let values = entries
+        .iter()
+        .map(|entry| {
+            let mut value = serde_json::to_value(entry).unwrap_or_default();
+            if entry.entry_type == EntryType::Text {
+                value["preview"] = entry.preview(200).into();
+            }
+            value
+        })
+        .collect();
+    Ok(Response::json(&serde_json::Value::Array(values)))
+}
+
+fn image(db: &Database, id: &str) -> Result<Response> {
+    let Some(entry) = db.get_by_id(id)? else {
+        return Ok(Response::text("404 Not Found"));
+    };
+    if entry.entry_type != EntryType::Image {
+        return Ok(Response::text("404 Not Found"));
+    }
+    let ext = entry.image_extension.as_deref().unwrap_or("png");
+    let content_type = match ext {
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        _ => "image/png",
+    };
+    match std::fs::read(Database::image_path(&entry.hash, ext)?) {
+        Ok(bytes) => Ok(Response::new("200 OK", content_type, bytes)),
+        Err(_) => Ok(Response::text("404 Not Found")),
+    }
+}
+
+/// Compare tokens without leaking the matching prefix length through timing
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' => match bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    out.push(byte);
+                    i += 2;
+                }
+                None => out.push(b'%'),
+            },
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ditox_core::Entry;
+    use tempfile::TempDir;
+
+    fn options(writable: bool) -> ServeOptions {
+        ServeOptions {
+            addr: "127.0.0.1:0".to_string(),
+            token: "secret".to_string(),
+            writable,
+            hooks: HooksConfig::default(),
+        }
+    }
+
+    fn request(method: &str, target: &str) -> Request {
+        let raw = format!("{} {} HTTP/1.1\r\nHost: x\r\n\r\n", method, target);
+        read_request(raw.as_bytes()).unwrap().unwrap()
+    }
+
+    fn sample_db(dir: &TempDir) -> Database {
+        let db = Database::open_at(dir.path().join("ditox.db")).unwrap();
+        db.init_schema().unwrap();
+        let mut entry = Entry::new_text("deploy key".to_string());
+        entry.id = "clip".to_string();
+        db.insert(&entry).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_percent_decoding() {
+        assert_eq!(percent_decode("tag%3Awork+key"), "tag:work key");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn test_oversized_or_truncated_heads_are_refused() {
+        let long_line = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(20_000));
+        assert!(read_request(long_line.as_bytes()).unwrap().is_none());
+
+        let many_headers = format!("GET / HTTP/1.1\r\n{}\r\n", "X-A: b\r\n".repeat(100));
+        assert!(read_request(many_headers.as_bytes()).unwrap().is_none());
+
+        assert!(read_request("GET / HTTP/1.1\r\nHost: x".as_bytes())
+            .unwrap()
+            .is_none());
+        assert!(read_request("GET / HTTP/1.1\r\nHost: x\r\n\r\n".as_bytes())
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_requires_token() {
+        let dir = TempDir::new().unwrap();
+        let mut db = sample_db(&dir);
+        let response = handle(&mut db, &options(false), &request("GET", "/api/clips"));
+        assert_eq!(response.status, "401 Unauthorized");
+
+        let mut authed = request("GET", "/api/clips?q=deploy");
+        authed.bearer = Some("secret".to_string());
+        let response = handle(&mut db, &options(false), &authed);
+        assert_eq!(response.status, "200 OK");
+        assert!(String::from_utf8_lossy(&response.body).contains("deploy key"));
+    }
+
+    #[test]
+    fn test_read_only_unless_writable() {
+        let dir = TempDir::new().unwrap();
+        let mut db = sample_db(&dir);
+        let delete = request("DELETE", "/api/clips/clip?token=secret");
+        assert_eq!(
+            handle(&mut db, &options(false), &delete).status,
+            "403 Forbidden"
+        );
+        assert!(db.get_by_id("clip").unwrap().is_some());
+
+        let marker = dir.path().join("deleted");
+        let mut writable = options(true);
+        if cfg!(unix) {
+            writable.hooks.on_delete = Some(format!("cat > '{}'", marker.display()));
+        }
+        let response = handle(&mut db, &writable, &delete);
+        assert_eq!(response.status, "204 No Content");
+        assert!(response.body.is_empty());
+        assert!(db.get_by_id("clip").unwrap().is_none());
+        if cfg!(unix) {
+            assert_eq!(std::fs::read_to_string(&marker).unwrap(), "deploy key");
+        }
+
+        assert_eq!(handle(&mut db, &writable, &delete).status, "404 Not Found");
+    }
+}
@@ -0,0 +1,176 @@
+//! Text transforms applied on `ditox copy --transform`
+//!
+//! Built-in transforms are plain functions. Any other name is looked up in
+//! the `[transforms]` config table, which maps names to shell commands that
+//! get the text on stdin and print the result, e.g. `slug = "tr ' ' -"`.
+//! Transforms chain left to right.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ditox_core::entry::strip_ansi_escapes;
+use ditox_core::i18n::tf;
+use ditox_core::{DitoxError, Result};
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+type Builtin = fn(&str) -> Result<String>;
+
+/// Built-in transforms by name
+const BUILTINS: &[(&str, Builtin)] = &[
+    ("trim", |s| Ok(s.trim().to_string())),
+    ("upper", |s| Ok(s.to_uppercase())),
+    ("lower", |s| Ok(s.to_lowercase())),
+    ("json-pretty", json_pretty),
+    ("base64", |s| Ok(STANDARD.encode(s))),
+    ("strip-ansi", |s| Ok(strip_ansi_escapes(s))),
+];
+
+/// Built-in transforms plus the user's shell-command ones
+pub struct Registry<'a> {
+    commands: &'a HashMap<String, String>,
+}
+
+impl<'a> Registry<'a> {
+    pub fn new(commands: &'a HashMap<String, String>) -> Self {
+        Self { commands }
+    }
+
+    /// Run the transforms `names` over `text`, in order
+    pub fn apply(&self, names: &[String], text: &str) -> Result<String> {
+        let mut text = text.to_string();
+        for name in names {
+            text = self.apply_one(name, &text)?;
+        }
+        Ok(text)
+    }
+
+    fn apply_one(&self, name: &str, text: &str) -> Result<String> {
+        if let Some((_, f)) = BUILTINS.iter().find(|(n, _)| *n == name) {
+            return f(text);
+        }
+        match self.commands.get(name) {
+            Some(command) => run_command(name, command, text),
+            None => Err(DitoxError::Other(tf(
+                "cli.transform.unknown",
+                &[("name", &name), ("available", &self.names().join(", "))],
+            ))),
+        }
+    }
+
+    /// Every transform name, built-ins first
+    pub fn names(&self) -> Vec<String> {
+        let mut user: Vec<String> = self
+            .commands
+            .keys()
+            .filter(|name| !BUILTINS.iter().any(|(n, _)| n == name))
+            .cloned()
+            .collect();
+        user.sort();
+        BUILTINS
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .chain(user)
+            .collect()
+    }
+}
+
+fn json_pretty(text: &str) -> Result<String> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| DitoxError::Other(tf("cli.transform.not_json", &[("error", &e)])))?;
+    serde_json::to_string_pretty(&value).map_err(|e| DitoxError::Other(e.to_string()))
+}
+
+/// Pipe `text` through `command` in the platform shell
+fn run_command(name: &str, command: &str, text: &str) -> Result<String> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let mut child = shell
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            DitoxError::Other(tf(
+                "cli.transform.failed",
+                &[("name", &name), ("error", &e)],
+            ))
+        })?;
+
+    // Write from another thread so a command that prints before reading
+    // everything can't fill its stdout pipe and stall both sides
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = text.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output()?;
+    // A command that ignores its input closes the pipe early; that's fine
+    let _ = writer.join();
+
+    if !output.status.success() {
+        let error = format!(
+            "{}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return Err(DitoxError::Other(tf(
+            "cli.transform.failed",
+            &[("name", &name), ("error", &error)],
+        )));
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|_| DitoxError::Other(tf("cli.transform.not_utf8", &[("name", &name)])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_builtins_chain_in_order() {
+        let commands = HashMap::new();
+        let registry = Registry::new(&commands);
+        let text = "  \x1b[31m{\"a\":1}\x1b[0m \n";
+        assert_eq!(
+            registry
+                .apply(&names(&["strip-ansi", "trim", "json-pretty"]), text)
+                .unwrap(),
+            "{\n  \"a\": 1\n}"
+        );
+        assert_eq!(
+            registry.apply(&names(&["upper", "base64"]), "hi").unwrap(),
+            "SEk="
+        );
+        assert!(registry.apply(&names(&["nope"]), "x").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shell_transforms_from_config() {
+        let commands = HashMap::from([
+            ("slug".to_string(), "tr ' ' -".to_string()),
+            ("fail".to_string(), "echo broken >&2; exit 3".to_string()),
+        ]);
+        let registry = Registry::new(&commands);
+        assert_eq!(
+            registry
+                .apply(&names(&["trim", "slug"]), " a b c ")
+                .unwrap(),
+            "a-b-c"
+        );
+        let err = registry.apply(&names(&["fail"]), "x").unwrap_err();
+        assert!(err.to_string().contains("broken"), "{}", err);
+        assert_eq!(registry.names().last().unwrap(), "slug");
+    }
+}
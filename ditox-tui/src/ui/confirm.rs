@@ -2,6 +2,8 @@
 
 use crate::ui::theme::Theme;
 use ditox_core::app::App;
+use ditox_core::confirm::{ConfirmStyle, CONFIRM_PHRASE};
+use ditox_core::i18n::{t, tf};
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
@@ -16,9 +18,14 @@ pub fn draw(frame: &mut Frame, app: &App, theme: &Theme) {
     };
 
     // Calculate popup dimensions
+    let (min_width, lines) = if app.confirm_style == ConfirmStyle::Typed {
+        (50, 6)
+    } else {
+        (30, 5)
+    };
     let msg_width = message.len() as u16 + 4;
-    let popup_width = msg_width.max(30).min(area.width.saturating_sub(4));
-    let popup_height = 5u16.min(area.height.saturating_sub(4));
+    let popup_width = msg_width.max(min_width).min(area.width.saturating_sub(4));
+    let popup_height = lines.min(area.height.saturating_sub(4));
 
     // Center the popup
     let popup_area = Rect {
@@ -32,7 +39,15 @@ pub fn draw(frame: &mut Frame, app: &App, theme: &Theme) {
     frame.render_widget(Clear, popup_area);
 
     // Build the content
-    let content = format!("{}\n\n[y/Enter] Confirm  [n/Esc] Cancel", message);
+    let content = match app.confirm_style {
+        ConfirmStyle::Typed => format!(
+            "{}\n\n> {}█\n{}",
+            message,
+            app.confirm_input,
+            tf("confirm.typed_hint", &[("phrase", &CONFIRM_PHRASE)])
+        ),
+        _ => format!("{}\n\n{}", message, t("confirm.hint")),
+    };
 
     let dialog = Paragraph::new(content)
         .style(theme.normal())
@@ -41,7 +56,7 @@ pub fn draw(frame: &mut Frame, app: &App, theme: &Theme) {
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Yellow))
-                .title(" Confirm ")
+                .title(format!(" {} ", t("confirm.title")))
                 .title_style(
                     Style::default()
                         .fg(Color::Yellow)
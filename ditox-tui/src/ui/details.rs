@@ -0,0 +1,87 @@
+//! Full-screen detail view of one clip
+
+use crate::ui::layout::format_size;
+use crate::ui::theme::Theme;
+use ditox_core::app::App;
+use ditox_core::entry::EntryType;
+use ditox_core::i18n::{t, tf};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+pub fn draw(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
+    let Some(details) = &app.details else {
+        return;
+    };
+    let entry = &details.entry;
+
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<12}", t(label)), theme.accent()),
+            Span::styled(value, theme.normal()),
+        ])
+    };
+    let time = |at: &chrono::DateTime<chrono::Utc>| {
+        at.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    };
+
+    let mut info = vec![
+        field("details.id", entry.id.clone()),
+        field("details.created", time(&entry.created_at)),
+        field("details.last_used", time(&entry.last_used)),
+        field(
+            "details.used",
+            tf("details.times", &[("count", &details.usage_count)]),
+        ),
+        field("details.size", format_size(entry.byte_size)),
+        field("details.sha256", entry.hash.clone()),
+    ];
+    if !details.tags.is_empty() {
+        info.push(field("details.tags", details.tags.join(", ")));
+    }
+    if entry.favorite {
+        info.push(field("details.favorite", t("details.yes")));
+    }
+    if let Some(notes) = &entry.notes {
+        info.push(field("details.note", notes.clone()));
+    }
+    for (key, value) in &details.meta {
+        info.push(field("details.meta", format!("{} = {}", key, value)));
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(info.len() as u16 + 2),
+            Constraint::Min(3),
+            Constraint::Length(1), // Status
+        ])
+        .split(area);
+
+    let info_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border())
+        .title(format!(" {} ", t("details.title")))
+        .title_style(theme.title());
+    frame.render_widget(Paragraph::new(info).block(info_block), chunks[0]);
+
+    let content = match entry.entry_type {
+        EntryType::Text => entry.sanitized_content(),
+        EntryType::Image => tf("details.image", &[("path", &entry.content)]),
+    };
+    let content_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.border())
+        .title(format!(" {} ", t("details.content")))
+        .title_style(theme.title());
+    let paragraph = Paragraph::new(content)
+        .style(theme.normal())
+        .wrap(Wrap { trim: false })
+        .scroll((app.details_scroll.min(u16::MAX as usize) as u16, 0))
+        .block(content_block);
+    frame.render_widget(paragraph, chunks[1]);
+
+    let status = Paragraph::new(format!(" {}", t("details.hint"))).style(theme.muted());
+    frame.render_widget(status, chunks[2]);
+}
@@ -1,6 +1,7 @@
 use crate::keybindings::KeybindingResolver;
 use crate::ui::theme::Theme;
 use ditox_core::actions::Action;
+use ditox_core::i18n::t;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
@@ -9,7 +10,7 @@ pub fn draw(frame: &mut Frame, theme: &Theme, keybindings: &KeybindingResolver)
 
     // Center the help popup
     let popup_width = 56.min(area.width.saturating_sub(4));
-    let popup_height = 28.min(area.height.saturating_sub(4));
+    let popup_height = 35.min(area.height.saturating_sub(4));
 
     let popup_area = Rect {
         x: (area.width - popup_width) / 2,
@@ -28,7 +29,7 @@ pub fn draw(frame: &mut Frame, theme: &Theme, keybindings: &KeybindingResolver)
         Block::default()
             .borders(Borders::ALL)
             .border_style(theme.accent())
-            .title(" Help ")
+            .title(format!(" {} ", t("help.title")))
             .title_style(theme.title()),
     );
 
@@ -39,76 +40,104 @@ pub fn draw(frame: &mut Frame, theme: &Theme, keybindings: &KeybindingResolver)
 fn key_for(keybindings: &KeybindingResolver, action: Action) -> String {
     keybindings
         .get_primary_key(action)
-        .unwrap_or_else(|| "N/A".to_string())
+        .unwrap_or_else(|| t("help.unbound"))
 }
 
-/// Generate help text dynamically from keybindings
+/// Generate help text dynamically from keybindings. Each section lists
+/// (keys, label message) pairs, laid out two per row.
 fn generate_help(keybindings: &KeybindingResolver) -> String {
-    format!(
-        r#"
-  Navigation
-  ──────────
-  {:>10}  Down       {:>10}  Top
-  {:>10}  Up         {:>10}  Bottom
-  {:>10}  Prev page  {:>10}  Next page
-  {:>10}  Prev/Next tab
-
-  Actions
-  ───────
-  {:>10}  Copy & exit   {:>10}  Delete
-  {:>10}  Copy          {:>10}  Clear all
-  {:>10}  Toggle fav    {:>10}  Edit note
-
-  Search
-  ──────
-  {:>10}  Start search  {:>10}  Regex mode
-  {:>10}  Toggle mode   {:>10}  Clear/exit
+    let key = |action| key_for(keybindings, action);
+    let sections: [(&str, Vec<(String, &str)>); 5] = [
+        (
+            "help.navigation",
+            vec![
+                (key(Action::MoveDown), "help.down"),
+                (key(Action::GoTop), "help.top"),
+                (key(Action::MoveUp), "help.up"),
+                (key(Action::GoBottom), "help.bottom"),
+                (key(Action::PrevPage), "help.prev_page"),
+                (key(Action::NextPage), "help.next_page"),
+                (
+                    format!("{}/{}", key(Action::PrevTab), key(Action::NextTab)),
+                    "help.tabs",
+                ),
+                (format!("{}..9", key(Action::View1)), "help.saved_view"),
+            ],
+        ),
+        (
+            "help.actions",
+            vec![
+                (key(Action::CopyAndQuit), "help.copy_and_exit"),
+                (key(Action::CopyAndType), "help.copy_and_type"),
+                (key(Action::Delete), "help.delete"),
+                (key(Action::UndoDelete), "help.undo_delete"),
+                (key(Action::Copy), "help.copy"),
+                (key(Action::ClearAll), "help.clear_all"),
+                (key(Action::ToggleFavorite), "help.toggle_favorite"),
+                (key(Action::EditAnnotation), "help.edit_note"),
+                (key(Action::EditInEditor), "help.edit_clip"),
+                (key(Action::EditInEditorAsNew), "help.edit_clip_as_new"),
+                (key(Action::EditTags), "help.edit_tags"),
+            ],
+        ),
+        (
+            "help.search",
+            vec![
+                (key(Action::EnterSearch), "help.start_search"),
+                (key(Action::EnterRegexSearch), "help.regex_mode"),
+                (key(Action::ToggleSearchMode), "help.toggle_mode"),
+                (key(Action::ExitSearch), "help.clear_exit"),
+            ],
+        ),
+        (
+            "help.multi_select",
+            vec![
+                (key(Action::ToggleMultiSelect), "help.toggle_mode"),
+                (key(Action::SelectCurrent), "help.select"),
+                (key(Action::SelectAll), "help.select_all"),
+                (key(Action::MergeSelected), "help.merge"),
+            ],
+        ),
+        (
+            "help.view",
+            vec![
+                (key(Action::ToggleExpanded), "help.expand"),
+                (key(Action::TogglePreview), "help.preview"),
+                (key(Action::ShowDetails), "help.details"),
+                (key(Action::CyclePreviewMode), "help.preview_mode"),
+                (key(Action::CycleSort), "help.cycle_sort"),
+                (key(Action::ToggleLineNumbers), "help.line_numbers"),
+                (key(Action::ToggleHelp), "help.help"),
+                (key(Action::Quit), "help.quit"),
+            ],
+        ),
+    ];
 
-  Multi-select
-  ────────────
-  {:>10}  Toggle mode   {:>10}  Select
-  {:>10}  Select all
+    // Pad left-column labels to the widest one so the right column lines up
+    let label_width = sections
+        .iter()
+        .flat_map(|(_, items)| items.iter().step_by(2))
+        .map(|(_, label)| t(label).chars().count())
+        .max()
+        .unwrap_or(0);
 
-  View
-  ────
-  {:>10}  Expand        {:>10}  Preview
-  {:>10}  Preview mode  {:>10}  Line numbers
-  {:>10}  Help          {:>10}  Quit
-"#,
-        // Navigation
-        key_for(keybindings, Action::MoveDown),
-        key_for(keybindings, Action::GoTop),
-        key_for(keybindings, Action::MoveUp),
-        key_for(keybindings, Action::GoBottom),
-        key_for(keybindings, Action::PrevPage),
-        key_for(keybindings, Action::NextPage),
-        format!(
-            "{}/{}",
-            key_for(keybindings, Action::PrevTab),
-            key_for(keybindings, Action::NextTab)
-        ),
-        // Actions
-        key_for(keybindings, Action::CopyAndQuit),
-        key_for(keybindings, Action::Delete),
-        key_for(keybindings, Action::Copy),
-        key_for(keybindings, Action::ClearAll),
-        key_for(keybindings, Action::ToggleFavorite),
-        key_for(keybindings, Action::EditAnnotation),
-        // Search
-        key_for(keybindings, Action::EnterSearch),
-        key_for(keybindings, Action::EnterRegexSearch),
-        key_for(keybindings, Action::ToggleSearchMode),
-        key_for(keybindings, Action::ExitSearch),
-        // Multi-select
-        key_for(keybindings, Action::ToggleMultiSelect),
-        key_for(keybindings, Action::SelectCurrent),
-        key_for(keybindings, Action::SelectAll),
-        // View
-        key_for(keybindings, Action::ToggleExpanded),
-        key_for(keybindings, Action::TogglePreview),
-        key_for(keybindings, Action::CyclePreviewMode),
-        key_for(keybindings, Action::ToggleLineNumbers),
-        key_for(keybindings, Action::ToggleHelp),
-        key_for(keybindings, Action::Quit),
-    )
+    let mut text = String::new();
+    for (title, items) in &sections {
+        let title = t(title);
+        text.push_str(&format!(
+            "\n  {}\n  {}\n",
+            title,
+            "─".repeat(title.chars().count())
+        ));
+        for pair in items.chunks(2) {
+            let (keys, label) = &pair[0];
+            let mut row = format!("  {:>10}  {:<width$}", keys, t(label), width = label_width);
+            if let Some((keys, label)) = pair.get(1) {
+                row.push_str(&format!("  {:>10}  {}", keys, t(label)));
+            }
+            text.push_str(row.trim_end());
+            text.push('\n');
+        }
+    }
+    text
 }
@@ -0,0 +1,296 @@
+//! Lightweight syntax coloring for the preview pane
+//!
+//! Not a parser: each line is split into strings, numbers, comments,
+//! keywords and the rest, which is enough to make copied JSON and code
+//! readable at a glance. The language comes from content type detection.
+
+use crate::ui::theme::Theme;
+use ditox_core::content_type::{self, ContentType};
+use ratatui::prelude::*;
+
+const KEYWORDS: &[&str] = &[
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "class",
+    "const",
+    "continue",
+    "def",
+    "default",
+    "defer",
+    "do",
+    "done",
+    "elif",
+    "else",
+    "enum",
+    "esac",
+    "except",
+    "export",
+    "extends",
+    "false",
+    "False",
+    "fi",
+    "finally",
+    "fn",
+    "for",
+    "from",
+    "func",
+    "function",
+    "if",
+    "impl",
+    "import",
+    "in",
+    "interface",
+    "lambda",
+    "let",
+    "local",
+    "loop",
+    "match",
+    "mod",
+    "mut",
+    "new",
+    "nil",
+    "None",
+    "null",
+    "package",
+    "private",
+    "pub",
+    "public",
+    "raise",
+    "return",
+    "self",
+    "Self",
+    "static",
+    "struct",
+    "switch",
+    "then",
+    "this",
+    "trait",
+    "true",
+    "True",
+    "try",
+    "type",
+    "use",
+    "var",
+    "void",
+    "where",
+    "while",
+    "with",
+    "yield",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    Json,
+    /// Code and shell; `hash_comments` enables `# ...` comments
+    Code {
+        hash_comments: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Plain,
+    Key,
+    Str,
+    Number,
+    Keyword,
+    Comment,
+}
+
+/// Colored lines for `content`, or `None` if it isn't JSON or code
+pub fn highlight(content: &str, theme: &Theme) -> Option<Vec<Line<'static>>> {
+    let lang = match content_type::detect(content) {
+        ContentType::Json => Lang::Json,
+        ContentType::Code => Lang::Code {
+            hash_comments: false,
+        },
+        ContentType::Shell | ContentType::Yaml => Lang::Code {
+            hash_comments: true,
+        },
+        _ => return None,
+    };
+    let lines = content
+        .lines()
+        .map(|line| {
+            let spans: Vec<Span<'static>> = tokenize(line, lang)
+                .into_iter()
+                .map(|(token, text)| Span::styled(text, style_of(token, theme)))
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+    Some(lines)
+}
+
+fn style_of(token: Token, theme: &Theme) -> Style {
+    match token {
+        Token::Plain => theme.normal(),
+        Token::Key => theme.syntax_key(),
+        Token::Str => theme.syntax_string(),
+        Token::Number => theme.syntax_number(),
+        Token::Keyword => theme.syntax_keyword(),
+        Token::Comment => theme.syntax_comment(),
+    }
+}
+
+/// Split one line into runs of the same token kind
+fn tokenize(line: &str, lang: Lang) -> Vec<(Token, String)> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out: Vec<(Token, String)> = Vec::new();
+    let mut push = |token: Token, text: String| match out.last_mut() {
+        Some((last, run)) if *last == token => run.push_str(&text),
+        _ => out.push((token, text)),
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let rest = &chars[i..];
+        let word_start = i == 0 || !is_word_char(chars[i - 1]);
+
+        if let Lang::Code { hash_comments } = lang {
+            let slashes = rest.starts_with(&['/', '/']);
+            let hash = hash_comments && c == '#' && (i == 0 || chars[i - 1].is_whitespace());
+            if slashes || hash {
+                push(Token::Comment, rest.iter().collect());
+                break;
+            }
+        }
+
+        // A quote right after a letter is an apostrophe ("it's"), not a string
+        let quote = c == '"' || (matches!(lang, Lang::Code { .. }) && (c == '\'' || c == '`'));
+        if quote && word_start {
+            if let Some(len) = quoted_len(rest) {
+                let text: String = rest[..len].iter().collect();
+                let after = chars[i + len..].iter().find(|c| !c.is_whitespace());
+                let token = if lang == Lang::Json && after == Some(&':') {
+                    Token::Key
+                } else {
+                    Token::Str
+                };
+                push(token, text);
+                i += len;
+                continue;
+            }
+        }
+
+        if c.is_ascii_digit() && word_start {
+            let len = rest
+                .iter()
+                .take_while(|&&c| is_word_char(c) || c == '.')
+                .count();
+            push(Token::Number, rest[..len].iter().collect());
+            i += len;
+            continue;
+        }
+
+        if is_word_char(c) && word_start {
+            let len = rest.iter().take_while(|&&c| is_word_char(c)).count();
+            let word: String = rest[..len].iter().collect();
+            let token = if KEYWORDS.contains(&word.as_str()) {
+                Token::Keyword
+            } else {
+                Token::Plain
+            };
+            push(token, word);
+            i += len;
+            continue;
+        }
+
+        push(Token::Plain, c.to_string());
+        i += 1;
+    }
+    out
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Length of the quoted string at the start of `chars`, closing quote
+/// included, or `None` if it isn't closed on this line
+fn quoted_len(chars: &[char]) -> Option<usize> {
+    let quote = chars[0];
+    let mut escaped = false;
+    for (i, &c) in chars.iter().enumerate().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if c == quote => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(line: &str, lang: Lang) -> Vec<(Token, String)> {
+        tokenize(line, lang)
+    }
+
+    fn runs(expected: &[(Token, &str)]) -> Vec<(Token, String)> {
+        expected.iter().map(|&(t, s)| (t, s.to_string())).collect()
+    }
+
+    #[test]
+    fn json_keys_differ_from_values() {
+        assert_eq!(
+            kinds(r#"  "name": "ditox", "n": 3,"#, Lang::Json),
+            runs(&[
+                (Token::Plain, "  "),
+                (Token::Key, r#""name""#),
+                (Token::Plain, ": "),
+                (Token::Str, r#""ditox""#),
+                (Token::Plain, ", "),
+                (Token::Key, r#""n""#),
+                (Token::Plain, ": "),
+                (Token::Number, "3"),
+                (Token::Plain, ","),
+            ])
+        );
+    }
+
+    #[test]
+    fn code_keywords_strings_and_comments() {
+        let code = Lang::Code {
+            hash_comments: false,
+        };
+        assert_eq!(
+            kinds(r#"let s = "a \" b"; // done"#, code),
+            runs(&[
+                (Token::Keyword, "let"),
+                (Token::Plain, " s = "),
+                (Token::Str, r#""a \" b""#),
+                (Token::Plain, "; "),
+                (Token::Comment, "// done"),
+            ])
+        );
+    }
+
+    #[test]
+    fn apostrophes_and_unclosed_quotes_stay_plain() {
+        let shell = Lang::Code {
+            hash_comments: true,
+        };
+        assert_eq!(
+            kinds("echo it's x1 'open # c", shell),
+            runs(&[
+                (Token::Plain, "echo it's x1 'open "),
+                (Token::Comment, "# c"),
+            ])
+        );
+    }
+
+    #[test]
+    fn only_json_and_code_are_highlighted() {
+        let theme = Theme::default();
+        assert!(highlight("just some words", &theme).is_none());
+        assert!(highlight("{\"a\": [1, 2]}", &theme).is_some());
+    }
+}
@@ -1,13 +1,18 @@
 use crate::keybindings::KeybindingResolver;
 use crate::ui::confirm;
+use crate::ui::details;
 use crate::ui::help;
 use crate::ui::list;
 use crate::ui::note_editor;
 use crate::ui::preview::{self, ImageCache, ImageLoader};
 use crate::ui::search;
 use crate::ui::tabs;
+use crate::ui::tag_editor;
 use crate::ui::theme::Theme;
 use ditox_core::app::{App, InputMode};
+use ditox_core::config::PreviewPosition;
+use ditox_core::i18n::{t, tf};
+use ditox_core::watcher::WatcherStatus;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 use ratatui_image::picker::Picker;
@@ -48,6 +53,12 @@ pub fn draw(
         return;
     }
 
+    // Detail view of the selected entry
+    if app.details.is_some() {
+        details::draw(frame, app, theme, area);
+        return;
+    }
+
     // Build layout based on whether tabs are shown (respecting narrow terminal)
     let chunks = if effective_show_tabs {
         Layout::default()
@@ -85,11 +96,21 @@ pub fn draw(
     // Search bar
     search::draw(frame, app, theme, search_chunk);
 
-    // Content area (list + optional preview)
-    if app.show_preview && area.width > 60 {
+    // Content area (list + optional preview, beside or below it)
+    let ui = &app.config().ui;
+    let direction = match ui.preview_position {
+        PreviewPosition::Right if area.width > 60 => Some(Direction::Horizontal),
+        PreviewPosition::Bottom if content_chunk.height >= 12 => Some(Direction::Vertical),
+        _ => None,
+    };
+    if let Some(direction) = direction.filter(|_| app.show_preview) {
+        let size = ui.preview_size.clamp(20, 80);
         let content_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .direction(direction)
+            .constraints([
+                Constraint::Percentage(100 - size),
+                Constraint::Percentage(size),
+            ])
             .split(content_chunk);
 
         list::draw(frame, app, theme, content_chunks[0]);
@@ -101,6 +122,9 @@ pub fn draw(
     // Status bar
     draw_status(frame, app, theme, status_chunk, effective_show_snippets);
 
+    // Tag completion popup (over the list)
+    search::draw_tag_completions(frame, app, theme, search_chunk);
+
     // Help overlay
     if app.show_help {
         help::draw(frame, theme, keybindings);
@@ -111,6 +135,11 @@ pub fn draw(
         note_editor::render(frame, &app.note_input, theme);
     }
 
+    // Tag editor overlay
+    if app.input_mode == InputMode::EditTags {
+        tag_editor::render(frame, app, theme);
+    }
+
     // Confirmation dialog overlay
     if app.input_mode == InputMode::Confirm {
         confirm::draw(frame, app, theme);
@@ -150,7 +179,7 @@ fn draw_expanded(
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(theme.border())
-        .title(" Expanded Preview (Esc to close) ")
+        .title(format!(" {} ", t("expanded.title")))
         .title_style(theme.title());
 
     let inner = block.inner(chunks[0]);
@@ -175,7 +204,10 @@ fn draw_expanded(
                     ])
                     .split(inner);
 
-                let path = &entry.content;
+                // `entry.content` is the blob hash, not a path
+                let path_buf = entry.image_path().unwrap_or_default();
+                let path = path_buf.to_string_lossy().into_owned();
+                let path: &str = &path;
 
                 // Render image
                 if let Some(protocol) = cache.get_mut(path) {
@@ -184,29 +216,31 @@ fn draw_expanded(
                 } else if !cache.is_pending(path) {
                     cache.set_pending(path);
                     loader.request_load(path);
-                    let placeholder = Paragraph::new("Loading image...")
+                    let placeholder = Paragraph::new(t("preview.loading_image"))
                         .style(theme.muted())
                         .alignment(Alignment::Center);
                     frame.render_widget(placeholder, img_chunks[0]);
                 } else {
-                    let placeholder = Paragraph::new("Loading image...")
+                    let placeholder = Paragraph::new(t("preview.loading_image"))
                         .style(theme.muted())
                         .alignment(Alignment::Center);
                     frame.render_widget(placeholder, img_chunks[0]);
                 }
 
                 // Render info
-                let dimensions_str = if let Ok((w, h)) = image::image_dimensions(&entry.content) {
+                let dimensions_str = if let Some((w, h)) = entry.image_dimensions() {
                     format!("{}x{}", w, h)
                 } else {
-                    "unknown".to_string()
+                    t("preview.unknown_dimensions")
                 };
-                let info = format!(
-                    "Path: {} │ Size: {} │ {} │ Created: {}",
-                    entry.content,
-                    format_size(entry.byte_size),
-                    dimensions_str,
-                    entry.created_at.format("%Y-%m-%d %H:%M:%S")
+                let info = tf(
+                    "expanded.image_info",
+                    &[
+                        ("path", &path),
+                        ("size", &format_size(entry.byte_size)),
+                        ("dimensions", &dimensions_str),
+                        ("created", &entry.created_at.format("%Y-%m-%d %H:%M:%S")),
+                    ],
                 );
                 let info_paragraph = Paragraph::new(info)
                     .style(theme.muted())
@@ -215,7 +249,7 @@ fn draw_expanded(
             }
         },
         None => {
-            let paragraph = Paragraph::new("No entry selected")
+            let paragraph = Paragraph::new(t("preview.no_entry"))
                 .style(theme.muted())
                 .alignment(Alignment::Center);
             frame.render_widget(paragraph, inner);
@@ -223,7 +257,7 @@ fn draw_expanded(
     }
 
     // Status bar
-    let status = " t/Esc: Close │ j/k: Navigate │ Enter: Copy+Exit │ y: Copy";
+    let status = format!(" {}", t("expanded.hint"));
     let status_bar = Paragraph::new(status)
         .style(theme.muted())
         .block(Block::default());
@@ -231,11 +265,11 @@ fn draw_expanded(
 }
 
 fn draw_status(frame: &mut Frame, app: &App, theme: &Theme, area: Rect, show_snippets: bool) {
-    // Check watcher status
-    let watcher_status = if ditox_core::watcher::is_watcher_running() {
-        "●" // Green dot (will be styled)
-    } else {
-        "○" // Empty dot
+    // Watcher status, cached by the refresh tick
+    let watcher_status = match app.watcher_status {
+        WatcherStatus::Running => "●",
+        WatcherStatus::Failing => "◐",
+        WatcherStatus::Stopped => "○",
     };
 
     let status = if let Some(msg) = &app.message {
@@ -244,31 +278,39 @@ fn draw_status(frame: &mut Frame, app: &App, theme: &Theme, area: Rect, show_sni
         // Multi-select mode status
         let selected_count = app.multi_selected.len();
         format!(
-            " {} │ [MULTI] Space:Select  v:All  d:Delete  y:Copy  Esc:Exit │ {} selected",
-            watcher_status, selected_count
+            " {} │ {} │ {}",
+            watcher_status,
+            t("status.multi_hint"),
+            tf("status.selected", &[("count", &selected_count)])
         )
     } else {
         // Show snippet hints if any slots are filled (respecting narrow terminal)
         let has_snippets = app.snippet_slots.iter().any(|s| s.is_some());
         let snippet_hint = if has_snippets && show_snippets {
-            "1-9:Snippet  "
+            format!("{}  ", t("status.snippet_hint"))
         } else {
-            ""
+            String::new()
         };
-        let keybindings = format!(
-            "{}j/k:Move  Enter:Copy  /:Search  ?:Help  q:Quit",
-            snippet_hint
-        );
+        let keybindings = format!("{}{}", snippet_hint, t("status.hint"));
         let entry_count = if !app.search_query.is_empty() {
             // Show filtered/total when searching
-            format!("{}/{} filtered", app.filtered.len(), app.entries.len())
+            tf(
+                "status.filtered",
+                &[
+                    ("shown", &app.filtered.len()),
+                    ("total", &app.entries.len()),
+                ],
+            )
         } else {
-            format!("{} entries", app.entries.len())
+            tf("status.entries", &[("count", &app.entries.len())])
         };
         let refresh_time = app.time_since_refresh();
         format!(
-            " {} │ {} │ {} │ Updated: {} ago",
-            watcher_status, keybindings, entry_count, refresh_time
+            " {} │ {} │ {} │ {}",
+            watcher_status,
+            keybindings,
+            entry_count,
+            tf("status.updated", &[("ago", &refresh_time)])
         )
     };
 
@@ -279,7 +321,7 @@ fn draw_status(frame: &mut Frame, app: &App, theme: &Theme, area: Rect, show_sni
     frame.render_widget(status_bar, area);
 }
 
-fn format_size(bytes: usize) -> String {
+pub(crate) fn format_size(bytes: usize) -> String {
     const KB: usize = 1024;
     const MB: usize = KB * 1024;
 
@@ -294,9 +336,14 @@ fn format_size(bytes: usize) -> String {
 
 /// Draw a warning when terminal is too small
 fn draw_size_warning(frame: &mut Frame, area: Rect, theme: &Theme) {
-    let msg = format!(
-        "Terminal too small\n\nCurrent: {}x{}\nMinimum: {}x{}\n\nPlease resize your terminal",
-        area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+    let msg = tf(
+        "layout.too_small",
+        &[
+            ("width", &area.width),
+            ("height", &area.height),
+            ("min_width", &MIN_WIDTH),
+            ("min_height", &MIN_HEIGHT),
+        ],
     );
 
     let paragraph = Paragraph::new(msg)
@@ -1,5 +1,8 @@
 use crate::ui::theme::Theme;
-use ditox_core::app::App;
+use ditox_core::app::{App, DayGroup};
+use ditox_core::entry::EntryType;
+use ditox_core::i18n::tf;
+use ditox_core::SearchHit;
 use ratatui::prelude::*;
 use ratatui::widgets::{
     Block, Borders, List, ListItem, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState,
@@ -14,36 +17,63 @@ pub fn draw(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
         0
     };
 
-    let items: Vec<ListItem> = app
-        .filtered
+    // Build rows for the visible window only; search results can be long
+    let rows = visible_list_rows(app, area.height.saturating_sub(2) as usize);
+    let selected_row = rows
         .iter()
-        .enumerate()
-        .map(|(i, &idx)| {
-            let entry = &app.entries[idx];
-            let match_indices = app.match_indices.get(&idx);
-            let is_multi_selected = app.is_multi_selected(i);
-            format_entry_row(
-                entry,
-                base_index + i + 1, // Global entry number
-                i == app.selected,
-                is_multi_selected,
-                app.multi_select_mode,
-                theme,
-                area.width,
-                match_indices,
-            )
+        .position(|row| *row == ListRow::Entry(app.selected))
+        .unwrap_or(0);
+    let items: Vec<ListItem> = rows
+        .into_iter()
+        .map(|row| match row {
+            ListRow::Header(group) => ListItem::new(Line::from(Span::styled(
+                format!(" ── {} ", group.label()),
+                theme.accent(),
+            ))),
+            ListRow::Entry(i) => {
+                let idx = app.filtered[i];
+                let entry = &app.entries[idx];
+                let match_indices = app.match_indices.get(&idx);
+                let hit = app.search_hits.get(&idx);
+                let is_multi_selected = app.is_multi_selected(i);
+                format_entry_row(
+                    entry,
+                    base_index + i + 1, // Global entry number
+                    i == app.selected,
+                    is_multi_selected,
+                    app.multi_select_mode,
+                    theme,
+                    area.width,
+                    match_indices,
+                    hit,
+                )
+            }
         })
         .collect();
 
     // Build title with page indicator
     let title = if app.search_query.is_empty() {
         format!(
-            " History  [Page {} of {}] ",
-            app.display_page(),
-            app.total_pages()
+            " {} ",
+            tf(
+                "list.history",
+                &[("page", &app.display_page()), ("pages", &app.total_pages())]
+            )
+        )
+    } else {
+        format!(
+            " {} ",
+            tf("list.search_results", &[("count", &app.filtered.len())])
+        )
+    };
+    let title = if app.new_clips > 0 && !app.at_top() {
+        format!(
+            "{}{} ",
+            title,
+            tf("list.new_clips", &[("count", &app.new_clips)])
         )
     } else {
-        format!(" Search Results ({}) ", app.filtered.len())
+        title
     };
 
     let list = List::new(items)
@@ -57,7 +87,7 @@ pub fn draw(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
         .highlight_style(theme.selected());
 
     let mut state = ListState::default();
-    state.select(Some(app.selected));
+    state.select(Some(selected_row));
 
     frame.render_stateful_widget(list, area, &mut state);
 
@@ -91,6 +121,56 @@ pub fn draw(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
     }
 }
 
+/// Index of the first row shown when `selected` must be visible in
+/// `visible_rows` rows. The selection sits on the last row once the list
+/// scrolls.
+pub fn first_visible_row(selected: usize, visible_rows: usize) -> usize {
+    selected.saturating_sub(visible_rows.saturating_sub(1))
+}
+
+/// A row of the entry list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListRow {
+    /// Day header of a grouped list; not selectable
+    Header(DayGroup),
+    /// Entry at this index into `app.filtered`
+    Entry(usize),
+}
+
+/// Rows shown in a list `height` rows tall, scrolled so the selection is
+/// visible. Day headers take rows too, so the window may start past
+/// [`first_visible_row`].
+pub fn visible_list_rows(app: &App, height: usize) -> Vec<ListRow> {
+    let mut first = first_visible_row(app.selected, height);
+    loop {
+        let rows = list_rows_from(app, first, height);
+        if first >= app.selected || rows.contains(&ListRow::Entry(app.selected)) {
+            return rows;
+        }
+        first += 1;
+    }
+}
+
+/// Up to `height` rows starting at entry `first`, with a header before
+/// the first entry and wherever the day group changes
+fn list_rows_from(app: &App, first: usize, height: usize) -> Vec<ListRow> {
+    let mut rows = Vec::with_capacity(height);
+    let mut previous = None;
+    for i in first..app.filtered.len() {
+        let group = app.day_group(i);
+        if let Some(group) = group.filter(|_| group != previous) {
+            rows.push(ListRow::Header(group));
+        }
+        previous = group;
+        rows.push(ListRow::Entry(i));
+        if rows.len() >= height {
+            break;
+        }
+    }
+    rows.truncate(height);
+    rows
+}
+
 // Each argument threads through rendering; grouping into a struct would
 // hide the dependencies without reducing coupling.
 #[allow(clippy::too_many_arguments)]
@@ -103,6 +183,7 @@ fn format_entry_row(
     theme: &Theme,
     width: u16,
     match_indices: Option<&Vec<u32>>,
+    hit: Option<&SearchHit>,
 ) -> ListItem<'static> {
     // Entry type icon (T/I)
     let type_str = entry.entry_type.icon();
@@ -141,8 +222,29 @@ fn format_entry_row(
     // Build prefix spans
     let prefix = format!("{} {:>3} │ {} │ ", marker, index, type_str);
 
+    // When the full-text match lies past what the head preview can show,
+    // show the FTS snippet around it instead
+    let snippet = hit.filter(|hit| {
+        entry.entry_type == EntryType::Text
+            && hit.offset.is_some_and(|offset| offset + 1 > content_width)
+    });
+    let snippet_indices: Vec<u32>;
+    let (preview, match_indices) = match snippet {
+        Some(hit) => {
+            snippet_indices = hit
+                .ranges
+                .iter()
+                .flat_map(|&(start, end)| start as u32..end as u32)
+                .collect();
+            (
+                snippet_preview(&hit.snippet, content_width),
+                Some(&snippet_indices),
+            )
+        }
+        None => (entry.preview(content_width), match_indices),
+    };
+
     // Build content with highlighting
-    let preview = entry.preview(content_width);
     let padded_preview = format!("{:<width$}", preview, width = content_width);
 
     let content_spans = if let Some(indices) = match_indices {
@@ -197,3 +299,19 @@ fn format_entry_row(
 
     ListItem::new(Line::from(all_spans))
 }
+
+/// Flatten a search snippet onto one row. Every char maps to exactly one
+/// output char so the hit's match ranges stay valid.
+fn snippet_preview(snippet: &str, max_len: usize) -> String {
+    snippet
+        .chars()
+        .map(|c| {
+            if c.is_whitespace() || c.is_control() {
+                ' '
+            } else {
+                c
+            }
+        })
+        .take(max_len)
+        .collect()
+}
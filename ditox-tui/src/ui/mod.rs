@@ -1,5 +1,7 @@
 mod confirm;
+mod details;
 mod help;
+mod highlight;
 mod layout;
 mod list;
 mod note_editor;
@@ -7,6 +9,7 @@ pub mod preview;
 mod search;
 mod snippets;
 mod tabs;
+mod tag_editor;
 mod theme;
 
 use crate::keybindings::{KeybindingResolver, KeybindingsConfigExt};
@@ -19,23 +22,35 @@ use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use ditox_core::actions::Action;
-use ditox_core::app::{App, InputMode, PreviewMode};
+use ditox_core::app::{App, EditMode, InputMode, PreviewMode};
+use ditox_core::clipboard::Clipboard;
 use ditox_core::config::Config;
+use ditox_core::confirm::ConfirmStyle;
 use ditox_core::db::Database;
+use ditox_core::entry::{Entry, EntryType};
 use ditox_core::error::Result;
+use ditox_core::i18n::{t, tf};
 use preview::{ImageCache, ImageLoader};
 use ratatui::prelude::*;
 use ratatui_image::picker::{Picker, ProtocolType};
-use std::io;
+use std::io::{self, IsTerminal, Write};
+use std::process::Command;
 use std::time::{Duration, Instant};
-use theme::Theme;
+use theme::{Theme, ThemeReloader};
 
 use ditox_core::config::GraphicsProtocol;
 
+/// Wait after closing before typing a clip (`copy_and_type`)
+const TYPE_DELAY: Duration = Duration::from_millis(200);
+
+/// How often to look for clips written by other processes
+const CHANGE_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Create image picker with terminal detection
 fn create_picker(
     override_protocol: Option<GraphicsProtocol>,
     override_font_size: Option<(u16, u16)>,
+    query_terminal: bool,
 ) -> Option<Picker> {
     let is_ghostty = std::env::var("GHOSTTY_RESOURCES_DIR").is_ok();
     let is_kitty = std::env::var("KITTY_WINDOW_ID").is_ok();
@@ -49,6 +64,9 @@ fn create_picker(
         // which we need for kitty/sixel when stdio query is unavailable.
         #[allow(deprecated)]
         Picker::from_fontsize((w, h))
+    } else if !query_terminal {
+        #[allow(deprecated)]
+        Picker::from_fontsize((9, 18))
     } else {
         match Picker::from_query_stdio() {
             Ok(p) => p,
@@ -76,22 +94,81 @@ fn create_picker(
 }
 
 pub fn run(db: Database, config: Config) -> Result<()> {
-    // Initialize image picker BEFORE entering alternate screen
-    let mut picker = create_picker(config.ui.graphics_protocol, config.ui.font_size);
+    let app = run_on(io::stdout(), db, config, None)?;
+    if app.type_after_copy {
+        if let Some(entry) = app
+            .selected_entry()
+            .filter(|entry| entry.entry_type == EntryType::Text)
+        {
+            // Let focus go back to the window the clip is typed into
+            std::thread::sleep(TYPE_DELAY);
+            Clipboard::type_text(&entry.content)?;
+        }
+    }
+    Ok(())
+}
+
+/// How `ditox pick` starts
+#[derive(Debug, Default)]
+pub struct PickOptions {
+    /// Search to start with
+    pub query: Option<String>,
+    /// Choose the top result without showing the picker
+    pub first: bool,
+    /// Show the theme inspector under the mouse pointer
+    pub theme_edit: bool,
+}
+
+/// Run the TUI as a picker for scripts: it draws on stderr so stdout stays
+/// free, and Enter returns the selected entries instead of copying them.
+/// Returns no entries if the picker was closed without choosing.
+pub fn pick(db: Database, config: Config, options: PickOptions) -> Result<Vec<Entry>> {
+    if options.first {
+        let mut app = App::new(db, config)?;
+        app.pick_mode = true;
+        app.set_search(options.query.as_deref().unwrap_or_default());
+        app.pick_selected();
+        return Ok(app.picked);
+    }
+    let app = run_on(io::stderr(), db, config, Some(options))?;
+    Ok(app.picked)
+}
+
+/// Run the TUI on `out`, as a picker if `pick` is given
+fn run_on<W: Write>(
+    out: W,
+    db: Database,
+    config: Config,
+    pick: Option<PickOptions>,
+) -> Result<App> {
+    let pick_mode = pick.is_some();
+    let theme_edit = pick.as_ref().is_some_and(|pick| pick.theme_edit);
+    // Initialize image picker BEFORE entering alternate screen. The query
+    // goes through stdout, which the picker may be printing results to.
+    let query_terminal = !pick_mode || io::stdout().is_terminal();
+    let mut picker = create_picker(
+        config.ui.graphics_protocol,
+        config.ui.font_size,
+        query_terminal,
+    );
 
     // Create keybinding resolver from config
     let keybindings = config.keybindings.create_resolver();
 
     // Setup terminal
     enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    let mut out = out;
+    execute!(out, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(out);
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
     let mut app = App::new(db, config)?;
-    let theme = Theme::from_config(&app.config().ui.theme);
+    app.pick_mode = pick_mode;
+    if let Some(query) = pick.and_then(|pick| pick.query) {
+        app.set_search(&query);
+    }
+    let mut theme = Theme::from_config(&app.config().ui.theme);
 
     // Initialize quick snippets from most-used entries
     let _ = app.refresh_snippets();
@@ -100,7 +177,7 @@ pub fn run(db: Database, config: Config) -> Result<()> {
     let mut image_cache = ImageCache::new(10); // Cache up to 10 images
     let image_loader = ImageLoader::new();
 
-    // Auto-refresh timer
+    // Watcher status refresh timer
     let refresh_interval = Duration::from_secs(2);
     let mut last_refresh = Instant::now();
 
@@ -108,13 +185,14 @@ pub fn run(db: Database, config: Config) -> Result<()> {
     let result = run_loop(
         &mut terminal,
         &mut app,
-        &theme,
+        &mut theme,
         &mut image_cache,
         &mut picker,
         &image_loader,
         refresh_interval,
         &mut last_refresh,
         &keybindings,
+        theme_edit,
     );
 
     // Cleanup
@@ -126,7 +204,7 @@ pub fn run(db: Database, config: Config) -> Result<()> {
         DisableMouseCapture
     )?;
 
-    result
+    result.map(|_| app)
 }
 
 /// Track mouse state for double-click detection
@@ -161,34 +239,53 @@ impl MouseState {
 }
 
 #[allow(clippy::too_many_arguments)]
-fn run_loop<B: Backend>(
-    terminal: &mut Terminal<B>,
+fn run_loop<W: Write>(
+    terminal: &mut Terminal<CrosstermBackend<W>>,
     app: &mut App,
-    theme: &Theme,
+    theme: &mut Theme,
     cache: &mut ImageCache,
     picker: &mut Option<Picker>,
     loader: &ImageLoader,
     refresh_interval: Duration,
     last_refresh: &mut Instant,
     keybindings: &KeybindingResolver,
+    theme_edit: bool,
 ) -> Result<()> {
     let mut mouse_state = MouseState::new();
+    let mut theme_reloader = ThemeReloader::new();
+    let mut hover = None;
+
+    let mut last_change_check = Instant::now();
 
     loop {
-        // Auto-refresh entries periodically
-        if last_refresh.elapsed() >= refresh_interval {
-            let old_count = app.entries.len();
-            if app.reload_entries().is_ok() {
-                let new_count = app.entries.len();
-                if new_count > old_count {
-                    app.set_message(format!("{} new entries", new_count - old_count));
-                }
+        // Pick up clips from the watcher (or any other writer) as they land
+        if last_change_check.elapsed() >= CHANGE_CHECK_INTERVAL {
+            if let Err(e) = app.check_for_changes() {
+                tracing::warn!("Failed to check for new clips: {}", e);
             }
+            last_change_check = Instant::now();
+        }
+        if last_refresh.elapsed() >= refresh_interval {
+            app.refresh_watcher_status();
             *last_refresh = Instant::now();
         }
+        app.run_pending_search();
+        match theme_reloader.poll() {
+            Some(Ok(reloaded)) => {
+                *theme = reloaded;
+                app.set_message(t("toast.theme_reloaded"));
+            }
+            Some(Err(e)) => app.set_message(tf("toast.theme_reload_failed", &[("error", &e)])),
+            None => {}
+        }
 
         terminal
-            .draw(|f| layout::draw(f, app, theme, cache, picker, loader, keybindings))
+            .draw(|f| {
+                layout::draw(f, app, theme, cache, picker, loader, keybindings);
+                if let Some(position) = hover {
+                    theme::draw_inspector(f, theme, position);
+                }
+            })
             .map_err(|e| ditox_core::error::DitoxError::Io(std::io::Error::other(e.to_string())))?;
         // Note: layout::draw updates app.terminal_height for page navigation
 
@@ -199,18 +296,31 @@ fn run_loop<B: Backend>(
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
                     handle_key(app, key, keybindings)?
                 }
-                Event::Mouse(mouse) => handle_mouse(app, mouse, &mut mouse_state)?,
+                Event::Mouse(mouse) => {
+                    if theme_edit {
+                        hover = Some((mouse.column, mouse.row));
+                    }
+                    handle_mouse(app, mouse, &mut mouse_state)?
+                }
                 _ => {}
             }
         }
 
+        if let Some(mode) = app.edit_requested.take() {
+            edit_selected(terminal, app, mode)?;
+        }
+
         // Clear message after timeout (2 seconds)
         if app.is_message_expired() {
             app.clear_message();
         }
 
         if app.should_copy_and_quit {
-            app.copy_selected()?;
+            if app.pick_mode {
+                app.pick_selected();
+            } else {
+                app.copy_selected()?;
+            }
             break;
         }
 
@@ -222,11 +332,91 @@ fn run_loop<B: Backend>(
     Ok(())
 }
 
+/// Open the selected text clip in `$VISUAL`/`$EDITOR`, suspending the TUI
+/// while the editor runs, and store the result as `mode` says
+fn edit_selected<W: Write>(
+    terminal: &mut Terminal<CrosstermBackend<W>>,
+    app: &mut App,
+    mode: EditMode,
+) -> Result<()> {
+    let Some(entry) = app.selected_entry() else {
+        return Ok(());
+    };
+    let (id, original) = (entry.id.clone(), entry.content.clone());
+
+    // In the data dir rather than /tmp, readable only by us: clips can be secrets
+    let path = Database::get_data_dir()?.join(format!("edit-{}.txt", std::process::id()));
+    write_private(&path, &original)?;
+
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    let status = editor_command(&path).status();
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture
+    )?;
+    terminal
+        .clear()
+        .map_err(|e| ditox_core::error::DitoxError::Io(std::io::Error::other(e.to_string())))?;
+
+    let edited = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+    let error = match status {
+        Ok(status) if status.success() => None,
+        Ok(status) => Some(status.to_string()),
+        Err(e) => Some(e.to_string()),
+    };
+    if let Some(error) = error {
+        app.set_message(tf("toast.editor_failed", &[("error", &error)]));
+        return Ok(());
+    }
+
+    // Editors add a final newline; don't count it as a change
+    let mut edited = edited?;
+    if !original.ends_with('\n') && edited.ends_with('\n') {
+        edited.pop();
+        if edited.ends_with('\r') {
+            edited.pop();
+        }
+    }
+    app.apply_edit(&id, &original, &edited, mode)
+}
+
+/// `$VISUAL`, else `$EDITOR` (either may carry arguments, e.g. `code
+/// --wait`), else a platform default, set up to edit `path`
+fn editor_command(path: &std::path::Path) -> Command {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+    let mut parts = editor.split_whitespace();
+    let mut command = Command::new(parts.next().unwrap_or("vi"));
+    command.args(parts).arg(path);
+    command
+}
+
+fn write_private(path: &std::path::Path, content: &str) -> Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(content.as_bytes())?;
+    Ok(())
+}
+
 fn handle_key(app: &mut App, key: KeyEvent, keybindings: &KeybindingResolver) -> Result<()> {
     match app.input_mode {
         InputMode::Normal => handle_normal_mode(app, key, keybindings),
         InputMode::Search => handle_search_mode(app, key, keybindings),
         InputMode::EditNote => handle_edit_note_mode(app, key),
+        InputMode::EditTags => handle_edit_tags_mode(app, key),
         InputMode::Confirm => handle_confirm_mode(app, key),
     }
 }
@@ -239,6 +429,36 @@ fn handle_normal_mode(
     // Resolve key to action
     let action = keybindings.resolve(key);
 
+    // Any other key in between breaks a double-press confirmation
+    if !matches!(action, Some(Action::Delete | Action::ClearAll)) {
+        app.confirm_tracker.reset();
+    }
+
+    // The detail view scrolls its text; copying still works from it
+    if app.details.is_some() {
+        match action {
+            Some(Action::MoveDown) => app.scroll_details(1),
+            Some(Action::MoveUp) => app.scroll_details(-1),
+            Some(Action::PageDown) => app.scroll_details(10),
+            Some(Action::PageUp) => app.scroll_details(-10),
+            Some(Action::GoTop) => app.details_scroll = 0,
+            Some(Action::GoBottom) => app.scroll_details(isize::MAX),
+            Some(Action::ShowDetails | Action::ExitSearch) => app.close_details(),
+            Some(
+                Action::Quit
+                | Action::ForceQuit
+                | Action::Copy
+                | Action::CopyAndQuit
+                | Action::CopyAndType,
+            ) => {
+                app.close_details();
+                return handle_normal_mode(app, key, keybindings);
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
     match action {
         Some(Action::Quit) => app.should_quit = true,
         Some(Action::ForceQuit) => app.should_quit = true,
@@ -269,7 +489,8 @@ fn handle_normal_mode(
 
         // Operations
         Some(Action::CopyAndQuit) => {
-            if app.multi_select_mode && !app.multi_selected.is_empty() {
+            // The picker returns multi-selections as they are
+            if app.multi_select_mode && !app.multi_selected.is_empty() && !app.pick_mode {
                 // In multi-select mode with selections, copy all and exit
                 app.copy_selected_multi()?;
                 app.should_quit = true;
@@ -277,6 +498,10 @@ fn handle_normal_mode(
                 app.should_copy_and_quit = true;
             }
         }
+        Some(Action::CopyAndType) => {
+            app.type_after_copy = !app.pick_mode;
+            app.should_copy_and_quit = true;
+        }
         Some(Action::Copy) => {
             if app.multi_select_mode && !app.multi_selected.is_empty() {
                 app.copy_selected_multi()?;
@@ -286,16 +511,17 @@ fn handle_normal_mode(
         }
         Some(Action::Delete) => {
             if app.multi_select_mode && !app.multi_selected.is_empty() {
-                app.request_delete_multi();
+                app.request_delete_multi()?;
             } else {
-                app.request_delete_selected();
+                app.request_delete_selected()?;
             }
         }
-        Some(Action::ClearAll) => app.request_clear_all(),
+        Some(Action::UndoDelete) => app.undo_delete()?,
+        Some(Action::ClearAll) => app.request_clear_all()?,
         Some(Action::ToggleFavorite) => app.toggle_favorite()?,
         Some(Action::Refresh) => {
             app.reload_entries()?;
-            app.set_message("Refreshed");
+            app.set_message(t("toast.refreshed"));
         }
 
         // Modes
@@ -303,6 +529,7 @@ fn handle_normal_mode(
         Some(Action::TogglePreview) => app.show_preview = !app.show_preview,
         Some(Action::ToggleExpanded) => app.show_expanded = !app.show_expanded,
         Some(Action::ToggleHelp) => app.show_help = !app.show_help,
+        Some(Action::ShowDetails) => app.open_details()?,
 
         // Multi-select
         Some(Action::ToggleMultiSelect) => app.toggle_multi_select(),
@@ -314,6 +541,8 @@ fn handle_normal_mode(
             app.toggle_select_all();
         }
         Some(Action::SelectAll) => {}
+        Some(Action::MergeSelected) if app.multi_select_mode => app.merge_selected()?,
+        Some(Action::MergeSelected) => {}
 
         // Handle Esc specially for closing modes
         Some(Action::ExitSearch) => {
@@ -342,6 +571,7 @@ fn handle_normal_mode(
         Some(Action::CyclePreviewMode) => {
             app.cycle_preview_mode();
         }
+        Some(Action::CycleSort) => app.cycle_sort()?,
         Some(Action::ToggleLineNumbers) => {
             app.toggle_line_numbers();
         }
@@ -360,9 +590,21 @@ fn handle_normal_mode(
         Some(Action::QuickSlot7) => app.copy_snippet(7)?,
         Some(Action::QuickSlot8) => app.copy_snippet(8)?,
         Some(Action::QuickSlot9) => app.copy_snippet(9)?,
+        Some(Action::View1) => app.go_to_view(1),
+        Some(Action::View2) => app.go_to_view(2),
+        Some(Action::View3) => app.go_to_view(3),
+        Some(Action::View4) => app.go_to_view(4),
+        Some(Action::View5) => app.go_to_view(5),
+        Some(Action::View6) => app.go_to_view(6),
+        Some(Action::View7) => app.go_to_view(7),
+        Some(Action::View8) => app.go_to_view(8),
+        Some(Action::View9) => app.go_to_view(9),
         Some(Action::EditAnnotation) => {
             app.start_edit_note();
         }
+        Some(Action::EditInEditor) => app.request_edit(EditMode::InPlace),
+        Some(Action::EditInEditorAsNew) => app.request_edit(EditMode::AsNew),
+        Some(Action::EditTags) => app.start_edit_tags()?,
         Some(Action::ShowStats) => {
             // TODO: Implement in Phase 1
         }
@@ -379,9 +621,16 @@ fn handle_search_mode(
 ) -> Result<()> {
     // In search mode, we handle text input directly, but some keys trigger actions
     match key.code {
+        // While tags are offered, Enter completes and Esc only closes the popup
+        KeyCode::Enter if !app.tag_completions.is_empty() => {
+            app.accept_tag_completion();
+        }
+        KeyCode::Esc if !app.tag_completions.is_empty() => app.dismiss_tag_completions(),
         KeyCode::Esc => app.end_search(),
         KeyCode::Enter => app.end_search(),
         KeyCode::Backspace => app.pop_search_char(),
+        KeyCode::Up if !app.tag_completions.is_empty() => app.move_tag_completion(false),
+        KeyCode::Down if !app.tag_completions.is_empty() => app.move_tag_completion(true),
         KeyCode::Char(c) => {
             // Check if this is a control combo that should trigger an action
             if key.modifiers.contains(KeyModifiers::CONTROL)
@@ -399,8 +648,12 @@ fn handle_search_mode(
                 app.push_search_char(c);
             }
         }
-        // Handle Tab in search mode - toggle preview without exiting search
+        // Handle Tab in search mode - complete a tag, otherwise toggle
+        // preview without exiting search
         KeyCode::Tab => {
+            if app.accept_tag_completion() {
+                return Ok(());
+            }
             if let Some(action) = keybindings.resolve(key) {
                 if action == Action::TogglePreview {
                     app.show_preview = !app.show_preview;
@@ -431,7 +684,43 @@ fn handle_edit_note_mode(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+fn handle_edit_tags_mode(app: &mut App, key: KeyEvent) -> Result<()> {
+    // Each Enter applies one tag change; the overlay stays open until Esc
+    // or Enter on an empty input
+    match key.code {
+        KeyCode::Esc => app.end_edit_tags(),
+        KeyCode::Enter => app.submit_tag_input()?,
+        KeyCode::Tab => app.complete_tag_input(),
+        KeyCode::Up => app.move_tag_completion(false),
+        KeyCode::Down => app.move_tag_completion(true),
+        KeyCode::Backspace => app.pop_tag_char(),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.should_quit = true;
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => app.push_tag_char(c),
+        _ => {}
+    }
+    Ok(())
+}
+
 fn handle_confirm_mode(app: &mut App, key: KeyEvent) -> Result<()> {
+    // Typed confirmation: collect the phrase, Enter submits it
+    if app.confirm_style == ConfirmStyle::Typed {
+        match key.code {
+            KeyCode::Enter => app.confirm_action()?,
+            KeyCode::Esc => app.cancel_confirm(),
+            KeyCode::Backspace => {
+                app.confirm_input.pop();
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.should_quit = true;
+            }
+            KeyCode::Char(c) => app.confirm_input.push(c),
+            _ => {}
+        }
+        return Ok(());
+    }
+
     // In confirmation mode, handle y/n/Enter/Esc
     match key.code {
         // Confirm with y or Enter
@@ -462,25 +751,12 @@ fn handle_mouse(app: &mut App, mouse: MouseEvent, state: &mut MouseState) -> Res
         MouseEventKind::Down(MouseButton::Left)
             if mouse.row >= list_start_row && mouse.row < list_end_row =>
         {
-            // Calculate which entry was clicked (accounting for scroll offset)
-            // Currently we don't track scroll offset separately - Ratatui handles it
-            // So we map the click to the visible position
+            // Map the click through the same window the list renders
             let clicked_visible_row = (mouse.row - list_start_row) as usize;
-
-            // The visible entries start from some offset based on selection
-            // For simplicity, we calculate the entry index relative to the scroll
             let visible_height = (list_end_row - list_start_row) as usize;
+            let rows = list::visible_list_rows(app, visible_height);
 
-            // Calculate scroll offset (how many items are scrolled off-screen)
-            let scroll_offset = if app.selected >= visible_height {
-                app.selected.saturating_sub(visible_height / 2)
-            } else {
-                0
-            };
-
-            let clicked_index = scroll_offset + clicked_visible_row;
-
-            if clicked_index < app.filtered.len() {
+            if let Some(&list::ListRow::Entry(clicked_index)) = rows.get(clicked_visible_row) {
                 // Check for double-click
                 if state.is_double_click(mouse.row) {
                     // Double-click: copy and exit
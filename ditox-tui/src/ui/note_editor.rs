@@ -1,6 +1,7 @@
 //! Note editor modal widget
 
 use crate::ui::theme::Theme;
+use ditox_core::i18n::t;
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
@@ -24,7 +25,7 @@ pub fn render(frame: &mut Frame, note: &str, theme: &Theme) {
 
     // Build the modal content
     let block = Block::default()
-        .title(" Edit Note ")
+        .title(format!(" {} ", t("note.title")))
         .borders(Borders::ALL)
         .border_style(theme.border())
         .title_style(theme.title());
@@ -43,7 +44,7 @@ pub fn render(frame: &mut Frame, note: &str, theme: &Theme) {
     let help_area = Rect::new(modal_x, modal_y + modal_height, modal_width, 1);
 
     if help_area.y < area.height {
-        let help = Paragraph::new("Enter: Save  Esc: Cancel")
+        let help = Paragraph::new(t("note.hint"))
             .style(theme.muted())
             .alignment(Alignment::Center);
         frame.render_widget(help, help_area);
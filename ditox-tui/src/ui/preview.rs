@@ -1,6 +1,8 @@
+use crate::ui::highlight;
 use crate::ui::theme::Theme;
 use ditox_core::app::{App, PreviewMode};
 use ditox_core::entry::EntryType;
+use ditox_core::i18n::{t, tf};
 use image::DynamicImage;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
@@ -75,15 +77,6 @@ fn load_image(path: &str) -> Option<DynamicImage> {
     image::open(img_path).ok()
 }
 
-/// Get image dimensions without loading the full image
-fn get_image_dimensions(path: &str) -> Option<(u32, u32)> {
-    let img_path = Path::new(path);
-    if !img_path.exists() {
-        return None;
-    }
-    image::image_dimensions(img_path).ok()
-}
-
 /// Cache for rendered image protocols
 pub struct ImageCache {
     cache: HashMap<String, StatefulProtocol>,
@@ -143,7 +136,10 @@ pub fn draw(
     loader: &ImageLoader,
 ) {
     // Show mode in title
-    let title = format!(" Preview ({}) ", app.preview_mode.label());
+    let title = format!(
+        " {} ",
+        tf("preview.title", &[("mode", &app.preview_mode.label())])
+    );
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(theme.border())
@@ -165,6 +161,9 @@ pub fn draw(
             // Get match indices for current entry
             let entry_idx = app.filtered.get(app.selected).copied();
             let match_indices = entry_idx.and_then(|idx| app.match_indices.get(&idx));
+            let match_offset = entry_idx
+                .and_then(|idx| app.search_hits.get(&idx))
+                .and_then(|hit| hit.offset);
 
             match entry.entry_type {
                 EntryType::Text => {
@@ -176,6 +175,7 @@ pub fn draw(
                         block,
                         &entry.content,
                         match_indices,
+                        match_offset,
                         app.show_line_numbers,
                     );
                 }
@@ -216,9 +216,9 @@ pub fn draw(
 
                     if !image_rendered {
                         let loading_text = if cache.is_pending(path) {
-                            "Loading image..."
+                            t("preview.loading_image")
                         } else {
-                            "[Image preview not available]"
+                            t("preview.image_unavailable")
                         };
                         let placeholder = Paragraph::new(loading_text)
                             .style(theme.muted())
@@ -227,17 +227,19 @@ pub fn draw(
                     }
 
                     // Render image info
-                    let dimensions_str = if let Some((w, h)) = get_image_dimensions(path) {
+                    let dimensions_str = if let Some((w, h)) = entry.image_dimensions() {
                         format!("{}x{}", w, h)
                     } else {
-                        "unknown".to_string()
+                        t("preview.unknown_dimensions")
                     };
-                    let info = format!(
-                        "Path: {}\nSize: {} │ Dimensions: {}\nCreated: {}",
-                        path,
-                        format_size(entry.byte_size),
-                        dimensions_str,
-                        entry.created_at.format("%Y-%m-%d %H:%M:%S")
+                    let info = tf(
+                        "preview.image_info",
+                        &[
+                            ("path", &path),
+                            ("size", &format_size(entry.byte_size)),
+                            ("dimensions", &dimensions_str),
+                            ("created", &entry.created_at.format("%Y-%m-%d %H:%M:%S")),
+                        ],
                     );
 
                     let info_paragraph = Paragraph::new(info)
@@ -249,7 +251,7 @@ pub fn draw(
             }
         }
         None => {
-            let paragraph = Paragraph::new("No entry selected")
+            let paragraph = Paragraph::new(t("preview.no_entry"))
                 .style(theme.muted())
                 .block(block)
                 .alignment(Alignment::Center);
@@ -334,6 +336,7 @@ fn render_text_preview(
     block: Block,
     content: &str,
     match_indices: Option<&Vec<u32>>,
+    match_offset: Option<usize>,
     show_line_numbers: bool,
 ) {
     match app.preview_mode {
@@ -345,7 +348,9 @@ fn render_text_preview(
                 block,
                 content,
                 match_indices,
+                match_offset,
                 show_line_numbers,
+                app.config().ui.syntax_highlight,
             );
         }
         PreviewMode::Scroll => {
@@ -363,7 +368,9 @@ fn render_text_preview(
     }
 }
 
-/// Wrap mode - text wraps at pane width (default)
+/// Wrap mode - text wraps at pane width (default). When a search match
+/// offset is given, the view is scrolled so the match sits mid-pane.
+#[allow(clippy::too_many_arguments)]
 fn render_wrap_mode(
     frame: &mut Frame,
     theme: &Theme,
@@ -371,35 +378,81 @@ fn render_wrap_mode(
     block: Block,
     content: &str,
     match_indices: Option<&Vec<u32>>,
+    match_offset: Option<usize>,
     show_line_numbers: bool,
+    syntax_highlight: bool,
 ) {
+    let inner = block.inner(area);
+    let gutter = if show_line_numbers { 7 } else { 0 };
+    let scroll = match_offset
+        .map(|offset| {
+            let row = wrapped_row_of(content, offset, inner.width.saturating_sub(gutter));
+            row.saturating_sub(inner.height as usize / 2)
+        })
+        .unwrap_or(0);
+
     // Sanitize content for display
     let sanitized = sanitize_for_display(content);
 
+    // Search matches take precedence over syntax colors
+    let highlighted = (syntax_highlight && match_indices.is_none())
+        .then(|| highlight::highlight(&sanitized, theme))
+        .flatten();
+
     let text = if show_line_numbers {
         // Add line numbers to each line
-        let lines: Vec<Line> = sanitized
-            .lines()
+        let body: Vec<Line> = match highlighted {
+            Some(lines) => lines,
+            None => sanitized
+                .lines()
+                .map(|line| Line::styled(line.to_string(), theme.normal()))
+                .collect(),
+        };
+        let lines: Vec<Line> = body
+            .into_iter()
             .enumerate()
             .map(|(i, line)| {
                 let line_num = format!("{:>4} │ ", i + 1);
                 let mut spans = vec![Span::styled(line_num, theme.muted())];
-                spans.push(Span::styled(line.to_string(), theme.normal()));
+                spans.extend(line.spans);
                 Line::from(spans)
             })
             .collect();
         Text::from(lines)
+    } else if let Some(lines) = highlighted {
+        Text::from(lines)
     } else if let Some(indices) = match_indices {
         create_highlighted_text(&sanitized, indices, theme)
     } else {
         Text::styled(sanitized, theme.normal())
     };
 
-    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll.min(u16::MAX as usize) as u16, 0));
 
     frame.render_widget(paragraph, area);
 }
 
+/// Estimate the wrapped display row holding char `offset` of `content` when
+/// wrapped at `width` columns. Word wrapping can push text a little further
+/// down than this, which only matters for centering, not visibility.
+fn wrapped_row_of(content: &str, offset: usize, width: u16) -> usize {
+    let width = width.max(1) as usize;
+    let mut row = 0;
+    let mut remaining = offset;
+    for line in content.split('\n') {
+        let len = line.chars().count();
+        if remaining <= len {
+            return row + remaining / width;
+        }
+        row += len.div_ceil(width).max(1);
+        remaining -= len + 1;
+    }
+    row
+}
+
 /// Scroll mode - horizontal scroll for long lines
 fn render_scroll_mode(
     frame: &mut Frame,
@@ -448,7 +501,7 @@ fn render_scroll_mode(
 
     // Show scroll position indicator at bottom
     if offset > 0 {
-        let indicator = format!("← {} cols", offset);
+        let indicator = tf("preview.cols_hidden", &[("count", &offset)]);
         let indicator_span = Span::styled(indicator, theme.muted());
         let indicator_para = Paragraph::new(indicator_span);
         if inner.height > 1 {
@@ -499,7 +552,7 @@ fn render_truncate_mode(
     // Add "more lines" indicator if truncated
     if total_lines > max_lines {
         let remaining = total_lines - max_lines;
-        let indicator = format!("... {} more lines", remaining);
+        let indicator = tf("preview.more_lines", &[("count", &remaining)]);
         display_lines.push(Line::from(Span::styled(indicator, theme.muted())));
     }
 
@@ -556,7 +609,7 @@ fn render_hex_mode(frame: &mut Frame, theme: &Theme, area: Rect, block: Block, c
 
     // Show total bytes indicator
     if bytes.len() > max_lines * bytes_per_line {
-        let indicator = format!("... {} total bytes", bytes.len());
+        let indicator = tf("preview.total_bytes", &[("count", &bytes.len())]);
         lines.push(Line::from(Span::styled(indicator, theme.muted())));
     }
 
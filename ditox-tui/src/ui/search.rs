@@ -1,25 +1,22 @@
 use crate::ui::theme::Theme;
 use ditox_core::app::{App, InputMode, SearchMode};
+use ditox_core::i18n::{t, tf};
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph};
 
 pub fn draw(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
     let search_style = match app.input_mode {
-        InputMode::Normal | InputMode::EditNote | InputMode::Confirm => theme.normal(),
+        InputMode::Normal | InputMode::EditNote | InputMode::EditTags | InputMode::Confirm => {
+            theme.normal()
+        }
         InputMode::Search => theme.accent(),
     };
 
     let cursor_char = match app.input_mode {
-        InputMode::Normal | InputMode::EditNote | InputMode::Confirm => "",
+        InputMode::Normal | InputMode::EditNote | InputMode::EditTags | InputMode::Confirm => "",
         InputMode::Search => "█",
     };
 
-    // Build search mode indicator
-    let mode_indicator = match app.search_mode {
-        SearchMode::Fuzzy => "",
-        SearchMode::Regex => "[regex] ",
-    };
-
     // Show match count or regex error when there's a search query
     let match_info = if let Some(err) = &app.regex_error {
         // Show truncated regex error
@@ -32,28 +29,33 @@ pub fn draw(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
     } else if !app.search_query.is_empty() {
         let count = app.filtered.len();
         if count == 1 {
-            " (1 match)".to_string()
+            format!(" ({})", t("search.one_match"))
         } else {
-            format!(" ({} matches)", count)
+            format!(" ({})", tf("search.matches", &[("count", &count)]))
         }
     } else {
         String::new()
     };
 
     let search_text = format!(
-        " {}Search: {}{}{}",
-        mode_indicator, app.search_query, cursor_char, match_info
+        "{}{}{}{}",
+        search_prefix(app),
+        app.search_query,
+        cursor_char,
+        match_info
     );
 
     let title = match app.input_mode {
-        InputMode::Normal => " Ditox ",
+        InputMode::Normal => "search.title",
         InputMode::Search => match app.search_mode {
-            SearchMode::Fuzzy => " Ditox (search) ",
-            SearchMode::Regex => " Ditox (regex search) ",
+            SearchMode::Fuzzy => "search.title_search",
+            SearchMode::Regex => "search.title_regex",
         },
-        InputMode::EditNote => " Ditox (editing note) ",
-        InputMode::Confirm => " Ditox (confirm) ",
+        InputMode::EditNote => "search.title_note",
+        InputMode::EditTags => "search.title_tags",
+        InputMode::Confirm => "search.title_confirm",
     };
+    let title = format!(" {} ", t(title));
 
     let search_bar = Paragraph::new(search_text).style(search_style).block(
         Block::default()
@@ -67,13 +69,9 @@ pub fn draw(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
 
     // Set cursor position when in search mode (with bounds checking)
     if app.input_mode == InputMode::Search {
-        // Calculate cursor X position: border(1) + " " + mode_indicator + "Search: " + query length
-        let mode_indicator_len = match app.search_mode {
-            SearchMode::Fuzzy => 0,
-            SearchMode::Regex => 8, // "[regex] "
-        };
-        // border(1) + " "(1) + mode_indicator + "Search: "(8) = 10 + mode_indicator
-        let cursor_offset = (10 + mode_indicator_len) as u16 + app.search_query.len() as u16;
+        // Cursor X position: border(1) + prefix + query
+        let cursor_offset =
+            (1 + search_prefix(app).chars().count() + app.search_query.chars().count()) as u16;
         let x = area.x.saturating_add(cursor_offset);
         let y = area.y.saturating_add(1);
 
@@ -85,3 +83,92 @@ pub fn draw(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
         }
     }
 }
+
+/// What the search bar shows before the query: mode indicator and label
+fn search_prefix(app: &App) -> String {
+    let mode_indicator = match app.search_mode {
+        SearchMode::Fuzzy => String::new(),
+        SearchMode::Regex => format!("{} ", t("search.regex_indicator")),
+    };
+    format!(" {}{}: ", mode_indicator, t("search.label"))
+}
+
+/// Render the tag completion popup just below the search bar, under the
+/// word being completed
+pub fn draw_tag_completions(frame: &mut Frame, app: &App, theme: &Theme, search_area: Rect) {
+    if app.input_mode != InputMode::Search || app.tag_completions.is_empty() {
+        return;
+    }
+    let area = frame.area();
+
+    // Tags come sorted by name, so sub-tags follow their parent; show them
+    // as an indented last level under it
+    let labels: Vec<String> = app
+        .tag_completions
+        .iter()
+        .enumerate()
+        .map(|(i, (name, count))| {
+            let label = match name.rsplit_once('/') {
+                Some((parent, leaf))
+                    if app.tag_completions[..i].iter().any(|(n, _)| n == parent) =>
+                {
+                    format!("{}{}", "  ".repeat(name.matches('/').count()), leaf)
+                }
+                _ => name.clone(),
+            };
+            format!(" {} ({})", label, count)
+        })
+        .collect();
+
+    let items: Vec<ListItem> = labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| {
+            let style = if i == app.tag_completion_selected {
+                theme.selected()
+            } else {
+                theme.normal()
+            };
+            ListItem::new(label.as_str()).style(style)
+        })
+        .collect();
+
+    let content_width = labels
+        .iter()
+        .map(|label| label.chars().count() + 1)
+        .max()
+        .unwrap_or(0) as u16;
+    let width = (content_width + 2).max(16).min(area.width);
+    let height = (items.len() as u16 + 2).min(area.height.saturating_sub(search_area.bottom()));
+    if height < 3 {
+        return;
+    }
+
+    // Align with the start of the last word in the query: the bar's left
+    // border, then the text rendered before it
+    let word_start = app
+        .search_query
+        .rfind(char::is_whitespace)
+        .map_or(0, |i| i + 1);
+    let before_word =
+        search_prefix(app).chars().count() + app.search_query[..word_start].chars().count();
+    let x = (search_area.x + 1 + before_word as u16).min(area.right().saturating_sub(width));
+
+    let popup_area = Rect {
+        x,
+        y: search_area.bottom(),
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(
+        List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.border())
+                .title(format!(" {} ", t("search.tag_completions")))
+                .title_style(theme.title()),
+        ),
+        popup_area,
+    );
+}
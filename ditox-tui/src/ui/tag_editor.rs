@@ -0,0 +1,83 @@
+//! Tag editor modal widget
+
+use crate::ui::theme::Theme;
+use ditox_core::app::App;
+use ditox_core::i18n::t;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Render the tag editor modal for the entry being tagged
+pub fn render(frame: &mut Frame, app: &App, theme: &Theme) {
+    let area = frame.area();
+
+    // Current tags, the input line, then one line per completion
+    let modal_width = (area.width * 60 / 100).clamp(40, 80);
+    let modal_height = (5 + app.tag_completions.len() as u16).min(area.height);
+
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect::new(modal_x, modal_y, modal_width, modal_height);
+
+    // Clear the area behind the modal
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", t("tags.title")))
+        .borders(Borders::ALL)
+        .border_style(theme.border())
+        .title_style(theme.title());
+
+    let tags_line = if app.editing_tags.is_empty() {
+        Line::styled(t("tags.none"), theme.muted())
+    } else {
+        let spans: Vec<Span> = app
+            .editing_tags
+            .iter()
+            .flat_map(|tag| {
+                [
+                    Span::styled(format!("#{}", tag), theme.accent()),
+                    Span::raw("  "),
+                ]
+            })
+            .collect();
+        Line::from(spans)
+    };
+
+    let mut lines = vec![
+        tags_line,
+        Line::raw(""),
+        Line::styled(format!("> {}█", app.tag_input), theme.normal()),
+    ];
+    lines.extend(
+        app.tag_completions
+            .iter()
+            .enumerate()
+            .map(|(i, (name, count))| {
+                let style = if i == app.tag_completion_selected {
+                    theme.selected()
+                } else {
+                    theme.muted()
+                };
+                Line::styled(format!("  {} ({})", name, count), style)
+            }),
+    );
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(paragraph, modal_area);
+
+    // Render help text below the modal
+    let help_area = Rect::new(modal_x, modal_y + modal_height, modal_width, 1);
+
+    if help_area.y < area.height {
+        let help = Paragraph::new(t("tags.hint"))
+            .style(theme.muted())
+            .alignment(Alignment::Center);
+        frame.render_widget(help, help_area);
+    }
+}
@@ -1,5 +1,13 @@
-use ditox_core::config::ThemeConfig;
-use ratatui::style::{Color, Modifier, Style};
+use ditox_core::config::{ColorDepth, Config, ThemeConfig};
+use ditox_core::error::Result;
+use ditox_core::i18n::{t, tf};
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the config file is checked for theme changes
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
 pub struct Theme {
     pub bg: Color,
@@ -9,6 +17,8 @@ pub struct Theme {
     pub border: Color,
     pub muted: Color,
     pub accent: Color,
+    /// Colors the terminal shows; every color is fitted to it
+    pub depth: ColorDepth,
 }
 
 impl Default for Theme {
@@ -21,20 +31,24 @@ impl Default for Theme {
             border: Color::Rgb(86, 95, 137),
             muted: Color::Rgb(86, 95, 137),
             accent: Color::Rgb(122, 162, 247),
+            depth: ColorDepth::TrueColor,
         }
     }
 }
 
 impl Theme {
     pub fn from_config(config: &ThemeConfig) -> Self {
+        let depth = config.color_depth.resolve();
+        let fit = |color| fit_color(color, depth);
         Self {
             bg: Color::Reset,
-            fg: parse_color(&config.text).unwrap_or(Color::White),
-            selected_bg: parse_color(&config.selected).unwrap_or(Color::Rgb(59, 66, 97)),
-            selected_fg: Color::Rgb(192, 202, 245),
-            border: parse_color(&config.border).unwrap_or(Color::Rgb(86, 95, 137)),
-            muted: parse_color(&config.muted).unwrap_or(Color::Rgb(86, 95, 137)),
-            accent: Color::Rgb(122, 162, 247),
+            fg: fit(parse_color(&config.text).unwrap_or(Color::White)),
+            selected_bg: fit(parse_color(&config.selected).unwrap_or(Color::Rgb(59, 66, 97))),
+            selected_fg: fit(Color::Rgb(192, 202, 245)),
+            border: fit(parse_color(&config.border).unwrap_or(Color::Rgb(86, 95, 137))),
+            muted: fit(parse_color(&config.muted).unwrap_or(Color::Rgb(86, 95, 137))),
+            accent: fit(Color::Rgb(122, 162, 247)),
+            depth,
         }
     }
 
@@ -74,6 +88,27 @@ impl Theme {
             .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
     }
 
+    /// Syntax colors for the preview pane
+    pub fn syntax_key(&self) -> Style {
+        Style::default().fg(fit_color(Color::Rgb(125, 207, 255), self.depth))
+    }
+
+    pub fn syntax_string(&self) -> Style {
+        Style::default().fg(fit_color(Color::Rgb(158, 206, 106), self.depth))
+    }
+
+    pub fn syntax_number(&self) -> Style {
+        Style::default().fg(fit_color(Color::Rgb(255, 158, 100), self.depth))
+    }
+
+    pub fn syntax_keyword(&self) -> Style {
+        Style::default().fg(fit_color(Color::Rgb(187, 154, 247), self.depth))
+    }
+
+    pub fn syntax_comment(&self) -> Style {
+        self.muted().add_modifier(Modifier::ITALIC)
+    }
+
     /// Style for highlighted search matches in selected row
     pub fn highlight_selected(&self) -> Style {
         Style::default()
@@ -83,6 +118,156 @@ impl Theme {
     }
 }
 
+/// Re-reads the theme when the config file changes, so a theme can be
+/// tuned without restarting
+pub struct ThemeReloader {
+    path: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    last_check: Instant,
+}
+
+impl ThemeReloader {
+    pub fn new() -> Self {
+        let path = Config::get_config_path().ok();
+        let modified = path.as_deref().and_then(modified_time);
+        Self {
+            path,
+            modified,
+            last_check: Instant::now(),
+        }
+    }
+
+    /// The reloaded theme if the config file changed since the last check
+    pub fn poll(&mut self) -> Option<Result<Theme>> {
+        if self.last_check.elapsed() < RELOAD_CHECK_INTERVAL {
+            return None;
+        }
+        self.last_check = Instant::now();
+        let modified = self.path.as_deref().and_then(modified_time);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(Config::load().map(|config| Theme::from_config(&config.ui.theme)))
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Theme inspector (`ditox pick --theme-edit`): name the `[ui.theme]` keys
+/// the cell at `(x, y)` is drawn with, on the bottom row
+pub fn draw_inspector(frame: &mut Frame, theme: &Theme, (x, y): (u16, u16)) {
+    let area = frame.area();
+    if !area.contains(Position::new(x, y)) {
+        return;
+    }
+    let cell = &frame.buffer_mut()[(x, y)];
+    let (fg, bg) = (cell.fg, cell.bg);
+
+    let mut keys = Vec::new();
+    for (key, color, matches) in [
+        ("text", theme.fg, fg == theme.fg),
+        ("selected", theme.selected_bg, bg == theme.selected_bg),
+        ("border", theme.border, fg == theme.border),
+        ("muted", theme.muted, fg == theme.muted),
+    ] {
+        if matches {
+            keys.push(format!("ui.theme.{} = {}", key, color_name(color)));
+        }
+    }
+    let described = if keys.is_empty() {
+        tf("inspect.builtin", &[("color", &color_name(fg))])
+    } else {
+        keys.join(" │ ")
+    };
+    let text = format!(
+        " {} │ {}",
+        tf("inspect.cell", &[("x", &x), ("y", &y)]),
+        described
+    );
+
+    let row = Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1);
+    frame.render_widget(Paragraph::new(text).style(theme.selected()), row);
+}
+
+fn color_name(color: Color) -> String {
+    match color {
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        Color::Reset => t("inspect.default_color"),
+        other => format!("{:?}", other).to_lowercase(),
+    }
+}
+
+/// xterm's 16 ANSI colors, in palette order
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the 6x6x6 cube in the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The nearest color to `color` that a terminal with `depth` can show.
+/// Only RGB colors change.
+fn fit_color(color: Color, depth: ColorDepth) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    match depth {
+        ColorDepth::Auto | ColorDepth::TrueColor => color,
+        ColorDepth::Ansi256 => Color::Indexed(nearest_256((r, g, b))),
+        ColorDepth::Ansi16 => ANSI16
+            .iter()
+            .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+            .map_or(color, |(ansi, _)| *ansi),
+    }
+}
+
+/// Index of the nearest cube or grayscale entry of the 256-color palette
+fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+    let level = |c: u8| {
+        (0..6)
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - c as i32).abs())
+            .unwrap_or(0)
+    };
+    let (r, g, b) = (level(rgb.0), level(rgb.1), level(rgb.2));
+    let cube = (CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+    let cube_index = 16 + 36 * r as u8 + 6 * g as u8 + b as u8;
+
+    // Grays 232..=255 run from 8 to 238 in steps of 10
+    let avg = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let step = (avg.saturating_sub(3) / 10).min(23) as u8;
+    let gray_value = 8 + 10 * step;
+    let gray = (gray_value, gray_value, gray_value);
+
+    if distance(gray, rgb) < distance(cube, rgb) {
+        232 + step
+    } else {
+        cube_index
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
 fn parse_color(s: &str) -> Option<Color> {
     if s.starts_with('#') && s.len() == 7 {
         let r = u8::from_str_radix(&s[1..3], 16).ok()?;
@@ -93,3 +278,43 @@ fn parse_color(s: &str) -> Option<Color> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truecolor_keeps_rgb() {
+        let color = Color::Rgb(122, 162, 247);
+        assert_eq!(fit_color(color, ColorDepth::TrueColor), color);
+    }
+
+    #[test]
+    fn test_256_uses_cube_and_grays() {
+        assert_eq!(
+            fit_color(Color::Rgb(255, 0, 0), ColorDepth::Ansi256),
+            Color::Indexed(196)
+        );
+        assert_eq!(
+            fit_color(Color::Rgb(128, 128, 128), ColorDepth::Ansi256),
+            Color::Indexed(244)
+        );
+        assert_eq!(
+            fit_color(Color::Rgb(122, 162, 247), ColorDepth::Ansi256),
+            Color::Indexed(111)
+        );
+    }
+
+    #[test]
+    fn test_16_picks_nearest_ansi() {
+        assert_eq!(
+            fit_color(Color::Rgb(250, 10, 10), ColorDepth::Ansi16),
+            Color::LightRed
+        );
+        assert_eq!(
+            fit_color(Color::Rgb(30, 30, 30), ColorDepth::Ansi16),
+            Color::Black
+        );
+        assert_eq!(fit_color(Color::Yellow, ColorDepth::Ansi16), Color::Yellow);
+    }
+}
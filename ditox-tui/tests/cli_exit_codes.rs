@@ -0,0 +1,26 @@
+//! Exit codes of the real `ditox` binary for command-line mistakes
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn ditox(dir: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("ditox").unwrap();
+    cmd.env("XDG_DATA_HOME", dir.path())
+        .env("XDG_CONFIG_HOME", dir.path())
+        .env("DITOX_DATA_DIR", dir.path());
+    cmd
+}
+
+#[test]
+fn invalid_arguments_exit_with_usage_code() {
+    let dir = TempDir::new().unwrap();
+    ditox(&dir)
+        .args(["copy", "--recent", "0"])
+        .assert()
+        .code(64);
+    ditox(&dir)
+        .args(["copy", "--recent", "x"])
+        .assert()
+        .code(64);
+    ditox(&dir).args(["no-such-command"]).assert().code(64);
+}
@@ -58,7 +58,7 @@ in {
       };
 
       Service = {
-        Type = "simple";
+        Type = "notify";
         ExecStart = "${cfg.package}/bin/ditox watch";
         Restart = "on-failure";
         RestartSec = 5;